@@ -3,16 +3,152 @@
 
 /* auto-generated by NAPI-RS */
 
+/** Throughput of one operation on the running backend */
+export interface SpeedMeasurement {
+  /** Operation that was timed */
+  operation: string
+  /** Number of timed iterations */
+  iterations: number
+  /** Completed operations per second */
+  opsPerSec: number
+  /** `backend_kind()` of the module that took the measurement */
+  backend: string
+}
+/**
+ * Time an operation and report its throughput
+ *
+ * Keys and inputs are prepared before timing starts, so only the named
+ * operation is measured.
+ *
+ * # Arguments
+ * * `operation` - One of "mlkem768-keygen", "mlkem768-encapsulate",
+ *   "mlkem768-decapsulate", "mldsa65-sign", "mldsa65-verify" or "blake3-1kib"
+ * * `iterations` - Number of timed runs (at least 1)
+ *
+ * # Returns
+ * SpeedMeasurement with the ops/sec figure and the backend it was taken on
+ */
+export declare function measureSpeedup(operation: string, iterations: number): SpeedMeasurement
 /** Key pair containing public and secret keys */
 export interface KeyPair {
   publicKey: Buffer
   secretKey: Buffer
 }
+/**
+ * Key pair returned as standalone `Uint8Array`s
+ *
+ * Each array is backed by its own native allocation rather than Node's
+ * shared `Buffer` pool, so storing it long-term can't alias other data.
+ */
+export interface OwnedKeyPair {
+  /** Public key bytes */
+  publicKey: Uint8Array
+  /** Secret key bytes */
+  secretKey: Uint8Array
+}
 /** Encapsulated secret containing ciphertext and shared secret */
 export interface EncapsulatedSecret {
   ciphertext: Buffer
   sharedSecret: Buffer
 }
+/** Encapsulated secret together with the random coins used to produce it */
+export interface CoinsEncapsulatedSecret {
+  /** ML-KEM-768 ciphertext (1088 bytes) */
+  ciphertext: Buffer
+  /** Shared secret (32 bytes) */
+  sharedSecret: Buffer
+  /** Encapsulation randomness `m` from FIPS 203 (32 bytes) */
+  coins: Buffer
+}
+/** ML-KEM-768 keypair together with its FIPS 203 key generation seed */
+export interface SeededKeyPair {
+  /** Public key (1184 bytes) */
+  publicKey: Buffer
+  /** Expanded secret key (2400 bytes) */
+  secretKey: Buffer
+  /** Seed `d || z` (64 bytes); regenerates both keys via `mlkem768_expand_secret_key` */
+  seed: Buffer
+}
+/** Non-sensitive description of a secret key, safe to log */
+export interface KeyMetadata {
+  /** Algorithm inferred from the key length ("ML-KEM-768" or "ML-DSA-65") */
+  algorithm: string
+  /** Secret key length in bytes */
+  length: number
+  /**
+   * `quantum_fingerprint` of the corresponding public key, when it can be
+   * recovered from the secret key (ML-KEM only)
+   */
+  publicKeyFingerprint?: string
+}
+/** One signature check for `all_signatures_valid` */
+export interface VerifyItem {
+  /** Signed message */
+  message: Buffer
+  /** ML-DSA-65 signature (3309 bytes) */
+  signature: Buffer
+  /** ML-DSA-65 public key (1952 bytes) */
+  publicKey: Buffer
+}
+/** One digest check for `blake3_verify_many` */
+export interface DigestCheck {
+  /** Data to hash */
+  data: Buffer
+  /** Expected BLAKE3 digest (32 bytes) */
+  expected: Buffer
+}
+/** Result of `mldsa65_verify_verbose` and `signature_binds_message` */
+export interface VerifyReport {
+  /** Whether the signature is valid */
+  valid: boolean
+  /**
+   * Why verification failed: "bad public key length", "bad signature
+   * length" or "signature does not verify"; absent when valid
+   */
+  reason?: string
+  /**
+   * Likely cause, set by `signature_binds_message`: "bound",
+   * "key_mismatch", "corrupt_signature" or "message_or_key_mismatch"
+   */
+  diagnosis?: string
+}
+/** Decoded quantum fingerprint */
+export interface ParsedFingerprint {
+  /**
+   * Digest algorithm: "blake3" for `qf:<hex>`, "shake256" for `qf:sk:<hex>`,
+   * "sha3-256" for `qf:s3:<hex>`
+   */
+  algorithm: string
+  /** Raw digest bytes */
+  digest: Buffer
+}
+/** Capability entry returned by `supported_algorithms` */
+export interface AlgorithmStatus {
+  /** Algorithm identifier, as accepted by algorithm-dispatch functions */
+  name: string
+  /** "kem", "signature" or "hash" */
+  kind: string
+  /** Whether the implementation is real rather than a placeholder */
+  ready: boolean
+}
+/**
+ * Algorithm identifiers accepted by algorithm-dispatch functions
+ *
+ * Exposed to TypeScript as a union of the string values below, so invalid
+ * names are rejected when arguments are converted.
+ */
+export const enum Algorithm {
+  /** ML-KEM-512 (FIPS 203) */
+  MlKem512 = 'ml-kem-512',
+  /** ML-KEM-768 (FIPS 203) */
+  MlKem768 = 'ml-kem-768',
+  /** ML-DSA-65 (FIPS 204) */
+  MlDsa65 = 'ml-dsa-65',
+  /** BLAKE3 hash */
+  Blake3 = 'blake3',
+  /** SHA3-256 hash (FIPS 202) */
+  Sha3_256 = 'sha3-256'
+}
 /**
  * Generate a new ML-KEM-768 keypair
  *
@@ -27,6 +163,73 @@ export interface EncapsulatedSecret {
  * ```
  */
 export declare function mlkem768GenerateKeypair(): KeyPair
+/**
+ * Generate a new ML-KEM-768 keypair as non-pooled `Uint8Array`s
+ *
+ * Same keys as `mlkem768_generate_keypair`, but each array owns a freshly
+ * allocated, externally managed `ArrayBuffer`. Prefer this when keys are
+ * kept around, since small pooled `Buffer`s share memory with unrelated
+ * allocations.
+ *
+ * # Returns
+ * OwnedKeyPair with a 1184-byte public key and a 2400-byte secret key
+ */
+export declare function mlkem768GenerateKeypairOwned(): OwnedKeyPair
+/**
+ * Generate an ML-KEM-768 keypair and return the 64-byte seed it came from
+ *
+ * FIPS 203 allows storing a secret key either expanded (2400 bytes) or as
+ * the 64-byte `d || z` seed. The seed cannot be recovered from an expanded
+ * key (`d` only enters key generation through a hash), so a compact
+ * backup is only possible when the seed is kept at generation time, which
+ * is what this function is for.
+ *
+ * # Returns
+ * SeededKeyPair with the public key, expanded secret key and seed
+ */
+export declare function mlkem768GenerateKeypairWithSeed(): SeededKeyPair
+/**
+ * Regenerate the expanded ML-KEM-768 secret key from its 64-byte seed
+ *
+ * # Arguments
+ * * `seed` - Seed `d || z` (64 bytes) from `mlkem768_generate_keypair_with_seed`
+ *
+ * # Returns
+ * Expanded 2400-byte secret key, identical to the one generated from the seed
+ */
+export declare function mlkem768ExpandSecretKey(seed: Buffer): Buffer
+/**
+ * Derive a deterministic ML-KEM-768 keypair for a context from a master seed
+ *
+ * Expands `master_seed` with BLAKE3 in `derive_key` mode using `context`
+ * as the context string, reading 64 bytes of output as the FIPS 203 `d || z`
+ * key generation seed. The same master seed and context always give the
+ * same keypair; different contexts give independent keypairs, so one
+ * backed-up master seed can recover a whole hierarchy of identities.
+ *
+ * # Arguments
+ * * `master_seed` - Secret master seed (at least 32 bytes)
+ * * `context` - Hardcoded, globally unique context string for this keypair
+ *
+ * # Returns
+ * Derived ML-KEM-768 KeyPair
+ */
+export declare function mlkem768DeriveKeypair(masterSeed: Buffer, context: string): KeyPair
+/**
+ * Fixed ML-KEM-768 keypair for documentation and examples
+ *
+ * **INSECURE: never use this keypair for real data.** It is generated from
+ * a published seed (`d || z` = the bytes `0x00..=0x3f`), so anyone can
+ * recompute the secret key. It exists so examples and doctests can assert
+ * concrete outputs; its public key fingerprint is
+ * `qf:cc4b207d7b69fc89154fcfc6f0b96f989939873391969bc1fe2eea03e6f389aa`.
+ * There is no ML-DSA-65 counterpart: signing keys come from
+ * `pqcrypto-dilithium`, which has no seeded key generation.
+ *
+ * # Returns
+ * The same ML-KEM-768 KeyPair on every call
+ */
+export declare function exampleKeypair(): KeyPair
 /**
  * Encapsulate a shared secret using a public key
  *
@@ -39,6 +242,56 @@ export declare function mlkem768GenerateKeypair(): KeyPair
  * - Shared secret: 32 bytes
  */
 export declare function mlkem768Encapsulate(publicKey: Buffer): EncapsulatedSecret
+/**
+ * Encapsulate to many public keys in parallel
+ *
+ * Runs on a dedicated rayon thread pool so a large batch doesn't compete
+ * with the global pool. Results are returned in input order.
+ *
+ * # Arguments
+ * * `public_keys` - ML-KEM-768 public keys (1184 bytes each)
+ * * `threads` - Thread pool size, at most 256; 0 uses rayon's default (one
+ *   per CPU)
+ *
+ * # Returns
+ * One EncapsulatedSecret per public key; errors name the first bad key's index
+ */
+export declare function mlkem768EncapsulateBatchParallel(publicKeys: Array<Buffer>, threads: number): Array<EncapsulatedSecret>
+/**
+ * Encapsulate a shared secret and return the randomness that was used
+ *
+ * Intended for regulated environments that must log encapsulation
+ * randomness. Passing the returned coins to
+ * `mlkem768_encapsulate_deterministic` reproduces the same ciphertext and
+ * shared secret, so the coins must be protected like the shared secret.
+ *
+ * # Arguments
+ * * `public_key` - ML-KEM-768 public key (1184 bytes)
+ *
+ * # Returns
+ * CoinsEncapsulatedSecret containing:
+ * - Ciphertext: 1088 bytes
+ * - Shared secret: 32 bytes
+ * - Coins: 32 bytes
+ */
+export declare function mlkem768EncapsulateWithCoins(publicKey: Buffer): CoinsEncapsulatedSecret
+/**
+ * Encapsulate a shared secret using caller-supplied randomness
+ *
+ * The same public key and coins always produce the same ciphertext and
+ * shared secret. Only use this to replay an audited encapsulation or for
+ * known-answer tests; fresh encapsulations must use `mlkem768_encapsulate`.
+ *
+ * # Arguments
+ * * `public_key` - ML-KEM-768 public key (1184 bytes)
+ * * `coins` - Encapsulation randomness (32 bytes)
+ *
+ * # Returns
+ * EncapsulatedSecret containing:
+ * - Ciphertext: 1088 bytes
+ * - Shared secret: 32 bytes
+ */
+export declare function mlkem768EncapsulateDeterministic(publicKey: Buffer, coins: Buffer): EncapsulatedSecret
 /**
  * Decapsulate a shared secret using a secret key
  *
@@ -50,6 +303,158 @@ export declare function mlkem768Encapsulate(publicKey: Buffer): EncapsulatedSecr
  * Shared secret (32 bytes)
  */
 export declare function mlkem768Decapsulate(ciphertext: Buffer, secretKey: Buffer): Buffer
+/**
+ * Decapsulate a ciphertext embedded in a larger buffer
+ *
+ * Reads the 1088-byte ciphertext at `buffer[offset..offset + 1088]`
+ * directly, so framed messages need no `subarray()` on the JS side.
+ *
+ * # Arguments
+ * * `buffer` - Buffer containing the ciphertext
+ * * `offset` - Byte offset of the ciphertext within `buffer`
+ * * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+ *
+ * # Returns
+ * Shared secret (32 bytes)
+ */
+export declare function mlkem768DecapsulateAt(buffer: Buffer, offset: number, secretKey: Buffer): Buffer
+/**
+ * Check whether two ciphertexts encapsulate the same shared secret
+ *
+ * Decapsulates both ciphertexts and compares the secrets in constant time;
+ * neither secret leaves native code. Useful for detecting replayed
+ * encapsulations in a store. Because ML-KEM uses implicit rejection, a
+ * malformed ciphertext still decapsulates (to a pseudorandom secret) and
+ * simply compares unequal.
+ *
+ * # Arguments
+ * * `ct_a` - First ML-KEM-768 ciphertext (1088 bytes)
+ * * `ct_b` - Second ML-KEM-768 ciphertext (1088 bytes)
+ * * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+ *
+ * # Returns
+ * true if both ciphertexts yield the same shared secret
+ */
+export declare function ciphertextsShareSecret(ctA: Buffer, ctB: Buffer, secretKey: Buffer): boolean
+/**
+ * Check whether a ciphertext was encapsulated to this secret key's public key
+ *
+ * ML-KEM decapsulation re-encrypts the recovered message and, if the result
+ * differs from the ciphertext, returns the implicit-rejection secret
+ * `K̄ = SHAKE256(z || ciphertext)` instead of failing. This computes K̄ from
+ * the secret key's `z` and reports whether decapsulation took the
+ * reject path.
+ *
+ * Limitations: it needs the recipient's secret key, so it can't be used by
+ * third parties; it says nothing about who made the ciphertext or whether
+ * it is a replay; and a ciphertext for another key only shows up as "not
+ * for this key", without identifying the intended recipient. The chance of
+ * a valid ciphertext's secret colliding with K̄ is negligible (2^-256).
+ *
+ * # Arguments
+ * * `ciphertext` - ML-KEM-768 ciphertext (1088 bytes)
+ * * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+ *
+ * # Returns
+ * true if the ciphertext decapsulates normally under this key
+ */
+export declare function ciphertextMatchesKey(ciphertext: Buffer, secretKey: Buffer): boolean
+/**
+ * Encapsulate a shared secret bound to a channel identifier
+ *
+ * The returned shared secret is derived from the raw ML-KEM secret and
+ * `channel_id`, so a ciphertext replayed on a different channel yields an
+ * unrelated secret. The peer must use `mlkem768_decapsulate_bound` with the
+ * same channel id.
+ *
+ * # Arguments
+ * * `public_key` - ML-KEM-768 public key (1184 bytes)
+ * * `channel_id` - Channel identifier (any length)
+ *
+ * # Returns
+ * EncapsulatedSecret with the channel-bound 32-byte shared secret
+ */
+export declare function mlkem768EncapsulateBound(publicKey: Buffer, channelId: Buffer): EncapsulatedSecret
+/**
+ * Decapsulate a shared secret produced by `mlkem768_encapsulate_bound`
+ *
+ * # Arguments
+ * * `ciphertext` - ML-KEM-768 ciphertext (1088 bytes)
+ * * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+ * * `channel_id` - Channel identifier used by the encapsulating side
+ *
+ * # Returns
+ * Channel-bound shared secret (32 bytes)
+ */
+export declare function mlkem768DecapsulateBound(ciphertext: Buffer, secretKey: Buffer, channelId: Buffer): Buffer
+/**
+ * Check that an ML-KEM-768 keypair is internally consistent
+ *
+ * Derives the public key embedded in the secret key and compares it with
+ * the stored public key, then runs a test encapsulation/decapsulation to
+ * confirm the pair works. Use this before trusting an imported keypair.
+ *
+ * # Arguments
+ * * `kp` - ML-KEM-768 keypair
+ *
+ * # Returns
+ * true if the keys form a working pair, false on any mismatch
+ */
+export declare function mlkem768ValidateKeypair(kp: KeyPair): boolean
+/**
+ * Convert an ML-KEM-768 public key between serialization layouts
+ *
+ * Supported layouts are "fips203" (the FIPS 203 `ByteEncode12(t) || rho`
+ * encoding) and "raw" (the bytes produced by the `ml-kem` crate). The
+ * `ml-kem` crate serializes keys in exactly the FIPS 203 layout, so every
+ * supported conversion is an identity on the bytes. The key is still
+ * validated: it must be 1184 bytes and pass the FIPS 203 modulus check
+ * (decoding and re-encoding must reproduce the input).
+ *
+ * # Arguments
+ * * `key` - ML-KEM-768 public key in the `from` layout
+ * * `from` - Source layout ("fips203" or "raw")
+ * * `to` - Target layout ("fips203" or "raw")
+ *
+ * # Returns
+ * Public key in the `to` layout
+ */
+export declare function mlkem768PublicKeyReencode(key: Buffer, from: string, to: string): Buffer
+/**
+ * Describe a secret key without exposing any of its bytes
+ *
+ * For logging and telemetry. The returned metadata never contains secret
+ * material: only the algorithm, the length, and a fingerprint of the public
+ * key. ML-KEM-768 secret keys embed their public key, so its fingerprint is
+ * always available; an ML-DSA-65 secret key does not contain the public key
+ * (recovering it needs the full signing-key expansion, which
+ * `pqcrypto-dilithium` does not expose), so the fingerprint is omitted.
+ *
+ * # Arguments
+ * * `secret_key` - ML-KEM-768 (2400 bytes) or ML-DSA-65 (4032 bytes) secret key
+ *
+ * # Returns
+ * KeyMetadata for the key
+ */
+export declare function secretKeyMetadata(secretKey: Buffer): KeyMetadata
+/**
+ * Check that a secret key belongs to a published public key fingerprint
+ *
+ * For restoring backups: the public key is recovered from the ML-KEM-768
+ * secret key, fingerprinted, and compared with `expected_fp` as
+ * `fingerprint_equals_data` does (constant time, case-insensitive, `qf:`
+ * prefix optional), so SHAKE256 fingerprints shorter than 32 bytes never
+ * match. ML-DSA-65 secret keys do not embed their public key (see
+ * `secret_key_metadata`) and are rejected with an error.
+ *
+ * # Arguments
+ * * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+ * * `expected_fp` - Published fingerprint of the public key
+ *
+ * # Returns
+ * true if the secret key's public key has this fingerprint
+ */
+export declare function verifySecretMatchesFingerprint(secretKey: Buffer, expectedFp: string): boolean
 /**
  * Generate a new ML-DSA-65 keypair
  *
@@ -64,9 +469,37 @@ export declare function mlkem768Decapsulate(ciphertext: Buffer, secretKey: Buffe
  * ```
  */
 export declare function mldsa65GenerateKeypair(): KeyPair
+/**
+ * Largest message `mldsa65_sign` accepts, in bytes
+ *
+ * # Returns
+ * Current limit; 1 MiB unless changed with `set_max_direct_sign_bytes`
+ */
+export declare function maxDirectSignBytes(): number
+/**
+ * Change the largest message `mldsa65_sign` accepts
+ *
+ * **This is a single process-wide setting, not a per-call option.** Every
+ * caller in the process shares it, including other libraries that load
+ * this module, and any of them can change it at any time; set it once at
+ * startup and don't rely on it as a security boundary. It also bounds the
+ * wrappers that sign the message directly: `mldsa65_sign_to_sig_format`
+ * and `mldsa65_sign_attached`.
+ *
+ * Larger inputs should be signed in pre-hash mode with `StreamSigner`,
+ * `mldsa65_sign_stream` or `mldsa65_sign_file`, which never hold the whole
+ * message at once. Pass `u32::MAX` (4294967295) to disable the check.
+ *
+ * # Arguments
+ * * `limit` - New limit in bytes
+ */
+export declare function setMaxDirectSignBytes(limit: number): void
 /**
  * Sign a message with ML-DSA-65
  *
+ * Messages longer than `max_direct_sign_bytes` (1 MiB by default) are
+ * rejected; sign those with `StreamSigner` or `mldsa65_sign_stream`.
+ *
  * # Arguments
  * * `message` - Message to sign
  * * `secret_key` - ML-DSA-65 secret key (4032 bytes)
@@ -87,6 +520,155 @@ export declare function mldsa65Sign(message: Buffer, secretKey: Buffer): Buffer
  * true if signature is valid, false otherwise
  */
 export declare function mldsa65Verify(message: Buffer, signature: Buffer, publicKey: Buffer): boolean
+/**
+ * Verify an ML-DSA-65 signature and report why it failed
+ *
+ * A debugging aid: unlike `mldsa65_verify` it never throws, and a failure
+ * carries a reason distinguishing malformed inputs from a signature that
+ * simply does not verify.
+ *
+ * # Arguments
+ * * `message` - Original message
+ * * `signature` - Signature to verify (3309 bytes)
+ * * `public_key` - ML-DSA-65 public key (1952 bytes)
+ *
+ * # Returns
+ * VerifyReport with `valid` and, on failure, a `reason`
+ */
+export declare function mldsa65VerifyVerbose(message: Buffer, signature: Buffer, publicKey: Buffer): VerifyReport
+/**
+ * Verify an ML-DSA-65 signature and diagnose a signer/message mismatch
+ *
+ * Runs the same verification as `mldsa65_verify_verbose`, then adds the
+ * likely cause as `diagnosis`:
+ * - "bound": the signature verifies for this message and key
+ * - "key_mismatch": the key is not an ML-DSA-65 public key, e.g. an
+ *   ML-KEM key or an all-zero placeholder passed by mistake
+ * - "corrupt_signature": the signature has the wrong length or is all zeros
+ * - "message_or_key_mismatch": a well-formed signature that does not verify
+ *
+ * An ML-DSA signature carries nothing about the message or the key beyond
+ * what verification checks, so a substituted message and a signature from
+ * a different genuine ML-DSA-65 key fail identically and both end up in
+ * "message_or_key_mismatch". To narrow that case down, verify against
+ * other candidate keys or the original message.
+ *
+ * # Arguments
+ * * `message` - Message the signature is claimed to cover
+ * * `signature` - Signature to check
+ * * `public_key` - Claimed signer's public key
+ *
+ * # Returns
+ * VerifyReport with `valid`, `reason` on failure, and `diagnosis`
+ */
+export declare function signatureBindsMessage(message: Buffer, signature: Buffer, publicKey: Buffer): VerifyReport
+/**
+ * Sign a list of messages with a single ML-DSA-65 signature
+ *
+ * ML-DSA has no native aggregation, so this signs a BLAKE3 digest of the
+ * length-prefixed messages instead. The signature commits to the exact
+ * messages and their order; it is not a true aggregate signature. Only the
+ * 32-byte digest is signed, so `max_direct_sign_bytes` does not limit the
+ * total size of the messages.
+ *
+ * # Arguments
+ * * `messages` - Messages to commit to, in order
+ * * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+ *
+ * # Returns
+ * Signature (3309 bytes)
+ */
+export declare function mldsa65SignMulti(messages: Array<Buffer>, secretKey: Buffer): Buffer
+/**
+ * Verify a signature produced by `mldsa65_sign_multi`
+ *
+ * # Arguments
+ * * `messages` - Messages in the same order they were signed
+ * * `signature` - Signature to verify (3309 bytes)
+ * * `public_key` - ML-DSA-65 public key (1952 bytes)
+ *
+ * # Returns
+ * true if the signature covers exactly these messages in this order
+ */
+export declare function mldsa65VerifyMulti(messages: Array<Buffer>, signature: Buffer, publicKey: Buffer): boolean
+/**
+ * Verify an ML-DSA-65 signature from a pinned signer
+ *
+ * Checks that `quantum_fingerprint(public_key)` equals `expected_fp`
+ * (compared in constant time) before verifying the signature, so a valid
+ * signature under some other key is rejected in the same call.
+ *
+ * # Arguments
+ * * `message` - Original message
+ * * `signature` - Signature to verify (3309 bytes)
+ * * `public_key` - ML-DSA-65 public key (1952 bytes)
+ * * `expected_fp` - Pinned signer fingerprint, "qf:<hex>" as produced by `quantum_fingerprint`
+ *
+ * # Returns
+ * true only if the key matches the fingerprint and the signature verifies
+ */
+export declare function mldsa65VerifyWithExpectedFingerprint(message: Buffer, signature: Buffer, publicKey: Buffer, expectedFp: string): boolean
+/**
+ * Check that every signature in a set is valid
+ *
+ * Every item is verified, even after one fails, and the results are
+ * combined without branching, so the running time doesn't reveal which
+ * signature was bad. A malformed item (wrong key or signature length)
+ * counts as invalid rather than throwing.
+ *
+ * # Arguments
+ * * `items` - Message, signature and public key triples
+ *
+ * # Returns
+ * true only if every item verifies; true for an empty list
+ */
+export declare function allSignaturesValid(items: Array<VerifyItem>): boolean
+/**
+ * Sign a whole message as a single-chunk stream
+ *
+ * # Arguments
+ * * `message` - Complete message
+ * * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+ *
+ * # Returns
+ * Signature (3309 bytes), interchangeable with `StreamSigner` output
+ */
+export declare function mldsa65SignStream(message: Buffer, secretKey: Buffer): Buffer
+/**
+ * Verify a stream signature over a whole message
+ *
+ * # Arguments
+ * * `message` - Complete message
+ * * `signature` - Signature from `StreamSigner` or `mldsa65_sign_stream` (3309 bytes)
+ * * `public_key` - ML-DSA-65 public key (1952 bytes)
+ *
+ * # Returns
+ * true if the signature is valid for the message
+ */
+export declare function mldsa65VerifyStream(message: Buffer, signature: Buffer, publicKey: Buffer): boolean
+/**
+ * Generate a new X25519 keypair
+ *
+ * Returns a KeyPair with:
+ * - Public key: 32 bytes
+ * - Secret key: 32 bytes
+ */
+export declare function x25519GenerateKeypair(): KeyPair
+/**
+ * Compute an X25519 shared secret
+ *
+ * Both parties get the same secret from their own secret key and the
+ * other's public key. Feed it, together with the ML-KEM secret, to
+ * `hybrid_kem_combine` rather than using it as a key directly.
+ *
+ * # Arguments
+ * * `my_secret` - Own X25519 secret key (32 bytes)
+ * * `their_public` - Peer's X25519 public key (32 bytes)
+ *
+ * # Returns
+ * Shared secret (32 bytes); errors if the peer key is a low-order point
+ */
+export declare function x25519DiffieHellman(mySecret: Buffer, theirPublic: Buffer): Buffer
 /**
  * Compute BLAKE3 hash of data
  *
@@ -97,6 +679,17 @@ export declare function mldsa65Verify(message: Buffer, signature: Buffer, public
  * 32-byte hash
  */
 export declare function blake3Hash(data: Buffer): Buffer
+/**
+ * Compute BLAKE3 hash of data into a caller-provided buffer
+ *
+ * Writes the digest into `out` instead of allocating a new Buffer, so a
+ * hashing loop can reuse one output buffer.
+ *
+ * # Arguments
+ * * `data` - Data to hash
+ * * `out` - Destination buffer (exactly 32 bytes), overwritten in place
+ */
+export declare function blake3HashInto(data: Buffer, out: Buffer): void
 /**
  * Compute BLAKE3 hash as hex string
  *
@@ -108,27 +701,1619 @@ export declare function blake3Hash(data: Buffer): Buffer
  */
 export declare function blake3HashHex(data: Buffer): string
 /**
- * Generate quantum-resistant fingerprint
+ * Check many blobs against expected BLAKE3 digests
  *
  * # Arguments
- * * `data` - Data to fingerprint
+ * * `items` - Data and expected digest pairs
  *
  * # Returns
- * Fingerprint string in format "qf:{hash}"
+ * One result per item, in order; each digest is compared in constant time,
+ * and an expected digest that is not 32 bytes yields false
  */
-export declare function quantumFingerprint(data: Buffer): string
-/** Convert hex string to bytes */
-export declare function hexToBytes(hex: string): Buffer
+export declare function blake3VerifyMany(items: Array<DigestCheck>): Array<boolean>
+/**
+ * Compute SHA3-256 hash of data (FIPS 202)
+ *
+ * # Arguments
+ * * `data` - Data to hash
+ *
+ * # Returns
+ * 32-byte hash
+ */
+export declare function sha3256Hash(data: Buffer): Buffer
+/**
+ * Compute a 64-byte composite hash: BLAKE3(data) || SHA3-256(data)
+ *
+ * For content addressing that must survive a break of either hash
+ * function: finding a collision requires colliding both at once.
+ *
+ * # Arguments
+ * * `data` - Data to hash
+ *
+ * # Returns
+ * 64-byte composite hash
+ */
+export declare function compositeHash(data: Buffer): Buffer
+/**
+ * Check data against a composite hash from `composite_hash`
+ *
+ * Both halves are compared, in constant time.
+ *
+ * # Arguments
+ * * `data` - Data to check
+ * * `expected` - 64-byte composite hash
+ *
+ * # Returns
+ * true only if both the BLAKE3 and SHA3-256 halves match; false for a
+ * hash that is not 64 bytes
+ */
+export declare function verifyCompositeHash(data: Buffer, expected: Buffer): boolean
+/**
+ * Compute BLAKE3 extendable output (XOF) of data
+ *
+ * # Arguments
+ * * `data` - Data to hash
+ * * `length` - Number of output bytes (at most 65536; use `Blake3Xof` for
+ *   longer streams)
+ *
+ * # Returns
+ * `length` bytes of BLAKE3 output; the first 32 bytes equal `blake3_hash`
+ */
+export declare function blake3HashXof(data: Buffer, length: number): Buffer
+/**
+ * Compute BLAKE3 hash of a range of a buffer
+ *
+ * Hashes `data[offset..offset + len]` without the caller having to create
+ * a `subarray()` on the JS side.
+ *
+ * # Arguments
+ * * `data` - Buffer containing the range
+ * * `offset` - Start of the range in bytes
+ * * `len` - Length of the range in bytes
+ *
+ * # Returns
+ * 32-byte hash
+ */
+export declare function blake3HashRange(data: Buffer, offset: number, len: number): Buffer
+/**
+ * Return the published digest of zero-length input
+ *
+ * Handy for assertions and sanity checks: the values are the published
+ * constants, not recomputed, so comparing them with `blake3_hash` or
+ * `sha3_256_hash` of an empty buffer checks the linked implementation.
+ *
+ * # Arguments
+ * * `algorithm` - "blake3" or "sha3-256"
+ *
+ * # Returns
+ * 32-byte digest of the empty input
+ */
+export declare function emptyDigest(algorithm: string): Buffer
+/**
+ * Compute BLAKE3 hash of data, feeding the hasher in fixed-size updates
+ *
+ * A tuning aid: `chunk_size` only changes how the input is batched into
+ * `update` calls, never the digest, which always equals `blake3_hash`.
+ *
+ * # Arguments
+ * * `data` - Data to hash
+ * * `chunk_size` - Bytes per update (at least 1)
+ *
+ * # Returns
+ * 32-byte hash
+ */
+export declare function blake3HashChunked(data: Buffer, chunkSize: number): Buffer
+/**
+ * Compute BLAKE3 keyed hash (MAC) of data
+ *
+ * # Arguments
+ * * `key` - Secret key (32 bytes)
+ * * `data` - Data to authenticate
+ *
+ * # Returns
+ * 32-byte tag
+ */
+export declare function blake3KeyedHash(key: Buffer, data: Buffer): Buffer
+/**
+ * Verify a BLAKE3 keyed hash (MAC) tag in constant time
+ *
+ * Recomputes the keyed hash of `data` and compares it with `tag` using the
+ * constant-time equality of `blake3::Hash`, so callers don't need to roll
+ * their own comparison.
+ *
+ * # Arguments
+ * * `key` - Secret key (32 bytes)
+ * * `data` - Authenticated data
+ * * `tag` - Tag to check
+ *
+ * # Returns
+ * true if the tag is valid; false on mismatch or if the tag is not 32 bytes
+ */
+export declare function blake3VerifyMac(key: Buffer, data: Buffer, tag: Buffer): boolean
+/**
+ * Derive a 32-byte key with BLAKE3 `derive_key` mode
+ *
+ * # Arguments
+ * * `context` - Hardcoded, globally unique, application-specific context string
+ * * `key_material` - Input key material
+ *
+ * # Returns
+ * 32-byte derived key
+ */
+export declare function blake3DeriveKey(context: string, keyMaterial: Buffer): Buffer
+/**
+ * Compute BLAKE3 hash of data within a named domain
+ *
+ * A key is derived from `context` and used to keyed-hash `data`, so the
+ * same data hashed under different contexts gives unrelated digests.
+ *
+ * Unlike `blake3_keyed_hash`, the key comes from a public context string
+ * rather than a caller-held secret, so the result separates domains but
+ * does not authenticate anything. Unlike `blake3_derive_key`, the output is
+ * a digest of `data`, not key material.
+ *
+ * # Arguments
+ * * `data` - Data to hash
+ * * `context` - Domain the hash belongs to
+ *
+ * # Returns
+ * 32-byte hash
+ */
+export declare function blake3HashWithContext(data: Buffer, context: string): Buffer
+/**
+ * Generate quantum-resistant fingerprint
+ *
+ * # Arguments
+ * * `data` - Data to fingerprint
+ *
+ * # Returns
+ * Fingerprint string in format "qf:{hash}"
+ */
+export declare function quantumFingerprint(data: Buffer): string
+/**
+ * Generate a variable-length quantum fingerprint using SHAKE256
+ *
+ * FIPS 202-aligned alternative to `quantum_fingerprint`. Like any XOF, a
+ * shorter fingerprint of the same data is a prefix of a longer one, so
+ * compare fingerprints of the same length only.
+ *
+ * # Arguments
+ * * `data` - Data to fingerprint
+ * * `bytes` - Digest length in bytes (1 to 1024)
+ *
+ * # Returns
+ * Fingerprint string in format "qf:sk:{hex}"
+ */
+export declare function quantumFingerprintShake(data: Buffer, bytes: number): string
+/**
+ * Generate a quantum fingerprint using SHA3-256
+ *
+ * # Arguments
+ * * `data` - Data to fingerprint
+ *
+ * # Returns
+ * Fingerprint string in format "qf:s3:{hex}"
+ */
+export declare function quantumFingerprintSha3(data: Buffer): string
+/**
+ * Compute fingerprints of one input under several algorithms
+ *
+ * All names are checked before any hashing, and an algorithm requested
+ * more than once is only computed once.
+ *
+ * # Arguments
+ * * `data` - Data to fingerprint
+ * * `algorithms` - "blake3" (as `quantum_fingerprint`) or "sha3-256" (as
+ *   `quantum_fingerprint_sha3`)
+ *
+ * # Returns
+ * One fingerprint per requested algorithm, in order
+ */
+export declare function multiFingerprint(data: Buffer, algorithms: Array<string>): Array<string>
+/**
+ * Generate a domain-separated quantum fingerprint
+ *
+ * The digest is BLAKE3 in `derive_key` mode over the length-prefixed
+ * domain and data, so the same data fingerprints differently in every
+ * domain and fingerprints from different subsystems never collide.
+ *
+ * # Arguments
+ * * `data` - Data to fingerprint
+ * * `domain` - Subsystem name: lowercase ASCII letters, digits, `.`, `_`
+ *   or `-`; "sk" and "s3" are reserved for SHAKE256 and SHA3-256 fingerprints
+ *
+ * # Returns
+ * Fingerprint string in format "qf:{domain}:{hash}"
+ */
+export declare function quantumFingerprintDomain(data: Buffer, domain: string): string
+/**
+ * Check data against a fingerprint from `quantum_fingerprint_domain`
+ *
+ * # Arguments
+ * * `data` - Data to check
+ * * `domain` - Domain the fingerprint must belong to
+ * * `fingerprint` - Fingerprint string
+ *
+ * # Returns
+ * true only if the fingerprint is for `data` in `domain`; a fingerprint
+ * from any other domain is rejected
+ */
+export declare function verifyFingerprintDomain(data: Buffer, domain: string, fingerprint: string): boolean
+/**
+ * Parse a fingerprint produced by `quantum_fingerprint`, `quantum_fingerprint_shake`
+ * or `quantum_fingerprint_sha3`
+ *
+ * # Arguments
+ * * `fingerprint` - Fingerprint string ("qf:{hex}", "qf:sk:{hex}" or "qf:s3:{hex}")
+ *
+ * SHAKE256 digests are limited to 1024 bytes, like `quantum_fingerprint_shake`.
+ *
+ * # Returns
+ * ParsedFingerprint with the algorithm and digest bytes
+ */
+export declare function parseFingerprint(fingerprint: string): ParsedFingerprint
+/**
+ * Check data against a user-supplied fingerprint
+ *
+ * The expected fingerprint is trimmed and lowercased, and a missing `qf:`
+ * prefix is added back, so "QF:ABCD..." and a bare hex digest both match.
+ * Any format accepted by `parse_fingerprint` works; the digest is
+ * recomputed with the matching algorithm and compared in constant time.
+ * SHAKE256 fingerprints shorter than 32 bytes never match, so a truncated
+ * or guessed `qf:sk:` value can't pass the comparison by chance.
+ *
+ * # Arguments
+ * * `data` - Data to fingerprint
+ * * `expected` - Fingerprint as entered by a user
+ *
+ * # Returns
+ * true if the fingerprint matches; false on mismatch or unparseable input
+ */
+export declare function fingerprintEqualsData(data: Buffer, expected: string): boolean
+/**
+ * Compute an order-independent commitment to a set of public keys
+ *
+ * Keys are sorted by their bytes, length-prefixed and hashed with BLAKE3 in
+ * `derive_key` mode, so the same committee or validator set always yields
+ * the same commitment regardless of input order. Duplicate keys are kept,
+ * so a set containing a key twice commits differently from one containing
+ * it once.
+ *
+ * # Arguments
+ * * `keys` - Public keys of any algorithm
+ *
+ * # Returns
+ * 32-byte commitment
+ */
+export declare function commitPublicKeySet(keys: Array<Buffer>): Buffer
+/**
+ * Check a public key set against a commitment from `commit_public_key_set`
+ *
+ * # Arguments
+ * * `keys` - Public keys, in any order
+ * * `commitment` - Expected 32-byte commitment
+ *
+ * # Returns
+ * true if the keys match the commitment; false otherwise (including a
+ * commitment that is not 32 bytes)
+ */
+export declare function verifySetCommitment(keys: Array<Buffer>, commitment: Buffer): boolean
+/**
+ * Compute a stable deduplication key for a keypair
+ *
+ * BLAKE3 over the length-prefixed public and secret keys, domain-separated
+ * from other hashes in this module. Identical keypairs always hash equal
+ * and any byte difference in either key changes the hash. The result is
+ * derived from the secret key, so treat it as sensitive and don't publish
+ * it as a key identifier; use `quantum_fingerprint` of the public key for
+ * that.
+ *
+ * # Arguments
+ * * `kp` - Keypair of any algorithm
+ *
+ * # Returns
+ * 32-byte hash
+ */
+export declare function keypairHash(kp: KeyPair): Buffer
+/**
+ * Generate a keypair for the given algorithm
+ *
+ * # Arguments
+ * * `algorithm` - "ml-kem-512", "ml-kem-768" or "ml-dsa-65"
+ *
+ * # Returns
+ * KeyPair for the requested algorithm; hash algorithms are rejected
+ */
+export declare function generateKeypair(algorithm: Algorithm): KeyPair
+/**
+ * List every algorithm this build exposes, for capability negotiation
+ *
+ * `ready` is false for algorithms that are present only as placeholders.
+ * Every algorithm in this build, including ML-DSA-65 (backed by
+ * `pqcrypto-dilithium`), has a real implementation and reports ready.
+ *
+ * # Returns
+ * One AlgorithmStatus per algorithm, in `Algorithm` declaration order
+ */
+export declare function supportedAlgorithms(): Array<AlgorithmStatus>
+/**
+ * Estimate the memory a keypair of the given algorithm occupies
+ *
+ * Intended for sizing key caches. The estimate is the public and secret
+ * key lengths plus a fixed allowance for the JS objects holding them; it is
+ * an approximation, not a measurement of the engine's actual allocations.
+ *
+ * # Arguments
+ * * `algorithm` - "ml-kem-512", "ml-kem-768" or "ml-dsa-65"
+ *
+ * # Returns
+ * Approximate bytes per keypair; hash algorithms are rejected
+ */
+export declare function estimatedMemory(algorithm: Algorithm): number
+/** Public keys carried by a verified identity document */
+export interface IdentityKeys {
+  /** ML-KEM-768 public key (1184 bytes) */
+  kemPublic: Buffer
+  /** ML-DSA-65 public key (1952 bytes) */
+  dsaPublic: Buffer
+}
+/** Contents of a verified self-signed certificate */
+export interface SelfSignedCertificate {
+  /** Subject: `quantum_fingerprint` of the public key */
+  subject: string
+  /** Subject ML-DSA-65 public key (1952 bytes) */
+  publicKey: Buffer
+  /** Start of validity, milliseconds since the Unix epoch */
+  notBefore: number
+  /** End of validity (inclusive), milliseconds since the Unix epoch */
+  notAfter: number
+}
+/** Signed contents of a verified notarization receipt */
+export interface NotarizationRecord {
+  /** BLAKE3 digest of the notarized data (32 bytes) */
+  digest: Buffer
+  /** Timestamp in milliseconds since the Unix epoch, as given to `notarize` */
+  timestamp: number
+}
+/** Lengths read from an attached signature header */
+export interface AttachedHeader {
+  /** Signature length in bytes */
+  sigLen: number
+  /** Message length in bytes */
+  msgLen: number
+}
+/**
+ * Serialize a keypair to CBOR
+ *
+ * The keypair is encoded as a map with an `alg` tag ("ML-KEM-768" or
+ * "ML-DSA-65") and the public and secret keys as byte strings. With
+ * `compress` set, the CBOR is wrapped in a zstd frame; the zstd magic makes
+ * the blob self-identifying, and `keypair_from_cbor` accepts both forms.
+ * ML-KEM secret keys embed the public key and compress well; if
+ * compression would not shrink the blob, the plain CBOR is returned.
+ *
+ * # Arguments
+ * * `kp` - ML-KEM-768 or ML-DSA-65 keypair
+ * * `compress` - Apply zstd compression (default false)
+ *
+ * # Returns
+ * CBOR-encoded keypair, optionally zstd-compressed
+ */
+export declare function keypairToCbor(kp: KeyPair, compress?: boolean | undefined | null): Buffer
+/**
+ * Deserialize a keypair from CBOR
+ *
+ * zstd-compressed blobs are detected by their frame magic and decompressed
+ * transparently, up to 8 KiB of decompressed CBOR.
+ *
+ * # Arguments
+ * * `bytes` - CBOR produced by `keypair_to_cbor`
+ *
+ * # Returns
+ * KeyPair, after checking both key lengths against the `alg` tag
+ */
+export declare function keypairFromCbor(bytes: Buffer): KeyPair
+/**
+ * Serialize an ML-KEM-768 encapsulated secret to CBOR
+ *
+ * # Arguments
+ * * `enc` - Encapsulated secret (1088-byte ciphertext, 32-byte shared secret)
+ *
+ * # Returns
+ * CBOR-encoded encapsulated secret
+ */
+export declare function encapsulatedSecretToCbor(enc: EncapsulatedSecret): Buffer
+/**
+ * Deserialize an ML-KEM-768 encapsulated secret from CBOR
+ *
+ * # Arguments
+ * * `bytes` - CBOR produced by `encapsulated_secret_to_cbor`
+ *
+ * # Returns
+ * EncapsulatedSecret, after checking the `alg` tag and field lengths
+ */
+export declare function encapsulatedSecretFromCbor(bytes: Buffer): EncapsulatedSecret
+/**
+ * Sign a message and wrap the signature in the `.sig` file format
+ *
+ * The message is signed directly, so it is subject to the process-wide
+ * `max_direct_sign_bytes` limit (1 MiB by default).
+ *
+ * # Arguments
+ * * `message` - Message to sign
+ * * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+ *
+ * # Returns
+ * `QSIG` header tagged "ML-DSA-65" followed by the 3309-byte signature
+ */
+export declare function mldsa65SignToSigFormat(message: Buffer, secretKey: Buffer): Buffer
+/**
+ * Verify a message against a `.sig` file produced by `mldsa65_sign_to_sig_format`
+ *
+ * The header is validated before any verification runs: a blob with the
+ * wrong magic, an unknown version, or an algorithm tag other than
+ * "ML-DSA-65" is an error rather than a failed verification.
+ *
+ * # Arguments
+ * * `message` - Signed message
+ * * `sig_blob` - `.sig` file contents
+ * * `public_key` - ML-DSA-65 public key (1952 bytes)
+ *
+ * # Returns
+ * true if the signature is valid for the message
+ */
+export declare function mldsa65VerifySigFormat(message: Buffer, sigBlob: Buffer, publicKey: Buffer): boolean
+/**
+ * Sign a message and produce an attached signature (signature + message)
+ *
+ * The message is signed directly, so it is subject to the process-wide
+ * `max_direct_sign_bytes` limit (1 MiB by default) and errors beyond it.
+ * The format itself allows messages up to 4 GiB.
+ *
+ * # Arguments
+ * * `message` - Message to sign (up to `max_direct_sign_bytes`)
+ * * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+ *
+ * # Returns
+ * `QATT` header || signature || message
+ */
+export declare function mldsa65SignAttached(message: Buffer, secretKey: Buffer): Buffer
+/**
+ * Read the fixed-size header of an attached signature
+ *
+ * Only the first 13 bytes are inspected, so a streaming parser can call
+ * this as soon as the header has arrived and then read exactly
+ * `sig_len + msg_len` more bytes.
+ *
+ * # Arguments
+ * * `blob` - Attached signature, or at least its first 13 bytes
+ *
+ * # Returns
+ * AttachedHeader with the signature and message lengths
+ */
+export declare function parseAttachedHeader(blob: Buffer): AttachedHeader
+/**
+ * Verify an attached signature and return the signed message
+ *
+ * # Arguments
+ * * `blob` - Output of `mldsa65_sign_attached`
+ * * `public_key` - ML-DSA-65 public key (1952 bytes)
+ *
+ * # Returns
+ * The message, if the signature is valid; errors otherwise
+ */
+export declare function mldsa65OpenAttached(blob: Buffer, publicKey: Buffer): Buffer
+/**
+ * Build a self-certifying identity document for a node
+ *
+ * Packages the node's KEM and signing public keys and signs them with the
+ * signing key, so anyone holding the document can check that whoever
+ * controls `dsa_public` vouches for `kem_public`.
+ *
+ * # Arguments
+ * * `kem_public` - ML-KEM-768 public key (1184 bytes)
+ * * `dsa_public` - ML-DSA-65 public key (1952 bytes)
+ * * `dsa_secret` - ML-DSA-65 secret key matching `dsa_public` (4032 bytes)
+ *
+ * # Returns
+ * `QIDN` identity document
+ */
+export declare function buildIdentity(kemPublic: Buffer, dsaPublic: Buffer, dsaSecret: Buffer): Buffer
+/**
+ * Verify an identity document's self-signature and return its keys
+ *
+ * # Arguments
+ * * `blob` - Output of `build_identity`
+ *
+ * # Returns
+ * IdentityKeys, if the self-signature is valid; errors otherwise
+ */
+export declare function verifyIdentity(blob: Buffer): IdentityKeys
+/**
+ * Produce a signed, timestamped receipt for data
+ *
+ * The data is hashed with BLAKE3 and the digest and timestamp are signed
+ * together, so the receipt proves the key holder vouched for this exact
+ * content at this time. The timestamp is taken as given; the signer is
+ * trusted to supply an accurate clock. Only the fixed-size receipt is
+ * signed, so `max_direct_sign_bytes` does not limit the size of `data`.
+ *
+ * # Arguments
+ * * `data` - Data to notarize
+ * * `dsa_secret` - Notary's ML-DSA-65 secret key (4032 bytes)
+ * * `timestamp_ms` - Milliseconds since the Unix epoch
+ *
+ * # Returns
+ * `QNTR` receipt
+ */
+export declare function notarize(data: Buffer, dsaSecret: Buffer, timestampMs: number): Buffer
+/**
+ * Verify a receipt from `notarize` and return what it attests to
+ *
+ * Compare the returned digest with `blake3_hash` of the data in question.
+ *
+ * # Arguments
+ * * `receipt` - Output of `notarize`
+ * * `dsa_public` - Notary's ML-DSA-65 public key (1952 bytes)
+ *
+ * # Returns
+ * NotarizationRecord, if the signature is valid; errors otherwise
+ */
+export declare function verifyNotarization(receipt: Buffer, dsaPublic: Buffer): NotarizationRecord
+/**
+ * Issue a self-signed certificate for an ML-DSA-65 keypair
+ *
+ * For bootstrapping a node identity before any CA is involved: the
+ * certificate only proves possession of the secret key for the subject
+ * fingerprint during the validity window.
+ *
+ * # Arguments
+ * * `keypair` - ML-DSA-65 keypair (1952-byte public, 4032-byte secret key)
+ * * `not_before_ms` - Start of validity, milliseconds since the Unix epoch
+ * * `not_after_ms` - End of validity (inclusive), not before `not_before_ms`
+ *
+ * # Returns
+ * `QCRT` certificate
+ */
+export declare function generateSelfSigned(keypair: KeyPair, notBeforeMs: number, notAfterMs: number): Buffer
+/**
+ * Verify a certificate from `generate_self_signed`
+ *
+ * # Arguments
+ * * `cert` - `QCRT` certificate
+ * * `now_ms` - Optional current time; when given, a certificate outside
+ *   its validity window is rejected
+ *
+ * # Returns
+ * SelfSignedCertificate with the subject public key, if the self-signature
+ * is valid; errors otherwise
+ */
+export declare function verifySelfSigned(cert: Buffer, nowMs?: number | undefined | null): SelfSignedCertificate
+/**
+ * Encode an ML-KEM-768 encapsulation for transmission
+ *
+ * Only the ciphertext is written, behind a `QKCT` header tagged
+ * "ML-KEM-768". The shared secret is deliberately left out: it must stay
+ * with the sender, and the recipient recovers it by decapsulating.
+ *
+ * # Arguments
+ * * `enc` - Encapsulated secret from `mlkem768_encapsulate`
+ *
+ * # Returns
+ * Wire-format ciphertext
+ */
+export declare function encapsulatedSecretToWire(enc: EncapsulatedSecret): Buffer
+/**
+ * Parse a wire-format ciphertext produced by `encapsulated_secret_to_wire`
+ *
+ * # Arguments
+ * * `bytes` - Wire-format ciphertext
+ *
+ * # Returns
+ * The 1088-byte ML-KEM-768 ciphertext, ready for `mlkem768_decapsulate`
+ */
+export declare function ciphertextFromWire(bytes: Buffer): Buffer
+/**
+ * Decode and validate a base64 public key from an environment variable
+ *
+ * Accepts standard base64 with padding; surrounding whitespace (such as a
+ * trailing newline from a secrets file) is ignored.
+ *
+ * # Arguments
+ * * `b64` - Base64-encoded public key
+ * * `expected_algo` - "ml-kem-512", "ml-kem-768" or "ml-dsa-65"
+ *
+ * # Returns
+ * Decoded public key; errors name the algorithm and the lengths involved
+ */
+export declare function loadPublicKeyEnv(b64: string, expectedAlgo: Algorithm): Buffer
+/**
+ * Derive a human-readable node address from a public key
+ *
+ * # Arguments
+ * * `key` - Public key of any algorithm
+ *
+ * # Returns
+ * Address string "qd" followed by 58 lowercase base32 characters
+ */
+export declare function publicKeyToAddress(key: Buffer): string
+/**
+ * Check the checksum of an address from `public_key_to_address`
+ *
+ * Letter case is ignored, so an address read back in uppercase still
+ * validates.
+ *
+ * # Arguments
+ * * `addr` - Address string
+ *
+ * # Returns
+ * true if the address is well-formed and its checksum matches
+ */
+export declare function validateAddress(addr: string): boolean
+/**
+ * Export an ML-KEM-768 secret key as PKCS#8
+ *
+ * # Arguments
+ * * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+ *
+ * # Returns
+ * DER-encoded PKCS#8 PrivateKeyInfo for tools and HSMs that only import PKCS#8
+ */
+export declare function mlkem768SecretKeyToPkcs8(secretKey: Buffer): Buffer
+/**
+ * Import an ML-KEM-768 secret key from PKCS#8
+ *
+ * # Arguments
+ * * `der` - DER-encoded PKCS#8 PrivateKeyInfo from `mlkem768_secret_key_to_pkcs8`
+ *
+ * # Returns
+ * The 2400-byte secret key; errors on a foreign algorithm OID, an
+ * unexpected inner key length, or malformed DER
+ */
+export declare function mlkem768SecretKeyFromPkcs8(der: Buffer): Buffer
+/**
+ * Hash a directory tree into a single BLAKE3 root
+ *
+ * Walks `root_path` recursively, hashes every file, and combines the
+ * sorted (relative path, file hash) pairs, each length-prefixed, with
+ * BLAKE3 in `derive_key` mode. Paths use `/` separators on every platform,
+ * so the root depends only on file names and contents. Empty directories
+ * do not contribute to the root.
+ *
+ * # Arguments
+ * * `root_path` - Directory to hash
+ *
+ * # Returns
+ * 32-byte root hash; errors on symlinks, unreadable entries, or non-UTF-8 names
+ */
+export declare function blake3HashTree(rootPath: string): Buffer
+/**
+ * Sign a file with ML-DSA-65 without loading it into memory
+ *
+ * Streams the file through BLAKE3 and signs the digest, exactly as
+ * `StreamSigner` would for the same bytes, so the signature also verifies
+ * with `mldsa65_verify_stream` over the file contents. This is
+ * hash-then-sign under a dedicated context, not FIPS 204 HashML-DSA.
+ *
+ * # Arguments
+ * * `path` - File to sign
+ * * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+ *
+ * # Returns
+ * Signature (3309 bytes); errors if the file cannot be read
+ */
+export declare function mldsa65SignFile(path: string, secretKey: Buffer): Buffer
+/**
+ * Verify a `mldsa65_sign_file` signature by re-hashing the file
+ *
+ * # Arguments
+ * * `path` - File to verify
+ * * `signature` - Signature (3309 bytes)
+ * * `public_key` - ML-DSA-65 public key (1952 bytes)
+ *
+ * # Returns
+ * true if the signature is valid for the file's current contents; errors
+ * if the file cannot be read
+ */
+export declare function mldsa65VerifyFile(path: string, signature: Buffer, publicKey: Buffer): boolean
+/** Bao encoding of some data with its BLAKE3 root */
+export interface VerifiedEncoding {
+  /** Combined Bao encoding: data interleaved with the BLAKE3 tree */
+  encoded: Buffer
+  /** BLAKE3 hash of the data (32 bytes), equal to `blake3_hash` */
+  root: Buffer
+}
+/**
+ * Encode data for verified streaming
+ *
+ * The encoding interleaves the data with the parent nodes of its BLAKE3
+ * tree, so a receiver holding only the 32-byte root can check every chunk
+ * as it arrives instead of buffering the whole input first.
+ *
+ * # Arguments
+ * * `data` - Data to encode
+ *
+ * # Returns
+ * VerifiedEncoding with the encoded stream and its root
+ */
+export declare function blake3EncodeVerified(data: Buffer): VerifiedEncoding
+/**
+ * Verify and decode a stream from `blake3_encode_verified`
+ *
+ * # Arguments
+ * * `encoded` - Bao-encoded stream
+ * * `root` - Trusted BLAKE3 root (32 bytes)
+ *
+ * # Returns
+ * The original data; errors if any part of the stream fails to verify
+ */
+export declare function blake3DecodeVerified(encoded: Buffer, root: Buffer): Buffer
+/**
+ * Compute the rolling hash of one window from scratch
+ *
+ * # Arguments
+ * * `window` - Window contents; its length is the window size
+ *
+ * # Returns
+ * The value a `RollingHasher` of that window size holds over these bytes
+ */
+export declare function rollingHash(window: Buffer): number
+/**
+ * Find content-defined chunk boundaries
+ *
+ * Slides a `RollingHasher` over `data` and cuts after every position
+ * whose full window hashes to a value with all `mask` bits set. With a
+ * mask of `k` one bits the average chunk is about `2^k` bytes. Boundaries
+ * depend only on the window contents, so an insertion early in the data
+ * leaves later boundaries where they were.
+ *
+ * # Arguments
+ * * `data` - Data to chunk
+ * * `window_size` - Rolling window size in bytes (1 to 4096)
+ * * `mask` - Boundary mask, e.g. `0x1fff` for ~8 KiB chunks
+ *
+ * # Returns
+ * Chunk end offsets in increasing order (each exclusive); the end of
+ * `data` is not included unless it is itself a boundary
+ */
+export declare function findChunkBoundaries(data: Buffer, windowSize: number, mask: number): Array<number>
+/** Outcome of `hybrid_decrypt_safe` */
+export interface HybridDecryptResult {
+  /** Whether decryption succeeded */
+  ok: boolean
+  /** Decrypted plaintext, when `ok` */
+  plaintext?: Buffer
+  /**
+   * Failure code when not `ok`: "truncated", "unsupported_version",
+   * "invalid_key" or "authentication_failed"
+   */
+  error?: string
+}
+/** Independent keys for encrypt-then-MAC, from `derive_enc_mac_keys` */
+export interface EncMacKeys {
+  /** Encryption key (32 bytes) */
+  encKey: Buffer
+  /** MAC key (32 bytes) */
+  macKey: Buffer
+}
+/** Key confirmation tags, from `derive_confirmation_tags` */
+export interface ConfirmationTags {
+  /** Tag the initiator sends to prove it holds the secret (32 bytes) */
+  initiatorTag: Buffer
+  /** Tag the responder sends to prove it holds the secret (32 bytes) */
+  responderTag: Buffer
+}
+/** Encapsulation whose shared secret is wrapped, from `mlkem768_encapsulate_wrapped` */
+export interface WrappedEncapsulation {
+  /** ML-KEM-768 ciphertext for the recipient (1088 bytes) */
+  ciphertext: Buffer
+  /** Shared secret wrapped under the caller's key: nonce || ciphertext || tag (60 bytes) */
+  wrappedSecret: Buffer
+}
+/** Group secret encapsulated to every member of a group */
+export interface GroupEncapsulation {
+  /** ML-KEM-768 ciphertext for each recipient, in input order */
+  perRecipientCiphertexts: Array<Buffer>
+  /** Group secret wrapped for each recipient, in input order */
+  wrappedGroupSecrets: Array<Buffer>
+  /** The group secret every recipient recovers (32 bytes) */
+  groupSecret: Buffer
+}
+/**
+ * Encrypt data to an ML-KEM-768 public key
+ *
+ * Encapsulates a fresh shared secret, derives an AES-256-GCM key from it
+ * with BLAKE3 and encrypts under a random 12-byte nonce. Random nonces are
+ * safe here because every call uses a new key; for many messages under one
+ * shared secret use `aead_seal_with_nonce` with a `NonceTracker`.
+ *
+ * # Arguments
+ * * `public_key` - Recipient's ML-KEM-768 public key (1184 bytes)
+ * * `plaintext` - Data to encrypt
+ *
+ * # Returns
+ * version || KEM ciphertext || nonce || AEAD ciphertext || tag
+ */
+export declare function hybridEncrypt(publicKey: Buffer, plaintext: Buffer): Buffer
+/**
+ * Decrypt data produced by `hybrid_encrypt`
+ *
+ * # Arguments
+ * * `secret_key` - Recipient's ML-KEM-768 secret key (2400 bytes)
+ * * `ciphertext` - Output of `hybrid_encrypt`
+ *
+ * # Returns
+ * Decrypted plaintext
+ */
+export declare function hybridDecrypt(secretKey: Buffer, ciphertext: Buffer): Buffer
+/**
+ * Encrypt data to an ML-KEM-768 public key deterministically
+ *
+ * Convergent encryption for content-addressed storage: the same public
+ * key, plaintext and `key_commitment` always give the same ciphertext, so
+ * duplicates can be detected and stored once. The KEM coins and AES-GCM
+ * nonce are derived with BLAKE3 from all three inputs, and the output opens
+ * with `hybrid_decrypt`.
+ *
+ * Privacy tradeoff: anyone who sees two ciphertexts learns whether they
+ * hold the same plaintext, and anyone who knows `key_commitment` can
+ * confirm a guessed plaintext by re-encrypting it. Keep `key_commitment`
+ * secret (e.g. one per tenant), and don't use this for low-entropy data
+ * such as passwords or short fields; use `hybrid_encrypt` when
+ * deduplication is not needed.
+ *
+ * # Arguments
+ * * `public_key` - Recipient's ML-KEM-768 public key (1184 bytes)
+ * * `plaintext` - Data to encrypt
+ * * `key_commitment` - Secret convergence key (32 bytes)
+ *
+ * # Returns
+ * version || KEM ciphertext || nonce || AEAD ciphertext || tag
+ */
+export declare function hybridEncryptDeterministic(publicKey: Buffer, plaintext: Buffer, keyCommitment: Buffer): Buffer
+/**
+ * Encrypt data to an ML-KEM-768 public key, hiding its exact length
+ *
+ * The plaintext is length-prefixed and zero-padded to a multiple of
+ * `pad_to` bytes before encryption, so every plaintext in the same block
+ * produces a ciphertext of the same size. The four-byte length prefix
+ * counts towards the padded size.
+ *
+ * # Arguments
+ * * `public_key` - Recipient's ML-KEM-768 public key (1184 bytes)
+ * * `plaintext` - Data to encrypt
+ * * `pad_to` - Block size in bytes (1 to 65536)
+ *
+ * # Returns
+ * Hybrid-layout blob for `hybrid_decrypt_padded`
+ */
+export declare function hybridEncryptPadded(publicKey: Buffer, plaintext: Buffer, padTo: number): Buffer
+/**
+ * Decrypt data produced by `hybrid_encrypt_padded`
+ *
+ * # Arguments
+ * * `secret_key` - Recipient's ML-KEM-768 secret key (2400 bytes)
+ * * `ciphertext` - Output of `hybrid_encrypt_padded`
+ *
+ * # Returns
+ * The original plaintext with the padding removed
+ */
+export declare function hybridDecryptPadded(secretKey: Buffer, ciphertext: Buffer): Buffer
+/**
+ * Read the format version byte of a hybrid ciphertext
+ *
+ * # Arguments
+ * * `blob` - Output of `hybrid_encrypt` (or another hybrid-layout blob)
+ *
+ * # Returns
+ * The version byte; errors on an empty blob. The version is not checked
+ * against the versions this build supports.
+ */
+export declare function hybridCiphertextVersion(blob: Buffer): number
+/**
+ * Decrypt data produced by `hybrid_encrypt` without throwing
+ *
+ * Intended for network code handling partial or corrupted frames. Instead
+ * of an exception, failures are reported in `error` as one of:
+ * - "truncated": shorter than the 1117-byte minimum, e.g. a partial frame
+ * - "unsupported_version": the version byte is not one this build reads
+ * - "invalid_key": the secret key is not 2400 bytes
+ * - "authentication_failed": well-formed, but the tag did not verify
+ *   (tampered data, wrong recipient, or not a `hybrid_encrypt` payload)
+ *
+ * # Arguments
+ * * `secret_key` - Recipient's ML-KEM-768 secret key (2400 bytes)
+ * * `ciphertext` - Output of `hybrid_encrypt`
+ *
+ * # Returns
+ * HybridDecryptResult with `ok` and either `plaintext` or `error`
+ */
+export declare function hybridDecryptSafe(secretKey: Buffer, ciphertext: Buffer): HybridDecryptResult
+/**
+ * Encrypt a secret key to a peer's ML-KEM-768 public key for delegation
+ *
+ * A sealed box for keys: the blob has the `hybrid_encrypt` layout, but is
+ * authenticated with a key-export AAD so it can't be confused with (or
+ * opened as) an ordinary `hybrid_encrypt` message.
+ *
+ * # Arguments
+ * * `secret_key` - Secret key to hand off (any algorithm)
+ * * `peer_public_kem` - Recipient's ML-KEM-768 public key (1184 bytes)
+ *
+ * # Returns
+ * Encrypted key blob for `import_secret_from_peer`
+ */
+export declare function exportSecretToPeer(secretKey: Buffer, peerPublicKem: Buffer): Buffer
+/**
+ * Decrypt a secret key produced by `export_secret_to_peer`
+ *
+ * # Arguments
+ * * `blob` - Output of `export_secret_to_peer`
+ * * `my_secret_kem` - Recipient's ML-KEM-768 secret key (2400 bytes)
+ *
+ * # Returns
+ * The delegated secret key; errors if the blob was not encrypted to this key
+ */
+export declare function importSecretFromPeer(blob: Buffer, mySecretKem: Buffer): Buffer
+/**
+ * Sign a message and encrypt it to a recipient
+ *
+ * Sign-then-encrypt: the ML-DSA-65 signature covers the recipient's public
+ * key and the message, so a recipient cannot re-encrypt the signed message
+ * to someone else as if the sender had addressed it to them. The signature
+ * travels inside the ciphertext, so relays learn nothing about the sender.
+ *
+ * # Arguments
+ * * `message` - Message to send
+ * * `recipient_kem_public` - Recipient's ML-KEM-768 public key (1184 bytes)
+ * * `sender_dsa_secret` - Sender's ML-DSA-65 secret key (4032 bytes)
+ *
+ * # Returns
+ * `QENV` envelope for `open_envelope`
+ */
+export declare function sealEnvelope(message: Buffer, recipientKemPublic: Buffer, senderDsaSecret: Buffer): Buffer
+/**
+ * Decrypt an envelope from `seal_envelope` and verify its signature
+ *
+ * # Arguments
+ * * `envelope` - `QENV` envelope
+ * * `recipient_kem_secret` - Recipient's ML-KEM-768 secret key (2400 bytes)
+ * * `sender_dsa_public` - Expected sender's ML-DSA-65 public key (1952 bytes)
+ *
+ * # Returns
+ * The message; errors on a corrupt envelope, the wrong recipient, or a
+ * signature that does not verify under `sender_dsa_public`
+ */
+export declare function openEnvelope(envelope: Buffer, recipientKemSecret: Buffer, senderDsaPublic: Buffer): Buffer
+/**
+ * Check an envelope's structure without any keys
+ *
+ * For relays triaging stored or forwarded envelopes: checks the magic,
+ * version, length field, the embedded hybrid header and minimum size, and
+ * the BLAKE3 checksum. The checksum is unkeyed, so it catches truncation
+ * and accidental corruption but not deliberate tampering; only
+ * `open_envelope` authenticates the contents.
+ *
+ * # Arguments
+ * * `envelope` - `QENV` envelope
+ *
+ * # Returns
+ * true if the envelope is structurally sound; false otherwise
+ */
+export declare function envelopeIntegrityOk(envelope: Buffer): boolean
+/**
+ * Derive the AES-256-GCM key `hybrid_encrypt` uses from a KEM shared secret
+ *
+ * This is a stable part of the hybrid format: the key is
+ * `BLAKE3-derive_key("qudag-napi hybrid aead key v1", shared_secret)`, so
+ * third-party implementations can reproduce it with any BLAKE3 library.
+ *
+ * # Arguments
+ * * `shared_secret` - ML-KEM shared secret (32 bytes)
+ *
+ * # Returns
+ * 32-byte AES-256-GCM key
+ */
+export declare function deriveAeadKey(sharedSecret: Buffer): Buffer
+/**
+ * Derive separate encryption and MAC keys from a KEM shared secret
+ *
+ * For encrypt-then-MAC constructions that must not reuse one key for both
+ * roles. Each key is `BLAKE3-derive_key` of the shared secret under its own
+ * context ("qudag-napi encrypt-then-mac enc key v1" and
+ * "qudag-napi encrypt-then-mac mac key v1"), so knowing one key reveals
+ * nothing about the other.
+ *
+ * # Arguments
+ * * `shared_secret` - ML-KEM shared secret (32 bytes)
+ *
+ * # Returns
+ * EncMacKeys with two 32-byte keys
+ */
+export declare function deriveEncMacKeys(sharedSecret: Buffer): EncMacKeys
+/**
+ * Derive key confirmation tags for both directions of a handshake
+ *
+ * Each side sends its own tag and checks the other's with
+ * `verify_initiator_tag` / `verify_responder_tag`. The tags come from
+ * separate `derive_key` contexts, so a tag reflected back at its sender
+ * does not verify as the peer's, and since every handshake has a fresh
+ * shared secret, tags from an earlier session do not verify either.
+ *
+ * # Arguments
+ * * `shared_secret` - ML-KEM shared secret (32 bytes)
+ *
+ * # Returns
+ * ConfirmationTags with the initiator and responder tags
+ */
+export declare function deriveConfirmationTags(sharedSecret: Buffer): ConfirmationTags
+/**
+ * Check the initiator's confirmation tag, in constant time
+ *
+ * # Arguments
+ * * `shared_secret` - ML-KEM shared secret (32 bytes)
+ * * `tag` - Tag received from the initiator
+ *
+ * # Returns
+ * true if the tag matches; false on mismatch or a tag that is not 32 bytes
+ */
+export declare function verifyInitiatorTag(sharedSecret: Buffer, tag: Buffer): boolean
+/**
+ * Check the responder's confirmation tag, in constant time
+ *
+ * # Arguments
+ * * `shared_secret` - ML-KEM shared secret (32 bytes)
+ * * `tag` - Tag received from the responder
+ *
+ * # Returns
+ * true if the tag matches; false on mismatch or a tag that is not 32 bytes
+ */
+export declare function verifyResponderTag(sharedSecret: Buffer, tag: Buffer): boolean
+/**
+ * Generate a fresh random 32-byte symmetric key
+ *
+ * Drawn from the operating system CSPRNG. The key can be passed wherever
+ * the AEAD helpers (`aead_seal_with_nonce`, `aead_open_with_nonce`,
+ * `NonceTracker`) take a `shared_secret`, for data encrypted without a KEM
+ * exchange.
+ *
+ * # Returns
+ * 32-byte key
+ */
+export declare function generateSymmetricKey(): Buffer
+/**
+ * Encrypt data under a KEM shared secret with a caller-chosen nonce
+ *
+ * Uses the same key derivation as `hybrid_encrypt`. The caller is
+ * responsible for never reusing a nonce with the same shared secret, which
+ * would break AES-GCM confidentiality and integrity; deterministic counters
+ * are the usual choice, and `NonceTracker` can enforce uniqueness.
+ *
+ * # Arguments
+ * * `shared_secret` - ML-KEM shared secret (32 bytes)
+ * * `plaintext` - Data to encrypt
+ * * `nonce` - Unique nonce (12 bytes)
+ * * `aad` - Optional associated data to authenticate
+ *
+ * # Returns
+ * AEAD ciphertext || tag (the nonce is not included)
+ */
+export declare function aeadSealWithNonce(sharedSecret: Buffer, plaintext: Buffer, nonce: Buffer, aad?: Buffer | undefined | null): Buffer
+/**
+ * Decrypt data produced by `aead_seal_with_nonce`
+ *
+ * # Arguments
+ * * `shared_secret` - ML-KEM shared secret (32 bytes)
+ * * `ciphertext` - AEAD ciphertext || tag
+ * * `nonce` - Nonce used for encryption (12 bytes)
+ * * `aad` - Associated data used for encryption, if any
+ *
+ * # Returns
+ * Decrypted plaintext
+ */
+export declare function aeadOpenWithNonce(sharedSecret: Buffer, ciphertext: Buffer, nonce: Buffer, aad?: Buffer | undefined | null): Buffer
+/**
+ * MAC a handshake transcript under a key derived from a KEM shared secret
+ *
+ * The MAC key is `derive_key("qudag-napi transcript mac key v1",
+ * shared_secret)`; the MAC is keyed BLAKE3 over the transcript elements,
+ * each prefixed with its length as a little-endian u64, so element
+ * boundaries are unambiguous. Both sides compute it over the messages they
+ * saw and compare to confirm they agree on the transcript.
+ *
+ * # Arguments
+ * * `shared_secret` - ML-KEM shared secret (32 bytes)
+ * * `transcript` - Handshake messages, in order
+ *
+ * # Returns
+ * 32-byte transcript MAC
+ */
+export declare function transcriptMac(sharedSecret: Buffer, transcript: Array<Buffer>): Buffer
+/**
+ * Verify a MAC produced by `transcript_mac`, in constant time
+ *
+ * # Arguments
+ * * `shared_secret` - ML-KEM shared secret (32 bytes)
+ * * `transcript` - Handshake messages, in order
+ * * `mac` - MAC to check
+ *
+ * # Returns
+ * true if the MAC matches; false on mismatch or a MAC that is not 32 bytes
+ */
+export declare function verifyTranscriptMac(sharedSecret: Buffer, transcript: Array<Buffer>, mac: Buffer): boolean
+/**
+ * Sign a handshake transcript with ML-DSA-65
+ *
+ * Asymmetric counterpart of `transcript_mac` for when the signer's identity
+ * matters: the length-prefixed transcript is hashed with BLAKE3 under its
+ * own context and the 32-byte digest is signed, so anyone with the public
+ * key can check who vouched for the transcript.
+ *
+ * # Arguments
+ * * `transcript` - Handshake messages, in order
+ * * `dsa_secret` - ML-DSA-65 secret key (4032 bytes)
+ *
+ * # Returns
+ * Signature (3309 bytes)
+ */
+export declare function signTranscript(transcript: Array<Buffer>, dsaSecret: Buffer): Buffer
+/**
+ * Verify a signature produced by `sign_transcript`
+ *
+ * # Arguments
+ * * `transcript` - Handshake messages, in the order they were signed
+ * * `signature` - Signature to verify (3309 bytes)
+ * * `dsa_public` - ML-DSA-65 public key (1952 bytes)
+ *
+ * # Returns
+ * true if the signature covers exactly these messages in this order
+ */
+export declare function verifyTranscript(transcript: Array<Buffer>, signature: Buffer, dsaPublic: Buffer): boolean
+/**
+ * Derive a public session identifier from a KEM shared secret
+ *
+ * `sid:` followed by the hex of the first 16 bytes of
+ * `derive_key("session-id", shared_secret)`. Both ends of a handshake get
+ * the same id, and it can be logged or sent in the clear: BLAKE3 is
+ * one-way and the context separates it from every other key derived from
+ * the secret.
+ *
+ * # Arguments
+ * * `shared_secret` - KEM shared secret (32 bytes)
+ *
+ * # Returns
+ * Session id string, `sid:` plus 32 hex characters
+ */
+export declare function sessionId(sharedSecret: Buffer): string
+/**
+ * Combine an ML-KEM and an X25519 shared secret into one key
+ *
+ * Both secrets are length-prefixed and hashed together with BLAKE3 in
+ * `derive_key` mode, so the output stays secret as long as either input
+ * does: a break of ML-KEM or of X25519 alone does not reveal the combined
+ * key. The same inputs always give the same key.
+ *
+ * # Arguments
+ * * `mlkem_secret` - ML-KEM shared secret (32 bytes)
+ * * `x25519_secret` - X25519 shared secret (32 bytes)
+ *
+ * # Returns
+ * 32-byte combined key
+ */
+export declare function hybridKemCombine(mlkemSecret: Buffer, x25519Secret: Buffer): Buffer
+/**
+ * Encapsulate one random group secret to several ML-KEM-768 public keys
+ *
+ * A fresh 32-byte group secret is generated, then for each recipient an
+ * ML-KEM encapsulation is performed and its shared secret (via HKDF-SHA256)
+ * AES-256-GCM-wraps the group secret. The KEM ciphertext is bound as
+ * associated data, so wrapped secrets cannot be swapped between recipients.
+ *
+ * # Arguments
+ * * `public_keys` - ML-KEM-768 public keys (1184 bytes each)
+ *
+ * # Returns
+ * GroupEncapsulation with one ciphertext and wrapped secret per recipient
+ */
+export declare function groupEncapsulate(publicKeys: Array<Buffer>): GroupEncapsulation
+/**
+ * Recover the group secret produced by `group_encapsulate`
+ *
+ * # Arguments
+ * * `my_secret` - Recipient's ML-KEM-768 secret key (2400 bytes)
+ * * `my_ciphertext` - Recipient's entry from `per_recipient_ciphertexts`
+ * * `wrapped_group_secret` - Recipient's entry from `wrapped_group_secrets`
+ *
+ * # Returns
+ * Group secret (32 bytes)
+ */
+export declare function groupDecapsulate(mySecret: Buffer, myCiphertext: Buffer, wrappedGroupSecret: Buffer): Buffer
+/**
+ * Derive a key bound to a shared secret and a group's membership
+ *
+ * The fingerprints are lowercased, sorted and length-prefixed before
+ * hashing with the secret, so the key depends on who is in the group but
+ * not on the order members are listed. Adding, removing or replacing any
+ * member changes the key, so members who disagree about the membership
+ * fail to decrypt instead of silently talking to a different group.
+ *
+ * # Arguments
+ * * `shared_secret` - Group secret, e.g. from `group_encapsulate` (32 bytes)
+ * * `member_fingerprints` - One fingerprint per member, e.g. from `quantum_fingerprint`
+ *
+ * # Returns
+ * 32-byte key; errors on an empty or duplicated member list
+ */
+export declare function groupKey(sharedSecret: Buffer, memberFingerprints: Array<string>): Buffer
+/**
+ * Encapsulate to an ML-KEM-768 public key, returning the secret wrapped
+ *
+ * The shared secret is AES-256-GCM-wrapped under `wrapping_key` and the
+ * native copy is zeroized, so this call returns only the wrapped form, never
+ * the raw secret. `unwrap_secret` does return the plaintext secret to its
+ * caller; keeping it off the JS heap means calling that only from the
+ * native code or enclave that holds `wrapping_key`.
+ *
+ * # Arguments
+ * * `public_key` - Recipient's ML-KEM-768 public key (1184 bytes)
+ * * `wrapping_key` - AES-256-GCM key, e.g. from `generate_symmetric_key` (32 bytes)
+ *
+ * # Returns
+ * WrappedEncapsulation with the KEM ciphertext and the wrapped secret
+ */
+export declare function mlkem768EncapsulateWrapped(publicKey: Buffer, wrappingKey: Buffer): WrappedEncapsulation
+/**
+ * Unwrap a shared secret from `mlkem768_encapsulate_wrapped`
+ *
+ * Returns the raw shared secret to the caller; called from JS, the secret
+ * is then on the JS heap like any other Buffer.
+ *
+ * # Arguments
+ * * `wrapped_secret` - `wrapped_secret` field of a WrappedEncapsulation
+ * * `wrapping_key` - Key the secret was wrapped under (32 bytes)
+ *
+ * # Returns
+ * Shared secret (32 bytes); errors if the key is wrong or the blob was modified
+ */
+export declare function unwrapSecret(wrappedSecret: Buffer, wrappingKey: Buffer): Buffer
+/** Keypairs recovered from an identity bundle */
+export interface IdentityBundle {
+  /** ML-KEM-768 keypair */
+  kem: KeyPair
+  /** ML-DSA-65 keypair */
+  dsa: KeyPair
+}
+/**
+ * Encrypt a secret key under a passphrase
+ *
+ * # Arguments
+ * * `secret_key` - Secret key bytes of any algorithm
+ * * `passphrase` - Passphrase to protect the key with
+ * * `min_entropy_bits` - Optional minimum estimated passphrase entropy;
+ *   weaker passphrases are rejected before any work is done
+ *
+ * # Returns
+ * Wrapped key blob for `decrypt_secret_key`
+ */
+export declare function encryptSecretKey(secretKey: Buffer, passphrase: string, minEntropyBits?: number | undefined | null): Buffer
+/**
+ * Decrypt a secret key wrapped by `encrypt_secret_key`
+ *
+ * # Arguments
+ * * `blob` - Wrapped key blob
+ * * `passphrase` - Passphrase used for wrapping
+ *
+ * # Returns
+ * Secret key bytes; errors on a wrong passphrase or a tampered blob
+ */
+export declare function decryptSecretKey(blob: Buffer, passphrase: string): Buffer
+/**
+ * Export ML-KEM and ML-DSA keypairs as one passphrase-protected bundle
+ *
+ * # Arguments
+ * * `kem` - ML-KEM-768 keypair
+ * * `dsa` - ML-DSA-65 keypair
+ * * `passphrase` - Passphrase protecting both secret keys
+ *
+ * # Returns
+ * Identity bundle for `import_identity_bundle`; the public keys stay
+ * readable with `identity_bundle_public_keys`
+ */
+export declare function exportIdentityBundle(kem: KeyPair, dsa: KeyPair, passphrase: string): Buffer
+/**
+ * Read the public keys of an identity bundle without the passphrase
+ *
+ * The keys are not authenticated until the bundle is imported; treat them
+ * as untrusted until then.
+ *
+ * # Arguments
+ * * `bundle` - Bundle from `export_identity_bundle`
+ *
+ * # Returns
+ * IdentityKeys with the ML-KEM and ML-DSA public keys
+ */
+export declare function identityBundlePublicKeys(bundle: Buffer): IdentityKeys
+/**
+ * Import both keypairs from an identity bundle
+ *
+ * # Arguments
+ * * `bundle` - Bundle from `export_identity_bundle`
+ * * `passphrase` - Passphrase used for export
+ *
+ * # Returns
+ * IdentityBundle with both keypairs; errors on a wrong passphrase, or if
+ * the public keys were altered after export
+ */
+export declare function importIdentityBundle(bundle: Buffer, passphrase: string): IdentityBundle
+/** Inclusion proof for one leaf of a Merkle tree */
+export interface MerkleProof {
+  /** Position of the proven leaf in the leaf list */
+  leafIndex: number
+  /** Number of leaves in the tree */
+  leafCount: number
+  /** Sibling hashes from the leaf level up to just below the root */
+  siblings: Array<Buffer>
+}
+/**
+ * Build the root of a Merkle tree with the given node hash
+ *
+ * # Arguments
+ * * `leaves` - Leaf data, in order (at least one)
+ * * `algorithm` - "blake3" or "sha3-256"
+ *
+ * # Returns
+ * 32-byte Merkle root
+ */
+export declare function buildMerkleRootWith(leaves: Array<Buffer>, algorithm: Algorithm): Buffer
+/**
+ * Build an inclusion proof for one leaf
+ *
+ * # Arguments
+ * * `leaves` - Leaf data, in order (at least one)
+ * * `index` - Position of the leaf to prove
+ * * `algorithm` - "blake3" or "sha3-256"; must match the one used for the root
+ *
+ * # Returns
+ * MerkleProof for `verify_merkle_proof_with`
+ */
+export declare function merkleProofWith(leaves: Array<Buffer>, index: number, algorithm: Algorithm): MerkleProof
+/**
+ * Verify that a leaf belongs to a Merkle tree
+ *
+ * # Arguments
+ * * `leaf` - Leaf data being proven
+ * * `proof` - Proof from `merkle_proof_with`
+ * * `root` - Expected 32-byte root
+ * * `algorithm` - "blake3" or "sha3-256"; must match the one used to build the tree
+ *
+ * # Returns
+ * True if the proof leads from `leaf` to `root`; false for any mismatch or malformed proof
+ */
+export declare function verifyMerkleProofWith(leaf: Buffer, proof: MerkleProof, root: Buffer, algorithm: Algorithm): boolean
+/**
+ * Derive the key for one epoch of a rotation schedule
+ *
+ * Keys for different epochs are independent: holding any number of epoch
+ * keys gives no way to compute another one without `master_secret`. The
+ * master itself can derive every epoch, past and future, so keep it out of
+ * the services that only need the current key.
+ *
+ * # Arguments
+ * * `master_secret` - Secret shared by everyone on the schedule (at least 32 bytes)
+ * * `epoch` - Epoch number, e.g. days since a fixed start (non-negative)
+ *
+ * # Returns
+ * 32-byte key for the epoch
+ */
+export declare function epochKey(masterSecret: Buffer, epoch: number): Buffer
+/** Public description of one embedded known-answer vector */
+export interface VectorInfo {
+  /** Vector identifier */
+  id: string
+  /** Algorithm the vector exercises */
+  algorithm: string
+  /** Where the expected value comes from, e.g. "NIST FIPS 202 examples" */
+  source: string
+}
+/** Outcome of running one known-answer vector */
+export interface SelfTestResult {
+  /** Vector identifier, as listed by `list_test_vectors` */
+  name: string
+  /** Whether the output matched the expected value */
+  passed: boolean
+  /** Wall-clock time to compute the output, in nanoseconds */
+  durationNs: number
+}
+/**
+ * Fingerprint of the module version and its known-answer outputs
+ *
+ * Hashes the crate version together with every embedded known-answer
+ * vector: its id, its expected value, and the actual output. The value is
+ * stable for a given build, and is expected to change whenever the crate
+ * version is bumped or an upgraded crypto dependency changes any output, so
+ * CI can pin it to detect silent behaviour changes.
+ *
+ * # Returns
+ * Fingerprint string in format "qf:{hash}"
+ */
+export declare function moduleFingerprint(): string
+/**
+ * List the embedded known-answer vectors and where they come from
+ *
+ * Lets auditors see exactly what `module_fingerprint` and the self-test
+ * cover. Vectors sourced as a "regression snapshot" have no published
+ * counterpart and only pin this crate's behaviour across upgrades.
+ *
+ * # Returns
+ * One VectorInfo per vector, in the order they are run
+ */
+export declare function listTestVectors(): Array<VectorInfo>
+/**
+ * Run every embedded known-answer vector with timing
+ *
+ * Checks each vector's output against its expected value and times the
+ * computation, so a startup check can catch both wrong results and
+ * performance regressions (e.g. a build that lost its SIMD backend).
+ * Timings are single runs and noisy; compare them against generous
+ * thresholds, or use `measure_speedup` for throughput figures.
+ *
+ * # Returns
+ * One SelfTestResult per vector, in the order they are run
+ */
+export declare function runSelfTestsDetailed(): Array<SelfTestResult>
+/**
+ * Export a deterministic interop test vector as JSON
+ *
+ * Expands `seed` with BLAKE3 into the FIPS 203 randomness `d || z || m`,
+ * generates a keypair from `d || z` and encapsulates to it with `m`. The
+ * JSON carries the seed, `d`, `z`, `m`, both keys, the ciphertext and the
+ * shared secret, all hex-encoded, so another implementation can start from
+ * `d`, `z` and `m` directly and compare every output.
+ *
+ * Only ML-KEM-768 is supported: ML-DSA-65 signing in this crate goes
+ * through `pqcrypto-dilithium`, which has no seeded key generation.
+ *
+ * # Arguments
+ * * `algorithm` - Algorithm to export a vector for ("ml-kem-768")
+ * * `seed` - 32-byte seed
+ *
+ * # Returns
+ * JSON test vector
+ */
+export declare function exportTestVector(algorithm: Algorithm, seed: Buffer): string
+/**
+ * Encapsulate a shared secret off the main thread
+ *
+ * Async counterpart of `mlkem768_encapsulate`. A malformed public key
+ * rejects the returned Promise with the same error as the sync function.
+ *
+ * # Arguments
+ * * `public_key` - ML-KEM-768 public key (1184 bytes)
+ *
+ * # Returns
+ * Promise of an EncapsulatedSecret
+ */
+export declare function mlkem768EncapsulateAsync(publicKey: Buffer): Promise<EncapsulatedSecret>
+/**
+ * Decapsulate a shared secret off the main thread
+ *
+ * Async counterpart of `mlkem768_decapsulate`. Malformed inputs reject the
+ * returned Promise with the same error as the sync function.
+ *
+ * # Arguments
+ * * `ciphertext` - ML-KEM-768 ciphertext (1088 bytes)
+ * * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+ *
+ * # Returns
+ * Promise of the 32-byte shared secret
+ */
+export declare function mlkem768DecapsulateAsync(ciphertext: Buffer, secretKey: Buffer): Promise<Buffer>
+/** Convert hex string to bytes */
+export declare function hexToBytes(hex: string): Buffer
 /** Convert bytes to hex string */
 export declare function bytesToHex(bytes: Buffer): string
 /** Generate random bytes */
 export declare function randomBytes(length: number): Buffer
 /** Constant-time comparison of buffers */
 export declare function constantTimeCompare(a: Buffer, b: Buffer): boolean
+/**
+ * Constant-time select between two buffers
+ *
+ * Returns a copy of `a` if `condition` is true and of `b` otherwise, using
+ * `subtle`'s branchless byte selection so the choice doesn't show up in
+ * timing. `condition` itself crosses the JS boundary as a plain boolean, so
+ * keep the code computing it branch-free as well.
+ *
+ * # Arguments
+ * * `condition` - Which input to return
+ * * `a` - Returned when `condition` is true
+ * * `b` - Returned when `condition` is false; must be the same length as `a`
+ *
+ * # Returns
+ * Copy of the selected buffer
+ */
+export declare function ctSelect(condition: boolean, a: Buffer, b: Buffer): Buffer
+/**
+ * Check whether a secret is trivially weak, in constant time
+ *
+ * A defensive guard against RNG or KEM failures: returns true for an empty
+ * secret or one whose bytes are all identical (including all zeros). The
+ * whole buffer is always scanned. Callers should abort on a true result.
+ *
+ * # Arguments
+ * * `secret` - Shared secret or key to check
+ *
+ * # Returns
+ * true if the secret is empty or made of a single repeated byte
+ */
+export declare function isWeakSecret(secret: Buffer): boolean
+/**
+ * Check whether a key is an all-zero placeholder
+ *
+ * Earlier builds returned zero-filled ML-DSA keys from a stub keygen. The
+ * current keygen is real, but zero-filled keys can still turn up from old
+ * fixtures or uninitialised buffers. Empty buffers count as placeholders.
+ *
+ * # Arguments
+ * * `key` - Public or secret key bytes
+ *
+ * # Returns
+ * true if every byte is zero
+ */
+export declare function isStubKey(key: Buffer): boolean
+/**
+ * Constant-time equality check for secret keys
+ *
+ * Intended for tests and key-management code: comparing secret keys with
+ * `Buffer.equals` in JS exits at the first differing byte and leaks timing.
+ * Keys of different lengths compare unequal.
+ *
+ * # Arguments
+ * * `a` - First secret key
+ * * `b` - Second secret key
+ *
+ * # Returns
+ * true if both keys have the same length and bytes
+ */
+export declare function secretKeysEqual(a: Buffer, b: Buffer): boolean
+/**
+ * Strip trailing whitespace from a key buffer and check its length
+ *
+ * Keys read from files often carry a trailing newline. Trailing ASCII
+ * whitespace (space, tab, CR, LF, VT, FF) is only removed when the buffer
+ * is longer than `expected_len`, so a binary key of the right length whose
+ * last byte happens to be 0x0a or 0x20 is returned untouched.
+ *
+ * # Arguments
+ * * `buf` - Key bytes as loaded
+ * * `expected_len` - Required key length in bytes
+ *
+ * # Returns
+ * The key without the trailing whitespace; errors if the result is not
+ * `expected_len` bytes
+ */
+export declare function normalizeKeyBuffer(buf: Buffer, expectedLen: number): Buffer
+/**
+ * Convert hex string to bytes in constant time
+ *
+ * Unlike `hex_to_bytes`, every character is decoded without branching on
+ * its value and invalid characters are only reported after the whole
+ * string has been processed. Use this for hex-encoded secret keys. Only
+ * the string length (and an optional `0x` prefix) may influence timing.
+ */
+export declare function decodeHexCt(s: string): Buffer
 /** Initialize the QuDAG native module */
 export declare function init(): string
 /** Get module version */
 export declare function version(): string
+/**
+ * Get the backend this module was compiled for
+ *
+ * Returns "wasm" when built for `wasm32` targets and "native" for the
+ * Node.js addon. The native addon is typically ~3x faster, so this is
+ * useful when logging or explaining performance differences.
+ */
+export declare function backendKind(): string
 /** Get module information */
 export interface ModuleInfo {
   name: string
@@ -138,3 +2323,223 @@ export interface ModuleInfo {
 }
 /** Get detailed module information */
 export declare function getModuleInfo(): ModuleInfo
+/**
+ * ML-KEM-768 keypair held natively for long-lived processes
+ *
+ * Keeps the secret key out of JS-managed memory: only the public key is
+ * readable, and the secret key is used through `decapsulate`. Supports
+ * in-place key rotation: `rotate()` zeroizes the old secret key and
+ * replaces both keys while the object identity stays the same, so callers
+ * holding a reference transparently get the new keys.
+ */
+export class MlKemKeyPair {
+  /** Generate a new ML-KEM-768 keypair */
+  constructor()
+  /** Current public key (1184 bytes) */
+  get publicKey(): Buffer
+  /** Decapsulate a shared secret with the current secret key */
+  decapsulate(ciphertext: Buffer): Buffer
+  /** Zeroize the current secret key and replace both keys with a fresh pair */
+  rotate(): void
+}
+/**
+ * High-throughput ML-KEM-768 keypair generator
+ *
+ * Holds a ChaCha20 CSPRNG seeded once from the OS RNG and generates
+ * keypairs from it, avoiding per-call RNG setup. After `reseed_interval`
+ * keypairs (1024 by default) the generator replaces its state with a fresh
+ * OS seed, so a captured generator state exposes at most the keypairs of
+ * the current interval.
+ */
+export class KeyPairGenerator {
+  /** Create a generator, reseeding every `reseed_interval` keypairs (default 1024) */
+  constructor(reseedInterval?: number | undefined | null)
+  /** Generate an ML-KEM-768 keypair (1184-byte public, 2400-byte secret key) */
+  generate(): KeyPair
+  /** Number of times the generator has reseeded since construction */
+  get reseeds(): number
+}
+/**
+ * Assembles an ML-KEM-768 ciphertext received in fragments
+ *
+ * Fragments are appended in order with `push`; `build` returns the
+ * assembled ciphertext once exactly 1088 bytes have been received.
+ */
+export class EncapsulatedSecretBuilder {
+  /** Create an empty builder */
+  constructor()
+  /**
+   * Append the next ciphertext fragment
+   *
+   * Errors without appending if the fragment would take the total past
+   * 1088 bytes.
+   */
+  push(fragment: Buffer): void
+  /** Bytes received so far */
+  get length(): number
+  /** Return the assembled 1088-byte ciphertext */
+  build(): Buffer
+}
+/**
+ * Incremental ML-DSA-65 signer for streamed data
+ *
+ * Hashes chunks with BLAKE3 (in `derive_key` mode under a dedicated
+ * context) and signs the 32-byte digest with ML-DSA-65. This is the same
+ * hash-then-sign construction as `mldsa65_sign_multi`, not the FIPS 204
+ * HashML-DSA variant, which `pqcrypto-dilithium` does not provide.
+ * Chunk boundaries do not affect the signature, so a stream signed in
+ * pieces verifies as one message with `mldsa65_verify_stream`, and vice
+ * versa.
+ */
+export class StreamSigner {
+  /** Create a signer with an empty stream */
+  constructor()
+  /** Absorb the next chunk of the stream */
+  update(chunk: Buffer): void
+  /** Sign everything absorbed so far with an ML-DSA-65 secret key (4032 bytes) */
+  finalize(secretKey: Buffer): Buffer
+}
+/** Incremental verifier for `StreamSigner` signatures */
+export class StreamVerifier {
+  /** Create a verifier with an empty stream */
+  constructor()
+  /** Absorb the next chunk of the stream */
+  update(chunk: Buffer): void
+  /** Verify a 3309-byte signature over everything absorbed so far */
+  verify(signature: Buffer, publicKey: Buffer): boolean
+}
+/**
+ * Incremental BLAKE3 hasher whose state can be saved and restored
+ *
+ * Produces the same digest as `blake3_hash` over the concatenated input.
+ * The hasher tracks the BLAKE3 tree itself (chaining values of completed
+ * subtrees plus the unfinished last chunk), so `checkpoint` can serialize
+ * it and `resume` can carry on in another process after a restart.
+ *
+ * Checkpoint layout:
+ * `magic "QB3C" (4) || version (1) || chunk count (8, LE) ||
+ * stack depth (1) || chaining values (32 each) || pending length (2, LE) ||
+ * pending bytes`
+ *
+ * The format is specific to this version of the module and is not a
+ * stable interchange format; only resume checkpoints written by the same
+ * release. A checkpoint holds up to 1 KiB of recent input in the clear.
+ */
+export class Blake3Hasher {
+  /** Create an empty hasher */
+  constructor()
+  /** Absorb more input */
+  update(data: Buffer): void
+  /**
+   * Read up to `length` bytes from an open file descriptor and absorb them
+   *
+   * Reads from the descriptor's current position, as `fs.readSync` does
+   * without a position, and leaves the descriptor open. Short reads are
+   * retried; EOF ends the read early. Returns the number of bytes absorbed,
+   * which is less than `length` only at EOF. Unix only.
+   */
+  updateFromFd(fd: number, length: number): number
+  /** Return the 32-byte digest of everything absorbed so far */
+  finalize(): Buffer
+  /**
+   * Return the digest of everything absorbed so far as lowercase hex
+   *
+   * Like `finalize`, this leaves the hasher usable, so progress displays
+   * can show the running digest between updates.
+   */
+  finalizeHex(): string
+  /** Serialize the current state for `resume` */
+  checkpoint(): Buffer
+  /** Rebuild a hasher from a `checkpoint` written by this module version */
+  static resume(checkpoint: Buffer): Blake3Hasher
+}
+/**
+ * Incremental reader over BLAKE3 extendable output
+ *
+ * Each `read` continues where the previous one stopped, so successive reads
+ * produce the same bytes as one `blake3_hash_xof` of the combined length.
+ */
+export class Blake3Xof {
+  /** Create an XOF reader over the BLAKE3 output of `data` */
+  constructor(data: Buffer)
+  /** Read the next `len` bytes (at most 65536) and advance the position */
+  read(len: number): Buffer
+  /** Current position in the output stream, in bytes */
+  get position(): number
+}
+/**
+ * Keyed BLAKE3 MAC that rekeys every `interval` bytes
+ *
+ * Input is split into fixed segments of `interval` bytes. Each segment is
+ * keyed-hashed under its own key; when a segment is complete, the next key
+ * is `derive_key("qudag-napi rekeying hasher v1", key || segment tag)` and
+ * the old key is erased. A key captured mid-stream therefore reveals
+ * nothing about earlier segment keys. `finalize` returns the keyed hash of
+ * the last (possibly partial) segment, which depends on every segment
+ * before it.
+ *
+ * Segment boundaries depend only on byte counts, so the output does not
+ * depend on how input is split across `update` calls. The output is not
+ * interoperable with plain or keyed BLAKE3.
+ */
+export class RekeyingHasher {
+  /** Create a hasher from a 32-byte key, rekeying every `interval` bytes */
+  constructor(key: Buffer, interval: number)
+  /** Absorb more input */
+  update(data: Buffer): void
+  /** Return the 32-byte tag over everything absorbed so far */
+  finalize(): Buffer
+}
+/**
+ * Rolling hash over a fixed-size sliding window
+ *
+ * A cyclic polynomial (buzhash) over 32-bit words: each `roll` moves the
+ * window one byte in O(1), and the value always equals `rolling_hash` of
+ * the bytes currently in the window. The window starts out filled with
+ * zero bytes, so after `window_size` rolls from a fresh hasher (passing 0
+ * as `byte_out`) the value covers exactly the bytes rolled in.
+ *
+ * This is not a cryptographic hash; use it only to pick chunk boundaries,
+ * then hash the chunks with BLAKE3.
+ */
+export class RollingHasher {
+  /** Create a hasher over a window of `window_size` bytes (1 to 4096) */
+  constructor(windowSize: number)
+  /**
+   * Slide the window: add `byte_in`, drop `byte_out` (the byte that entered
+   * `window_size` rolls ago), and return the new hash
+   */
+  roll(byteIn: number, byteOut: number): number
+  /** Hash of the current window */
+  get value(): number
+}
+/**
+ * In-process guard against AES-GCM nonce reuse
+ *
+ * Remembers every nonce sealed through it, per derived key, and refuses to
+ * seal twice with the same key and nonce. Keys are tracked by a BLAKE3
+ * digest of the AEAD key, never the shared secret itself. Memory grows with
+ * the number of sealed messages, and state is lost on restart, so persistent
+ * counters are still needed across processes.
+ */
+export class NonceTracker {
+  /** Create an empty tracker */
+  constructor()
+  /** Seal like `aead_seal_with_nonce`, erroring if the nonce was already used with this key */
+  seal(sharedSecret: Buffer, plaintext: Buffer, nonce: Buffer, aad?: Buffer | undefined | null): Buffer
+}
+/**
+ * Symmetric KDF chain producing one fresh message key per step
+ *
+ * Two ratchets seeded with the same shared secret produce the same sequence
+ * of message keys, so both ends of a session can derive matching keys
+ * without further communication.
+ */
+export class KeyRatchet {
+  /** Create a ratchet seeded from a 32-byte shared secret */
+  constructor(sharedSecret: Buffer)
+  /** Return the current message key (32 bytes) and advance the chain */
+  next(): Buffer
+  /** Number of message keys produced so far */
+  get step(): number
+}
@@ -310,21 +310,171 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { mlkem768GenerateKeypair, mlkem768Encapsulate, mlkem768Decapsulate, mldsa65GenerateKeypair, mldsa65Sign, mldsa65Verify, blake3Hash, blake3HashHex, quantumFingerprint, hexToBytes, bytesToHex, randomBytes, constantTimeCompare, init, version, getModuleInfo } = nativeBinding
+const { measureSpeedup, Algorithm, mlkem768GenerateKeypair, mlkem768GenerateKeypairOwned, mlkem768GenerateKeypairWithSeed, mlkem768ExpandSecretKey, mlkem768DeriveKeypair, exampleKeypair, mlkem768Encapsulate, mlkem768EncapsulateBatchParallel, mlkem768EncapsulateWithCoins, mlkem768EncapsulateDeterministic, mlkem768Decapsulate, mlkem768DecapsulateAt, ciphertextsShareSecret, ciphertextMatchesKey, mlkem768EncapsulateBound, mlkem768DecapsulateBound, mlkem768ValidateKeypair, mlkem768PublicKeyReencode, secretKeyMetadata, verifySecretMatchesFingerprint, mldsa65GenerateKeypair, maxDirectSignBytes, setMaxDirectSignBytes, mldsa65Sign, mldsa65Verify, mldsa65VerifyVerbose, signatureBindsMessage, mldsa65SignMulti, mldsa65VerifyMulti, mldsa65VerifyWithExpectedFingerprint, allSignaturesValid, mldsa65SignStream, mldsa65VerifyStream, x25519GenerateKeypair, x25519DiffieHellman, blake3Hash, blake3HashInto, blake3HashHex, blake3VerifyMany, sha3256Hash, compositeHash, verifyCompositeHash, blake3HashXof, blake3HashRange, emptyDigest, blake3HashChunked, blake3KeyedHash, blake3VerifyMac, blake3DeriveKey, blake3HashWithContext, quantumFingerprint, quantumFingerprintShake, quantumFingerprintSha3, multiFingerprint, quantumFingerprintDomain, verifyFingerprintDomain, parseFingerprint, fingerprintEqualsData, commitPublicKeySet, verifySetCommitment, keypairHash, generateKeypair, supportedAlgorithms, estimatedMemory, keypairToCbor, keypairFromCbor, encapsulatedSecretToCbor, encapsulatedSecretFromCbor, mldsa65SignToSigFormat, mldsa65VerifySigFormat, mldsa65SignAttached, parseAttachedHeader, mldsa65OpenAttached, buildIdentity, verifyIdentity, notarize, verifyNotarization, generateSelfSigned, verifySelfSigned, encapsulatedSecretToWire, ciphertextFromWire, loadPublicKeyEnv, publicKeyToAddress, validateAddress, mlkem768SecretKeyToPkcs8, mlkem768SecretKeyFromPkcs8, blake3HashTree, mldsa65SignFile, mldsa65VerifyFile, blake3EncodeVerified, blake3DecodeVerified, rollingHash, findChunkBoundaries, hybridEncrypt, hybridDecrypt, hybridEncryptDeterministic, hybridEncryptPadded, hybridDecryptPadded, hybridCiphertextVersion, hybridDecryptSafe, exportSecretToPeer, importSecretFromPeer, sealEnvelope, openEnvelope, envelopeIntegrityOk, deriveAeadKey, deriveEncMacKeys, deriveConfirmationTags, verifyInitiatorTag, verifyResponderTag, generateSymmetricKey, aeadSealWithNonce, aeadOpenWithNonce, transcriptMac, verifyTranscriptMac, signTranscript, verifyTranscript, sessionId, hybridKemCombine, groupEncapsulate, groupDecapsulate, groupKey, mlkem768EncapsulateWrapped, unwrapSecret, encryptSecretKey, decryptSecretKey, exportIdentityBundle, identityBundlePublicKeys, importIdentityBundle, buildMerkleRootWith, merkleProofWith, verifyMerkleProofWith, epochKey, moduleFingerprint, listTestVectors, runSelfTestsDetailed, exportTestVector, mlkem768EncapsulateAsync, mlkem768DecapsulateAsync, hexToBytes, bytesToHex, randomBytes, constantTimeCompare, ctSelect, isWeakSecret, isStubKey, secretKeysEqual, normalizeKeyBuffer, decodeHexCt, init, version, backendKind, getModuleInfo, MlKemKeyPair, KeyPairGenerator, EncapsulatedSecretBuilder, StreamSigner, StreamVerifier, Blake3Hasher, Blake3Xof, RekeyingHasher, RollingHasher, NonceTracker, KeyRatchet } = nativeBinding
 
+module.exports.measureSpeedup = measureSpeedup
+module.exports.Algorithm = Algorithm
 module.exports.mlkem768GenerateKeypair = mlkem768GenerateKeypair
+module.exports.mlkem768GenerateKeypairOwned = mlkem768GenerateKeypairOwned
+module.exports.mlkem768GenerateKeypairWithSeed = mlkem768GenerateKeypairWithSeed
+module.exports.mlkem768ExpandSecretKey = mlkem768ExpandSecretKey
+module.exports.mlkem768DeriveKeypair = mlkem768DeriveKeypair
+module.exports.exampleKeypair = exampleKeypair
 module.exports.mlkem768Encapsulate = mlkem768Encapsulate
+module.exports.mlkem768EncapsulateBatchParallel = mlkem768EncapsulateBatchParallel
+module.exports.mlkem768EncapsulateWithCoins = mlkem768EncapsulateWithCoins
+module.exports.mlkem768EncapsulateDeterministic = mlkem768EncapsulateDeterministic
 module.exports.mlkem768Decapsulate = mlkem768Decapsulate
+module.exports.mlkem768DecapsulateAt = mlkem768DecapsulateAt
+module.exports.ciphertextsShareSecret = ciphertextsShareSecret
+module.exports.ciphertextMatchesKey = ciphertextMatchesKey
+module.exports.mlkem768EncapsulateBound = mlkem768EncapsulateBound
+module.exports.mlkem768DecapsulateBound = mlkem768DecapsulateBound
+module.exports.mlkem768ValidateKeypair = mlkem768ValidateKeypair
+module.exports.mlkem768PublicKeyReencode = mlkem768PublicKeyReencode
+module.exports.secretKeyMetadata = secretKeyMetadata
+module.exports.verifySecretMatchesFingerprint = verifySecretMatchesFingerprint
 module.exports.mldsa65GenerateKeypair = mldsa65GenerateKeypair
+module.exports.maxDirectSignBytes = maxDirectSignBytes
+module.exports.setMaxDirectSignBytes = setMaxDirectSignBytes
 module.exports.mldsa65Sign = mldsa65Sign
 module.exports.mldsa65Verify = mldsa65Verify
+module.exports.mldsa65VerifyVerbose = mldsa65VerifyVerbose
+module.exports.signatureBindsMessage = signatureBindsMessage
+module.exports.mldsa65SignMulti = mldsa65SignMulti
+module.exports.mldsa65VerifyMulti = mldsa65VerifyMulti
+module.exports.mldsa65VerifyWithExpectedFingerprint = mldsa65VerifyWithExpectedFingerprint
+module.exports.allSignaturesValid = allSignaturesValid
+module.exports.mldsa65SignStream = mldsa65SignStream
+module.exports.mldsa65VerifyStream = mldsa65VerifyStream
+module.exports.x25519GenerateKeypair = x25519GenerateKeypair
+module.exports.x25519DiffieHellman = x25519DiffieHellman
 module.exports.blake3Hash = blake3Hash
+module.exports.blake3HashInto = blake3HashInto
 module.exports.blake3HashHex = blake3HashHex
+module.exports.blake3VerifyMany = blake3VerifyMany
+module.exports.sha3256Hash = sha3256Hash
+module.exports.compositeHash = compositeHash
+module.exports.verifyCompositeHash = verifyCompositeHash
+module.exports.blake3HashXof = blake3HashXof
+module.exports.blake3HashRange = blake3HashRange
+module.exports.emptyDigest = emptyDigest
+module.exports.blake3HashChunked = blake3HashChunked
+module.exports.blake3KeyedHash = blake3KeyedHash
+module.exports.blake3VerifyMac = blake3VerifyMac
+module.exports.blake3DeriveKey = blake3DeriveKey
+module.exports.blake3HashWithContext = blake3HashWithContext
 module.exports.quantumFingerprint = quantumFingerprint
+module.exports.quantumFingerprintShake = quantumFingerprintShake
+module.exports.quantumFingerprintSha3 = quantumFingerprintSha3
+module.exports.multiFingerprint = multiFingerprint
+module.exports.quantumFingerprintDomain = quantumFingerprintDomain
+module.exports.verifyFingerprintDomain = verifyFingerprintDomain
+module.exports.parseFingerprint = parseFingerprint
+module.exports.fingerprintEqualsData = fingerprintEqualsData
+module.exports.commitPublicKeySet = commitPublicKeySet
+module.exports.verifySetCommitment = verifySetCommitment
+module.exports.keypairHash = keypairHash
+module.exports.generateKeypair = generateKeypair
+module.exports.supportedAlgorithms = supportedAlgorithms
+module.exports.estimatedMemory = estimatedMemory
+module.exports.keypairToCbor = keypairToCbor
+module.exports.keypairFromCbor = keypairFromCbor
+module.exports.encapsulatedSecretToCbor = encapsulatedSecretToCbor
+module.exports.encapsulatedSecretFromCbor = encapsulatedSecretFromCbor
+module.exports.mldsa65SignToSigFormat = mldsa65SignToSigFormat
+module.exports.mldsa65VerifySigFormat = mldsa65VerifySigFormat
+module.exports.mldsa65SignAttached = mldsa65SignAttached
+module.exports.parseAttachedHeader = parseAttachedHeader
+module.exports.mldsa65OpenAttached = mldsa65OpenAttached
+module.exports.buildIdentity = buildIdentity
+module.exports.verifyIdentity = verifyIdentity
+module.exports.notarize = notarize
+module.exports.verifyNotarization = verifyNotarization
+module.exports.generateSelfSigned = generateSelfSigned
+module.exports.verifySelfSigned = verifySelfSigned
+module.exports.encapsulatedSecretToWire = encapsulatedSecretToWire
+module.exports.ciphertextFromWire = ciphertextFromWire
+module.exports.loadPublicKeyEnv = loadPublicKeyEnv
+module.exports.publicKeyToAddress = publicKeyToAddress
+module.exports.validateAddress = validateAddress
+module.exports.mlkem768SecretKeyToPkcs8 = mlkem768SecretKeyToPkcs8
+module.exports.mlkem768SecretKeyFromPkcs8 = mlkem768SecretKeyFromPkcs8
+module.exports.blake3HashTree = blake3HashTree
+module.exports.mldsa65SignFile = mldsa65SignFile
+module.exports.mldsa65VerifyFile = mldsa65VerifyFile
+module.exports.blake3EncodeVerified = blake3EncodeVerified
+module.exports.blake3DecodeVerified = blake3DecodeVerified
+module.exports.rollingHash = rollingHash
+module.exports.findChunkBoundaries = findChunkBoundaries
+module.exports.hybridEncrypt = hybridEncrypt
+module.exports.hybridDecrypt = hybridDecrypt
+module.exports.hybridEncryptDeterministic = hybridEncryptDeterministic
+module.exports.hybridEncryptPadded = hybridEncryptPadded
+module.exports.hybridDecryptPadded = hybridDecryptPadded
+module.exports.hybridCiphertextVersion = hybridCiphertextVersion
+module.exports.hybridDecryptSafe = hybridDecryptSafe
+module.exports.exportSecretToPeer = exportSecretToPeer
+module.exports.importSecretFromPeer = importSecretFromPeer
+module.exports.sealEnvelope = sealEnvelope
+module.exports.openEnvelope = openEnvelope
+module.exports.envelopeIntegrityOk = envelopeIntegrityOk
+module.exports.deriveAeadKey = deriveAeadKey
+module.exports.deriveEncMacKeys = deriveEncMacKeys
+module.exports.deriveConfirmationTags = deriveConfirmationTags
+module.exports.verifyInitiatorTag = verifyInitiatorTag
+module.exports.verifyResponderTag = verifyResponderTag
+module.exports.generateSymmetricKey = generateSymmetricKey
+module.exports.aeadSealWithNonce = aeadSealWithNonce
+module.exports.aeadOpenWithNonce = aeadOpenWithNonce
+module.exports.transcriptMac = transcriptMac
+module.exports.verifyTranscriptMac = verifyTranscriptMac
+module.exports.signTranscript = signTranscript
+module.exports.verifyTranscript = verifyTranscript
+module.exports.sessionId = sessionId
+module.exports.hybridKemCombine = hybridKemCombine
+module.exports.groupEncapsulate = groupEncapsulate
+module.exports.groupDecapsulate = groupDecapsulate
+module.exports.groupKey = groupKey
+module.exports.mlkem768EncapsulateWrapped = mlkem768EncapsulateWrapped
+module.exports.unwrapSecret = unwrapSecret
+module.exports.encryptSecretKey = encryptSecretKey
+module.exports.decryptSecretKey = decryptSecretKey
+module.exports.exportIdentityBundle = exportIdentityBundle
+module.exports.identityBundlePublicKeys = identityBundlePublicKeys
+module.exports.importIdentityBundle = importIdentityBundle
+module.exports.buildMerkleRootWith = buildMerkleRootWith
+module.exports.merkleProofWith = merkleProofWith
+module.exports.verifyMerkleProofWith = verifyMerkleProofWith
+module.exports.epochKey = epochKey
+module.exports.moduleFingerprint = moduleFingerprint
+module.exports.listTestVectors = listTestVectors
+module.exports.runSelfTestsDetailed = runSelfTestsDetailed
+module.exports.exportTestVector = exportTestVector
+module.exports.mlkem768EncapsulateAsync = mlkem768EncapsulateAsync
+module.exports.mlkem768DecapsulateAsync = mlkem768DecapsulateAsync
 module.exports.hexToBytes = hexToBytes
 module.exports.bytesToHex = bytesToHex
 module.exports.randomBytes = randomBytes
 module.exports.constantTimeCompare = constantTimeCompare
+module.exports.ctSelect = ctSelect
+module.exports.isWeakSecret = isWeakSecret
+module.exports.isStubKey = isStubKey
+module.exports.secretKeysEqual = secretKeysEqual
+module.exports.normalizeKeyBuffer = normalizeKeyBuffer
+module.exports.decodeHexCt = decodeHexCt
 module.exports.init = init
 module.exports.version = version
+module.exports.backendKind = backendKind
 module.exports.getModuleInfo = getModuleInfo
+module.exports.MlKemKeyPair = MlKemKeyPair
+module.exports.KeyPairGenerator = KeyPairGenerator
+module.exports.EncapsulatedSecretBuilder = EncapsulatedSecretBuilder
+module.exports.StreamSigner = StreamSigner
+module.exports.StreamVerifier = StreamVerifier
+module.exports.Blake3Hasher = Blake3Hasher
+module.exports.Blake3Xof = Blake3Xof
+module.exports.RekeyingHasher = RekeyingHasher
+module.exports.RollingHasher = RollingHasher
+module.exports.NonceTracker = NonceTracker
+module.exports.KeyRatchet = KeyRatchet
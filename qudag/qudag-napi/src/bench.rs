@@ -0,0 +1,122 @@
+//! Runtime throughput measurement
+//!
+//! Published speedups (~3x for the native addon over the WASM build) depend
+//! on hardware, so `measure_speedup` lets users time an operation on their
+//! own machine. Run it under both backends with the same arguments and
+//! compare the `ops_per_sec` figures; each result carries `backend_kind` so
+//! logged measurements stay attributable.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::backend_kind;
+use crate::crypto::{
+  mldsa65_generate_keypair, mldsa65_sign, mldsa65_verify, mlkem768_decapsulate, mlkem768_encapsulate,
+  mlkem768_generate_keypair,
+};
+
+/// Operations accepted by `measure_speedup`
+const BENCH_OPERATIONS: [&str; 6] = [
+  "mlkem768-keygen",
+  "mlkem768-encapsulate",
+  "mlkem768-decapsulate",
+  "mldsa65-sign",
+  "mldsa65-verify",
+  "blake3-1kib",
+];
+
+/// Throughput of one operation on the running backend
+#[napi(object)]
+pub struct SpeedMeasurement {
+  /// Operation that was timed
+  pub operation: String,
+  /// Number of timed iterations
+  pub iterations: u32,
+  /// Completed operations per second
+  pub ops_per_sec: f64,
+  /// `backend_kind()` of the module that took the measurement
+  pub backend: String,
+}
+
+/// Time an operation and report its throughput
+///
+/// Keys and inputs are prepared before timing starts, so only the named
+/// operation is measured.
+///
+/// # Arguments
+/// * `operation` - One of "mlkem768-keygen", "mlkem768-encapsulate",
+///   "mlkem768-decapsulate", "mldsa65-sign", "mldsa65-verify" or "blake3-1kib"
+/// * `iterations` - Number of timed runs (at least 1)
+///
+/// # Returns
+/// SpeedMeasurement with the ops/sec figure and the backend it was taken on
+#[napi]
+pub fn measure_speedup(operation: String, iterations: u32) -> Result<SpeedMeasurement> {
+  if iterations == 0 {
+    return Err(Error::from_reason("Iterations must be at least 1"));
+  }
+  if !BENCH_OPERATIONS.contains(&operation.as_str()) {
+    return Err(Error::from_reason(format!(
+      "Unknown benchmark operation: {} (expected one of {})",
+      operation,
+      BENCH_OPERATIONS.join(", ")
+    )));
+  }
+
+  let kem = mlkem768_generate_keypair()?;
+  let dsa = mldsa65_generate_keypair()?;
+  let encapsulated = mlkem768_encapsulate(kem.public_key.clone())?;
+  let message: Buffer = vec![0x5a; 1024].into();
+  let signature = mldsa65_sign(message.clone(), dsa.secret_key.clone())?;
+
+  let start = Instant::now();
+  for _ in 0..iterations {
+    match operation.as_str() {
+      "mlkem768-keygen" => {
+        black_box(mlkem768_generate_keypair()?);
+      }
+      "mlkem768-encapsulate" => {
+        black_box(mlkem768_encapsulate(kem.public_key.clone())?);
+      }
+      "mlkem768-decapsulate" => {
+        black_box(mlkem768_decapsulate(encapsulated.ciphertext.clone(), kem.secret_key.clone())?);
+      }
+      "mldsa65-sign" => {
+        black_box(mldsa65_sign(message.clone(), dsa.secret_key.clone())?);
+      }
+      "mldsa65-verify" => {
+        black_box(mldsa65_verify(message.clone(), signature.clone(), dsa.public_key.clone())?);
+      }
+      _ => {
+        black_box(blake3::hash(black_box(message.as_ref())));
+      }
+    }
+  }
+  let elapsed = start.elapsed().as_secs_f64().max(f64::MIN_POSITIVE);
+
+  Ok(SpeedMeasurement {
+    operation,
+    iterations,
+    ops_per_sec: iterations as f64 / elapsed,
+    backend: backend_kind(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_measure_speedup() {
+    let measurement = measure_speedup("blake3-1kib".to_string(), 100).unwrap();
+    assert!(measurement.ops_per_sec > 0.0 && measurement.ops_per_sec.is_finite());
+    assert_eq!(measurement.backend, backend_kind());
+
+    assert!(measure_speedup("mlkem768-encapsulate".to_string(), 3).unwrap().ops_per_sec > 0.0);
+    assert!(measure_speedup("md5".to_string(), 1).is_err());
+    assert!(measure_speedup("blake3-1kib".to_string(), 0).is_err());
+  }
+}
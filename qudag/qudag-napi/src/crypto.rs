@@ -3,15 +3,25 @@
 //! This module provides NIST-compliant post-quantum cryptography:
 //! - ML-KEM-768 (FIPS 203) - Key encapsulation mechanism
 //! - ML-DSA-65 (FIPS 204) - Digital signatures
+//! - X25519 (RFC 7748) - Classical key agreement for hybrid handshakes
 //! - BLAKE3 - Cryptographic hashing
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
-use ml_kem::{KemCore, MlKem768, EncodedSizeUser};
+use ml_kem::{KemCore, MlKem512, MlKem768, EncodedSizeUser, EncapsulateDeterministic, B32};
 use kem::{Decapsulate, Encapsulate};
 use pqcrypto_dilithium::dilithium3::*;
 use pqcrypto_traits::sign::{PublicKey as PqPublicKeyTrait, SecretKey as PqSecretKeyTrait, DetachedSignature as PqDetachedSignatureTrait};
-use rand::rngs::OsRng;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rayon::prelude::*;
+use sha3::digest::ExtendableOutput;
+use sha3::{Digest, Sha3_256, Shake256};
+use std::sync::atomic::{AtomicU32, Ordering};
+use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret as X25519Secret};
+use zeroize::Zeroize;
+
+use crate::rng::module_rng;
 
 /// Key pair containing public and secret keys
 #[napi(object)]
@@ -20,6 +30,18 @@ pub struct KeyPair {
   pub secret_key: Buffer,
 }
 
+/// Key pair returned as standalone `Uint8Array`s
+///
+/// Each array is backed by its own native allocation rather than Node's
+/// shared `Buffer` pool, so storing it long-term can't alias other data.
+#[napi(object)]
+pub struct OwnedKeyPair {
+  /// Public key bytes
+  pub public_key: Uint8Array,
+  /// Secret key bytes
+  pub secret_key: Uint8Array,
+}
+
 /// Encapsulated secret containing ciphertext and shared secret
 #[napi(object)]
 pub struct EncapsulatedSecret {
@@ -27,6 +49,190 @@ pub struct EncapsulatedSecret {
   pub shared_secret: Buffer,
 }
 
+/// Encapsulated secret together with the random coins used to produce it
+#[napi(object)]
+pub struct CoinsEncapsulatedSecret {
+  /// ML-KEM-768 ciphertext (1088 bytes)
+  pub ciphertext: Buffer,
+  /// Shared secret (32 bytes)
+  pub shared_secret: Buffer,
+  /// Encapsulation randomness `m` from FIPS 203 (32 bytes)
+  pub coins: Buffer,
+}
+
+/// ML-KEM-768 keypair together with its FIPS 203 key generation seed
+#[napi(object)]
+pub struct SeededKeyPair {
+  /// Public key (1184 bytes)
+  pub public_key: Buffer,
+  /// Expanded secret key (2400 bytes)
+  pub secret_key: Buffer,
+  /// Seed `d || z` (64 bytes); regenerates both keys via `mlkem768_expand_secret_key`
+  pub seed: Buffer,
+}
+
+/// Non-sensitive description of a secret key, safe to log
+#[napi(object)]
+pub struct KeyMetadata {
+  /// Algorithm inferred from the key length ("ML-KEM-768" or "ML-DSA-65")
+  pub algorithm: String,
+  /// Secret key length in bytes
+  pub length: u32,
+  /// `quantum_fingerprint` of the corresponding public key, when it can be
+  /// recovered from the secret key (ML-KEM only)
+  pub public_key_fingerprint: Option<String>,
+}
+
+/// One signature check for `all_signatures_valid`
+#[napi(object)]
+pub struct VerifyItem {
+  /// Signed message
+  pub message: Buffer,
+  /// ML-DSA-65 signature (3309 bytes)
+  pub signature: Buffer,
+  /// ML-DSA-65 public key (1952 bytes)
+  pub public_key: Buffer,
+}
+
+/// One digest check for `blake3_verify_many`
+#[napi(object)]
+pub struct DigestCheck {
+  /// Data to hash
+  pub data: Buffer,
+  /// Expected BLAKE3 digest (32 bytes)
+  pub expected: Buffer,
+}
+
+/// Result of `mldsa65_verify_verbose` and `signature_binds_message`
+#[napi(object)]
+pub struct VerifyReport {
+  /// Whether the signature is valid
+  pub valid: bool,
+  /// Why verification failed: "bad public key length", "bad signature
+  /// length" or "signature does not verify"; absent when valid
+  pub reason: Option<String>,
+  /// Likely cause, set by `signature_binds_message`: "bound",
+  /// "key_mismatch", "corrupt_signature" or "message_or_key_mismatch"
+  pub diagnosis: Option<String>,
+}
+
+/// Decoded quantum fingerprint
+#[napi(object)]
+pub struct ParsedFingerprint {
+  /// Digest algorithm: "blake3" for `qf:<hex>`, "shake256" for `qf:sk:<hex>`,
+  /// "sha3-256" for `qf:s3:<hex>`
+  pub algorithm: String,
+  /// Raw digest bytes
+  pub digest: Buffer,
+}
+
+/// Capability entry returned by `supported_algorithms`
+#[napi(object)]
+pub struct AlgorithmStatus {
+  /// Algorithm identifier, as accepted by algorithm-dispatch functions
+  pub name: String,
+  /// "kem", "signature" or "hash"
+  pub kind: String,
+  /// Whether the implementation is real rather than a placeholder
+  pub ready: bool,
+}
+
+/// Algorithm identifiers accepted by algorithm-dispatch functions
+///
+/// Exposed to TypeScript as a union of the string values below, so invalid
+/// names are rejected when arguments are converted.
+#[napi(string_enum)]
+pub enum Algorithm {
+  /// ML-KEM-512 (FIPS 203)
+  #[napi(value = "ml-kem-512")]
+  MlKem512,
+  /// ML-KEM-768 (FIPS 203)
+  #[napi(value = "ml-kem-768")]
+  MlKem768,
+  /// ML-DSA-65 (FIPS 204)
+  #[napi(value = "ml-dsa-65")]
+  MlDsa65,
+  /// BLAKE3 hash
+  #[napi(value = "blake3")]
+  Blake3,
+  /// SHA3-256 hash (FIPS 202)
+  #[napi(value = "sha3-256")]
+  Sha3_256,
+}
+
+impl Algorithm {
+  /// Every supported algorithm, in declaration order
+  pub const ALL: [Algorithm; 5] = [
+    Algorithm::MlKem512,
+    Algorithm::MlKem768,
+    Algorithm::MlDsa65,
+    Algorithm::Blake3,
+    Algorithm::Sha3_256,
+  ];
+
+  /// The string value used for this algorithm at the JS boundary
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Algorithm::MlKem512 => "ml-kem-512",
+      Algorithm::MlKem768 => "ml-kem-768",
+      Algorithm::MlDsa65 => "ml-dsa-65",
+      Algorithm::Blake3 => "blake3",
+      Algorithm::Sha3_256 => "sha3-256",
+    }
+  }
+}
+
+impl std::str::FromStr for Algorithm {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    Algorithm::ALL
+      .into_iter()
+      .find(|alg| alg.as_str() == s)
+      .ok_or_else(|| Error::from_reason(format!("Unknown algorithm: {}", s)))
+  }
+}
+
+type MlKemEncapsulationKey = <MlKem768 as KemCore>::EncapsulationKey;
+type MlKemDecapsulationKey = <MlKem768 as KemCore>::DecapsulationKey;
+
+/// Parse an ML-KEM-768 public key, validating its length
+fn parse_mlkem_public_key(public_key: &[u8]) -> Result<MlKemEncapsulationKey> {
+  if public_key.len() != 1184 {
+    return Err(Error::from_reason(format!(
+      "Invalid public key length: expected 1184 bytes, got {}",
+      public_key.len()
+    )));
+  }
+
+  let ek_array: [u8; 1184] = public_key
+    .try_into()
+    .map_err(|_| Error::from_reason("Failed to convert public key"))?;
+
+  Ok(MlKemEncapsulationKey::from_bytes((&ek_array).into()))
+}
+
+/// Parse an ML-KEM-768 secret key, validating its length
+fn parse_mlkem_secret_key(secret_key: &[u8]) -> Result<MlKemDecapsulationKey> {
+  if secret_key.len() != 2400 {
+    return Err(Error::from_reason(format!(
+      "Invalid secret key length: expected 2400 bytes, got {}",
+      secret_key.len()
+    )));
+  }
+
+  let dk_array: [u8; 2400] = secret_key
+    .try_into()
+    .map_err(|_| Error::from_reason("Failed to convert secret key"))?;
+
+  Ok(MlKemDecapsulationKey::from_bytes((&dk_array).into()))
+}
+
+/// Recover the ML-KEM-768 public key embedded in a secret key
+pub(crate) fn mlkem768_public_from_secret(secret_key: &[u8]) -> Result<Vec<u8>> {
+  Ok(parse_mlkem_secret_key(secret_key)?.encapsulation_key().as_bytes().to_vec())
+}
+
 // ============================================================================
 // ML-KEM-768 (NIST FIPS 203) - Quantum-Resistant Key Encapsulation
 // ============================================================================
@@ -44,7 +250,7 @@ pub struct EncapsulatedSecret {
 /// ```
 #[napi]
 pub fn mlkem768_generate_keypair() -> Result<KeyPair> {
-  let mut rng = OsRng;
+  let mut rng = module_rng();
 
   // Generate keypair - returns (DecapsulationKey, EncapsulationKey)
   let (dk, ek) = MlKem768::generate(&mut rng);
@@ -62,268 +268,2386 @@ pub fn mlkem768_generate_keypair() -> Result<KeyPair> {
   })
 }
 
-/// Encapsulate a shared secret using a public key
+/// Generate a new ML-KEM-768 keypair as non-pooled `Uint8Array`s
 ///
-/// # Arguments
-/// * `public_key` - ML-KEM-768 public key (1184 bytes)
+/// Same keys as `mlkem768_generate_keypair`, but each array owns a freshly
+/// allocated, externally managed `ArrayBuffer`. Prefer this when keys are
+/// kept around, since small pooled `Buffer`s share memory with unrelated
+/// allocations.
 ///
 /// # Returns
-/// EncapsulatedSecret containing:
-/// - Ciphertext: 1088 bytes
-/// - Shared secret: 32 bytes
+/// OwnedKeyPair with a 1184-byte public key and a 2400-byte secret key
 #[napi]
-pub fn mlkem768_encapsulate(public_key: Buffer) -> Result<EncapsulatedSecret> {
-  if public_key.len() != 1184 {
-    return Err(Error::from_reason(format!(
-      "Invalid public key length: expected 1184 bytes, got {}",
-      public_key.len()
-    )));
-  }
+pub fn mlkem768_generate_keypair_owned() -> Result<OwnedKeyPair> {
+  let (dk, ek) = MlKem768::generate(&mut module_rng());
 
-  let mut rng = OsRng;
+  Ok(OwnedKeyPair {
+    public_key: Uint8Array::new(ek.as_bytes().to_vec()),
+    secret_key: Uint8Array::new(dk.as_bytes().to_vec()),
+  })
+}
 
-  // Convert to fixed-size array
-  let ek_array: [u8; 1184] = public_key.as_ref()
-    .try_into()
-    .map_err(|_| Error::from_reason("Failed to convert public key"))?;
+/// Generate an ML-KEM-768 keypair and return the 64-byte seed it came from
+///
+/// FIPS 203 allows storing a secret key either expanded (2400 bytes) or as
+/// the 64-byte `d || z` seed. The seed cannot be recovered from an expanded
+/// key (`d` only enters key generation through a hash), so a compact
+/// backup is only possible when the seed is kept at generation time, which
+/// is what this function is for.
+///
+/// # Returns
+/// SeededKeyPair with the public key, expanded secret key and seed
+#[napi]
+pub fn mlkem768_generate_keypair_with_seed() -> Result<SeededKeyPair> {
+  let mut seed = [0u8; 64];
+  module_rng().fill_bytes(&mut seed);
+  let (dk, ek) = mlkem768_keypair_from_seed(&seed);
 
-  // Create encapsulation key from bytes
-  type EncapKey = <MlKem768 as KemCore>::EncapsulationKey;
-  let ek = EncapKey::from_bytes((&ek_array).into());
+  let keypair = SeededKeyPair {
+    public_key: ek.as_bytes().to_vec().into(),
+    secret_key: dk.as_bytes().to_vec().into(),
+    seed: seed.to_vec().into(),
+  };
+  seed.zeroize();
+  Ok(keypair)
+}
 
-  // Encapsulate to generate shared secret and ciphertext
-  let (ct, ss) = ek.encapsulate(&mut rng)
-    .map_err(|_| Error::from_reason("Encapsulation failed"))?;
+/// Regenerate the expanded ML-KEM-768 secret key from its 64-byte seed
+///
+/// # Arguments
+/// * `seed` - Seed `d || z` (64 bytes) from `mlkem768_generate_keypair_with_seed`
+///
+/// # Returns
+/// Expanded 2400-byte secret key, identical to the one generated from the seed
+#[napi]
+pub fn mlkem768_expand_secret_key(seed: Buffer) -> Result<Buffer> {
+  let seed: &[u8; 64] = seed.as_ref()
+    .try_into()
+    .map_err(|_| Error::from_reason(format!(
+      "Invalid seed length: expected 64 bytes, got {}",
+      seed.len()
+    )))?;
 
-  Ok(EncapsulatedSecret {
-    ciphertext: (&*ct).to_vec().into(),
-    shared_secret: (&*ss).to_vec().into(),
-  })
+  let (dk, _) = mlkem768_keypair_from_seed(seed);
+  Ok(dk.as_bytes().to_vec().into())
 }
 
-/// Decapsulate a shared secret using a secret key
+/// Run FIPS 203 `ML-KEM.KeyGen_internal(d, z)` on a 64-byte `d || z` seed
+fn mlkem768_keypair_from_seed(seed: &[u8; 64]) -> (MlKemDecapsulationKey, MlKemEncapsulationKey) {
+  let d = B32::try_from(&seed[..32]).expect("32-byte d");
+  let z = B32::try_from(&seed[32..]).expect("32-byte z");
+  MlKem768::generate_deterministic(&d, &z)
+}
+
+/// Derive a deterministic ML-KEM-768 keypair for a context from a master seed
+///
+/// Expands `master_seed` with BLAKE3 in `derive_key` mode using `context`
+/// as the context string, reading 64 bytes of output as the FIPS 203 `d || z`
+/// key generation seed. The same master seed and context always give the
+/// same keypair; different contexts give independent keypairs, so one
+/// backed-up master seed can recover a whole hierarchy of identities.
 ///
 /// # Arguments
-/// * `ciphertext` - Encapsulated ciphertext (1088 bytes)
-/// * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+/// * `master_seed` - Secret master seed (at least 32 bytes)
+/// * `context` - Hardcoded, globally unique context string for this keypair
 ///
 /// # Returns
-/// Shared secret (32 bytes)
+/// Derived ML-KEM-768 KeyPair
 #[napi]
-pub fn mlkem768_decapsulate(ciphertext: Buffer, secret_key: Buffer) -> Result<Buffer> {
-  if ciphertext.len() != 1088 {
-    return Err(Error::from_reason(format!(
-      "Invalid ciphertext length: expected 1088 bytes, got {}",
-      ciphertext.len()
-    )));
-  }
-
-  if secret_key.len() != 2400 {
+pub fn mlkem768_derive_keypair(master_seed: Buffer, context: String) -> Result<KeyPair> {
+  if master_seed.len() < 32 {
     return Err(Error::from_reason(format!(
-      "Invalid secret key length: expected 2400 bytes, got {}",
-      secret_key.len()
+      "Master seed too short: expected at least 32 bytes, got {}",
+      master_seed.len()
     )));
   }
 
-  // Convert to fixed-size arrays
-  let dk_array: [u8; 2400] = secret_key.as_ref()
-    .try_into()
-    .map_err(|_| Error::from_reason("Failed to convert secret key"))?;
-  let ct_array: [u8; 1088] = ciphertext.as_ref()
-    .try_into()
-    .map_err(|_| Error::from_reason("Failed to convert ciphertext"))?;
-
-  // Create keys from bytes
-  type DecapKey = <MlKem768 as KemCore>::DecapsulationKey;
-  let dk = DecapKey::from_bytes((&dk_array).into());
-  let ct = ct_array.into();
+  let mut seed = [0u8; 64];
+  blake3::Hasher::new_derive_key(&context)
+    .update(master_seed.as_ref())
+    .finalize_xof()
+    .fill(&mut seed);
 
-  // Decapsulate to recover shared secret
-  let ss = dk.decapsulate(&ct)
-    .map_err(|_| Error::from_reason("Decapsulation failed"))?;
+  let (dk, ek) = mlkem768_keypair_from_seed(&seed);
+  seed.zeroize();
 
-  Ok((&*ss).to_vec().into())
+  Ok(KeyPair {
+    public_key: ek.as_bytes().to_vec().into(),
+    secret_key: dk.as_bytes().to_vec().into(),
+  })
 }
 
-// ============================================================================
-// ML-DSA-65 (NIST FIPS 204) - Quantum-Resistant Digital Signatures
-// ============================================================================
+/// Seed of `example_keypair`: the bytes `0x00..=0x3f` as `d || z`
+const EXAMPLE_SEED: [u8; 64] = {
+  let mut seed = [0u8; 64];
+  let mut i = 0;
+  while i < 64 {
+    seed[i] = i as u8;
+    i += 1;
+  }
+  seed
+};
 
-/// Generate a new ML-DSA-65 keypair
+/// Fixed ML-KEM-768 keypair for documentation and examples
 ///
-/// Returns a KeyPair with:
-/// - Public key: 1952 bytes
-/// - Secret key: 4032 bytes
+/// **INSECURE: never use this keypair for real data.** It is generated from
+/// a published seed (`d || z` = the bytes `0x00..=0x3f`), so anyone can
+/// recompute the secret key. It exists so examples and doctests can assert
+/// concrete outputs; its public key fingerprint is
+/// `qf:cc4b207d7b69fc89154fcfc6f0b96f989939873391969bc1fe2eea03e6f389aa`.
+/// There is no ML-DSA-65 counterpart: signing keys come from
+/// `pqcrypto-dilithium`, which has no seeded key generation.
 ///
-/// # Example
-/// ```javascript
-/// const { mldsa65GenerateKeypair } = require('@daa/qudag-native');
-/// const { publicKey, secretKey } = mldsa65GenerateKeypair();
-/// ```
+/// # Returns
+/// The same ML-KEM-768 KeyPair on every call
 #[napi]
-pub fn mldsa65_generate_keypair() -> Result<KeyPair> {
-  // Generate ML-DSA-65 keypair using pqcrypto-dilithium
-  let (pk, sk) = keypair();
-
-  Ok(KeyPair {
-    public_key: pk.as_bytes().to_vec().into(),
-    secret_key: sk.as_bytes().to_vec().into(),
-  })
+pub fn example_keypair() -> KeyPair {
+  let (dk, ek) = mlkem768_keypair_from_seed(&EXAMPLE_SEED);
+  KeyPair {
+    public_key: ek.as_bytes().to_vec().into(),
+    secret_key: dk.as_bytes().to_vec().into(),
+  }
 }
 
-/// Sign a message with ML-DSA-65
+/// Encapsulate a shared secret using a public key
 ///
 /// # Arguments
-/// * `message` - Message to sign
-/// * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+/// * `public_key` - ML-KEM-768 public key (1184 bytes)
 ///
 /// # Returns
-/// Signature (3309 bytes)
+/// EncapsulatedSecret containing:
+/// - Ciphertext: 1088 bytes
+/// - Shared secret: 32 bytes
 #[napi]
-pub fn mldsa65_sign(message: Buffer, secret_key: Buffer) -> Result<Buffer> {
-  if secret_key.len() != 4032 {
-    return Err(Error::from_reason(format!(
-      "Invalid secret key length: expected 4032 bytes, got {}",
-      secret_key.len()
-    )));
-  }
+pub fn mlkem768_encapsulate(public_key: Buffer) -> Result<EncapsulatedSecret> {
+  let ek = parse_mlkem_public_key(public_key.as_ref())?;
 
-  // Parse secret key from bytes
-  let sk = SecretKey::from_bytes(secret_key.as_ref())
-    .map_err(|_| Error::from_reason("Invalid secret key format"))?;
+  let mut rng = module_rng();
 
-  // Sign the message
-  let signature = detached_sign(message.as_ref(), &sk);
+  // Encapsulate to generate shared secret and ciphertext
+  let (ct, ss) = ek.encapsulate(&mut rng)
+    .map_err(|_| Error::from_reason("Encapsulation failed"))?;
 
-  Ok(signature.as_bytes().to_vec().into())
+  Ok(EncapsulatedSecret {
+    ciphertext: ct.to_vec().into(),
+    shared_secret: ss.to_vec().into(),
+  })
 }
 
-/// Verify a signature with ML-DSA-65
+/// Largest thread pool `mlkem768_encapsulate_batch_parallel` will build
+const MAX_BATCH_THREADS: u32 = 256;
+
+/// Encapsulate to many public keys in parallel
+///
+/// Runs on a dedicated rayon thread pool so a large batch doesn't compete
+/// with the global pool. Results are returned in input order.
 ///
 /// # Arguments
-/// * `message` - Original message
-/// * `signature` - Signature to verify (3309 bytes)
-/// * `public_key` - ML-DSA-65 public key (1952 bytes)
+/// * `public_keys` - ML-KEM-768 public keys (1184 bytes each)
+/// * `threads` - Thread pool size, at most 256; 0 uses rayon's default (one
+///   per CPU)
 ///
 /// # Returns
-/// true if signature is valid, false otherwise
+/// One EncapsulatedSecret per public key; errors name the first bad key's index
 #[napi]
-pub fn mldsa65_verify(message: Buffer, signature: Buffer, public_key: Buffer) -> Result<bool> {
-  if public_key.len() != 1952 {
+pub fn mlkem768_encapsulate_batch_parallel(public_keys: Vec<Buffer>, threads: u32) -> Result<Vec<EncapsulatedSecret>> {
+  if threads > MAX_BATCH_THREADS {
     return Err(Error::from_reason(format!(
-      "Invalid public key length: expected 1952 bytes, got {}",
-      public_key.len()
+      "Too many threads: at most {}, got {}",
+      MAX_BATCH_THREADS, threads
     )));
   }
+  let keys: Vec<&[u8]> = public_keys.iter().map(|k| k.as_ref()).collect();
+  let pool = rayon::ThreadPoolBuilder::new()
+    .num_threads(threads as usize)
+    .build()
+    .map_err(|e| Error::from_reason(format!("Failed to build thread pool: {}", e)))?;
 
-  if signature.len() != 3309 {
-    return Err(Error::from_reason(format!(
-      "Invalid signature length: expected 3309 bytes, got {}",
-      signature.len()
-    )));
-  }
+  // Fork one RNG per key up front so seeded test runs stay deterministic
+  let jobs: Vec<_> = keys.into_iter().map(|key| (key, module_rng())).collect();
 
-  // Parse public key and signature from bytes
-  let pk = PublicKey::from_bytes(public_key.as_ref())
-    .map_err(|_| Error::from_reason("Invalid public key format"))?;
-  let sig = DetachedSignature::from_bytes(signature.as_ref())
-    .map_err(|_| Error::from_reason("Invalid signature format"))?;
+  let results: Vec<(Vec<u8>, Vec<u8>)> = pool.install(|| {
+    jobs
+      .into_par_iter()
+      .enumerate()
+      .map(|(i, (key, mut rng))| {
+        let ek = parse_mlkem_public_key(key)
+          .map_err(|e| Error::from_reason(format!("Public key {}: {}", i, e.reason)))?;
+        let (ct, ss) = ek.encapsulate(&mut rng)
+          .map_err(|_| Error::from_reason(format!("Public key {}: Encapsulation failed", i)))?;
+        Ok((ct.to_vec(), ss.to_vec()))
+      })
+      .collect::<Result<_>>()
+  })?;
 
-  // Verify the signature
-  Ok(verify_detached_signature(&sig, message.as_ref(), &pk).is_ok())
+  Ok(results
+    .into_iter()
+    .map(|(ct, ss)| EncapsulatedSecret {
+      ciphertext: ct.into(),
+      shared_secret: ss.into(),
+    })
+    .collect())
 }
 
-// ============================================================================
-// BLAKE3 - Cryptographic Hashing
-// ============================================================================
-
-/// Compute BLAKE3 hash of data
+/// Encapsulate a shared secret and return the randomness that was used
+///
+/// Intended for regulated environments that must log encapsulation
+/// randomness. Passing the returned coins to
+/// `mlkem768_encapsulate_deterministic` reproduces the same ciphertext and
+/// shared secret, so the coins must be protected like the shared secret.
 ///
 /// # Arguments
-/// * `data` - Data to hash
+/// * `public_key` - ML-KEM-768 public key (1184 bytes)
 ///
 /// # Returns
-/// 32-byte hash
+/// CoinsEncapsulatedSecret containing:
+/// - Ciphertext: 1088 bytes
+/// - Shared secret: 32 bytes
+/// - Coins: 32 bytes
 #[napi]
-pub fn blake3_hash(data: Buffer) -> Result<Buffer> {
-  let hash = blake3::hash(data.as_ref());
-  Ok(hash.as_bytes().to_vec().into())
+pub fn mlkem768_encapsulate_with_coins(public_key: Buffer) -> Result<CoinsEncapsulatedSecret> {
+  let ek = parse_mlkem_public_key(public_key.as_ref())?;
+
+  let mut coins = [0u8; 32];
+  module_rng().fill_bytes(&mut coins);
+
+  let (ct, ss) = ek.encapsulate_deterministic(&B32::from(coins))
+    .map_err(|_| Error::from_reason("Encapsulation failed"))?;
+
+  Ok(CoinsEncapsulatedSecret {
+    ciphertext: ct.to_vec().into(),
+    shared_secret: ss.to_vec().into(),
+    coins: coins.to_vec().into(),
+  })
 }
 
-/// Compute BLAKE3 hash as hex string
+/// Encapsulate a shared secret using caller-supplied randomness
+///
+/// The same public key and coins always produce the same ciphertext and
+/// shared secret. Only use this to replay an audited encapsulation or for
+/// known-answer tests; fresh encapsulations must use `mlkem768_encapsulate`.
 ///
 /// # Arguments
-/// * `data` - Data to hash
+/// * `public_key` - ML-KEM-768 public key (1184 bytes)
+/// * `coins` - Encapsulation randomness (32 bytes)
 ///
 /// # Returns
-/// 64-character hex string
+/// EncapsulatedSecret containing:
+/// - Ciphertext: 1088 bytes
+/// - Shared secret: 32 bytes
 #[napi]
-pub fn blake3_hash_hex(data: Buffer) -> Result<String> {
-  let hash = blake3::hash(data.as_ref());
-  Ok(hash.to_hex().to_string())
+pub fn mlkem768_encapsulate_deterministic(public_key: Buffer, coins: Buffer) -> Result<EncapsulatedSecret> {
+  let ek = parse_mlkem_public_key(public_key.as_ref())?;
+
+  let coins: [u8; 32] = coins.as_ref()
+    .try_into()
+    .map_err(|_| Error::from_reason(format!(
+      "Invalid coins length: expected 32 bytes, got {}",
+      coins.len()
+    )))?;
+
+  let (ct, ss) = ek.encapsulate_deterministic(&B32::from(coins))
+    .map_err(|_| Error::from_reason("Encapsulation failed"))?;
+
+  Ok(EncapsulatedSecret {
+    ciphertext: ct.to_vec().into(),
+    shared_secret: ss.to_vec().into(),
+  })
 }
 
-/// Generate quantum-resistant fingerprint
+/// Decapsulate a shared secret using a secret key
 ///
 /// # Arguments
-/// * `data` - Data to fingerprint
+/// * `ciphertext` - Encapsulated ciphertext (1088 bytes)
+/// * `secret_key` - ML-KEM-768 secret key (2400 bytes)
 ///
 /// # Returns
-/// Fingerprint string in format "qf:{hash}"
+/// Shared secret (32 bytes)
+#[napi]
+pub fn mlkem768_decapsulate(ciphertext: Buffer, secret_key: Buffer) -> Result<Buffer> {
+  decapsulate_bytes(ciphertext.as_ref(), secret_key.as_ref())
+}
+
+/// Decapsulate a ciphertext embedded in a larger buffer
+///
+/// Reads the 1088-byte ciphertext at `buffer[offset..offset + 1088]`
+/// directly, so framed messages need no `subarray()` on the JS side.
+///
+/// # Arguments
+/// * `buffer` - Buffer containing the ciphertext
+/// * `offset` - Byte offset of the ciphertext within `buffer`
+/// * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+///
+/// # Returns
+/// Shared secret (32 bytes)
+#[napi]
+pub fn mlkem768_decapsulate_at(buffer: Buffer, offset: u32, secret_key: Buffer) -> Result<Buffer> {
+  let start = offset as usize;
+  let ciphertext = start
+    .checked_add(1088)
+    .and_then(|end| buffer.get(start..end))
+    .ok_or_else(|| Error::from_reason(format!(
+      "Ciphertext at offset {} out of range for buffer of {} bytes",
+      offset,
+      buffer.len()
+    )))?;
+
+  decapsulate_bytes(ciphertext, secret_key.as_ref())
+}
+
+fn decapsulate_bytes(ciphertext: &[u8], secret_key: &[u8]) -> Result<Buffer> {
+  if ciphertext.len() != 1088 {
+    return Err(Error::from_reason(format!(
+      "Invalid ciphertext length: expected 1088 bytes, got {}",
+      ciphertext.len()
+    )));
+  }
+
+  let dk = parse_mlkem_secret_key(secret_key)?;
+
+  // Convert to fixed-size array
+  let ct_array: [u8; 1088] = ciphertext
+    .try_into()
+    .map_err(|_| Error::from_reason("Failed to convert ciphertext"))?;
+  let ct = ct_array.into();
+
+  // Decapsulate to recover shared secret
+  let ss = dk.decapsulate(&ct)
+    .map_err(|_| Error::from_reason("Decapsulation failed"))?;
+
+  Ok(ss.to_vec().into())
+}
+
+/// Check whether two ciphertexts encapsulate the same shared secret
+///
+/// Decapsulates both ciphertexts and compares the secrets in constant time;
+/// neither secret leaves native code. Useful for detecting replayed
+/// encapsulations in a store. Because ML-KEM uses implicit rejection, a
+/// malformed ciphertext still decapsulates (to a pseudorandom secret) and
+/// simply compares unequal.
+///
+/// # Arguments
+/// * `ct_a` - First ML-KEM-768 ciphertext (1088 bytes)
+/// * `ct_b` - Second ML-KEM-768 ciphertext (1088 bytes)
+/// * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+///
+/// # Returns
+/// true if both ciphertexts yield the same shared secret
+#[napi]
+pub fn ciphertexts_share_secret(ct_a: Buffer, ct_b: Buffer, secret_key: Buffer) -> Result<bool> {
+  let ss_a = mlkem768_decapsulate(ct_a, secret_key.clone())?;
+  let ss_b = mlkem768_decapsulate(ct_b, secret_key)?;
+  crate::utils::constant_time_compare(ss_a, ss_b)
+}
+
+/// Check whether a ciphertext was encapsulated to this secret key's public key
+///
+/// ML-KEM decapsulation re-encrypts the recovered message and, if the result
+/// differs from the ciphertext, returns the implicit-rejection secret
+/// `K̄ = SHAKE256(z || ciphertext)` instead of failing. This computes K̄ from
+/// the secret key's `z` and reports whether decapsulation took the
+/// reject path.
+///
+/// Limitations: it needs the recipient's secret key, so it can't be used by
+/// third parties; it says nothing about who made the ciphertext or whether
+/// it is a replay; and a ciphertext for another key only shows up as "not
+/// for this key", without identifying the intended recipient. The chance of
+/// a valid ciphertext's secret colliding with K̄ is negligible (2^-256).
+///
+/// # Arguments
+/// * `ciphertext` - ML-KEM-768 ciphertext (1088 bytes)
+/// * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+///
+/// # Returns
+/// true if the ciphertext decapsulates normally under this key
+#[napi]
+pub fn ciphertext_matches_key(ciphertext: Buffer, secret_key: Buffer) -> Result<bool> {
+  let shared_secret = mlkem768_decapsulate(ciphertext.clone(), secret_key.clone())?;
+
+  // z is the last 32 bytes of the secret key (dk_pke || ek || H(ek) || z)
+  let z = &secret_key[2400 - 32..];
+  let mut rejection_secret = [0u8; 32];
+  Shake256::digest_xof([z, ciphertext.as_ref()].concat(), &mut rejection_secret);
+
+  let rejected = crate::utils::constant_time_compare(shared_secret, rejection_secret.to_vec().into())?;
+  rejection_secret.zeroize();
+  Ok(!rejected)
+}
+
+/// Bind a raw ML-KEM shared secret to a channel identifier
+///
+/// The BLAKE3 `derive_key` context is `"channel:"` followed by the lowercase
+/// hex of `channel_id`, so binary channel ids (e.g. TLS exporter values)
+/// are accepted as-is.
+fn bind_to_channel(shared_secret: &[u8], channel_id: &[u8]) -> Buffer {
+  let context = format!("channel:{}", hex::encode(channel_id));
+  blake3::derive_key(&context, shared_secret).to_vec().into()
+}
+
+/// Encapsulate a shared secret bound to a channel identifier
+///
+/// The returned shared secret is derived from the raw ML-KEM secret and
+/// `channel_id`, so a ciphertext replayed on a different channel yields an
+/// unrelated secret. The peer must use `mlkem768_decapsulate_bound` with the
+/// same channel id.
+///
+/// # Arguments
+/// * `public_key` - ML-KEM-768 public key (1184 bytes)
+/// * `channel_id` - Channel identifier (any length)
+///
+/// # Returns
+/// EncapsulatedSecret with the channel-bound 32-byte shared secret
+#[napi]
+pub fn mlkem768_encapsulate_bound(public_key: Buffer, channel_id: Buffer) -> Result<EncapsulatedSecret> {
+  let encapsulated = mlkem768_encapsulate(public_key)?;
+  let mut raw = encapsulated.shared_secret.to_vec();
+  let shared_secret = bind_to_channel(&raw, channel_id.as_ref());
+  raw.zeroize();
+
+  Ok(EncapsulatedSecret {
+    ciphertext: encapsulated.ciphertext,
+    shared_secret,
+  })
+}
+
+/// Decapsulate a shared secret produced by `mlkem768_encapsulate_bound`
+///
+/// # Arguments
+/// * `ciphertext` - ML-KEM-768 ciphertext (1088 bytes)
+/// * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+/// * `channel_id` - Channel identifier used by the encapsulating side
+///
+/// # Returns
+/// Channel-bound shared secret (32 bytes)
+#[napi]
+pub fn mlkem768_decapsulate_bound(ciphertext: Buffer, secret_key: Buffer, channel_id: Buffer) -> Result<Buffer> {
+  let mut raw = mlkem768_decapsulate(ciphertext, secret_key)?.to_vec();
+  let shared_secret = bind_to_channel(&raw, channel_id.as_ref());
+  raw.zeroize();
+
+  Ok(shared_secret)
+}
+
+/// Check that an ML-KEM-768 keypair is internally consistent
+///
+/// Derives the public key embedded in the secret key and compares it with
+/// the stored public key, then runs a test encapsulation/decapsulation to
+/// confirm the pair works. Use this before trusting an imported keypair.
+///
+/// # Arguments
+/// * `kp` - ML-KEM-768 keypair
+///
+/// # Returns
+/// true if the keys form a working pair, false on any mismatch
+#[napi]
+pub fn mlkem768_validate_keypair(kp: KeyPair) -> Result<bool> {
+  let (Ok(ek), Ok(dk)) = (
+    parse_mlkem_public_key(kp.public_key.as_ref()),
+    parse_mlkem_secret_key(kp.secret_key.as_ref()),
+  ) else {
+    return Ok(false);
+  };
+
+  if dk.encapsulation_key().as_bytes().as_slice() != kp.public_key.as_ref() {
+    return Ok(false);
+  }
+
+  let mut rng = module_rng();
+  let Ok((ct, ss)) = ek.encapsulate(&mut rng) else {
+    return Ok(false);
+  };
+  let Ok(recovered) = dk.decapsulate(&ct) else {
+    return Ok(false);
+  };
+
+  Ok(ss == recovered)
+}
+
+/// Byte layouts accepted by `mlkem768_public_key_reencode`
+const MLKEM_PUBLIC_KEY_LAYOUTS: [&str; 2] = ["fips203", "raw"];
+
+/// Convert an ML-KEM-768 public key between serialization layouts
+///
+/// Supported layouts are "fips203" (the FIPS 203 `ByteEncode12(t) || rho`
+/// encoding) and "raw" (the bytes produced by the `ml-kem` crate). The
+/// `ml-kem` crate serializes keys in exactly the FIPS 203 layout, so every
+/// supported conversion is an identity on the bytes. The key is still
+/// validated: it must be 1184 bytes and pass the FIPS 203 modulus check
+/// (decoding and re-encoding must reproduce the input).
+///
+/// # Arguments
+/// * `key` - ML-KEM-768 public key in the `from` layout
+/// * `from` - Source layout ("fips203" or "raw")
+/// * `to` - Target layout ("fips203" or "raw")
+///
+/// # Returns
+/// Public key in the `to` layout
+#[napi]
+pub fn mlkem768_public_key_reencode(key: Buffer, from: String, to: String) -> Result<Buffer> {
+  for layout in [&from, &to] {
+    if !MLKEM_PUBLIC_KEY_LAYOUTS.contains(&layout.as_str()) {
+      return Err(Error::from_reason(format!(
+        "Unsupported public key layout: {} (expected one of {})",
+        layout,
+        MLKEM_PUBLIC_KEY_LAYOUTS.join(", ")
+      )));
+    }
+  }
+
+  let ek = parse_mlkem_public_key(key.as_ref())?;
+  let encoded = ek.as_bytes();
+  if encoded.as_slice() != key.as_ref() {
+    return Err(Error::from_reason(
+      "Invalid public key: coefficients are not reduced modulo q",
+    ));
+  }
+
+  Ok(encoded.to_vec().into())
+}
+
+/// Describe a secret key without exposing any of its bytes
+///
+/// For logging and telemetry. The returned metadata never contains secret
+/// material: only the algorithm, the length, and a fingerprint of the public
+/// key. ML-KEM-768 secret keys embed their public key, so its fingerprint is
+/// always available; an ML-DSA-65 secret key does not contain the public key
+/// (recovering it needs the full signing-key expansion, which
+/// `pqcrypto-dilithium` does not expose), so the fingerprint is omitted.
+///
+/// # Arguments
+/// * `secret_key` - ML-KEM-768 (2400 bytes) or ML-DSA-65 (4032 bytes) secret key
+///
+/// # Returns
+/// KeyMetadata for the key
+#[napi]
+pub fn secret_key_metadata(secret_key: Buffer) -> Result<KeyMetadata> {
+  let (algorithm, public_key_fingerprint) = match secret_key.len() {
+    2400 => {
+      let dk = parse_mlkem_secret_key(secret_key.as_ref())?;
+      let public_key = dk.encapsulation_key().as_bytes();
+      (
+        "ML-KEM-768",
+        Some(quantum_fingerprint(public_key.to_vec().into())?),
+      )
+    }
+    4032 => ("ML-DSA-65", None),
+    other => {
+      return Err(Error::from_reason(format!(
+        "Unrecognized secret key length: {} bytes",
+        other
+      )));
+    }
+  };
+
+  Ok(KeyMetadata {
+    algorithm: algorithm.to_string(),
+    length: secret_key.len() as u32,
+    public_key_fingerprint,
+  })
+}
+
+/// Check that a secret key belongs to a published public key fingerprint
+///
+/// For restoring backups: the public key is recovered from the ML-KEM-768
+/// secret key, fingerprinted, and compared with `expected_fp` as
+/// `fingerprint_equals_data` does (constant time, case-insensitive, `qf:`
+/// prefix optional), so SHAKE256 fingerprints shorter than 32 bytes never
+/// match. ML-DSA-65 secret keys do not embed their public key (see
+/// `secret_key_metadata`) and are rejected with an error.
+///
+/// # Arguments
+/// * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+/// * `expected_fp` - Published fingerprint of the public key
+///
+/// # Returns
+/// true if the secret key's public key has this fingerprint
+#[napi]
+pub fn verify_secret_matches_fingerprint(secret_key: Buffer, expected_fp: String) -> Result<bool> {
+  if secret_key.len() == 4032 {
+    return Err(Error::from_reason(
+      "Cannot recover the public key from an ML-DSA-65 secret key; compare the keypair instead",
+    ));
+  }
+
+  let public_key = mlkem768_public_from_secret(secret_key.as_ref())?;
+  Ok(fingerprint_equals_data(public_key.into(), expected_fp))
+}
+
+pub use ml_kem_key_pair_class::MlKemKeyPair;
+
+#[allow(missing_docs)]
+mod ml_kem_key_pair_class {
+  use super::*;
+
+  /// ML-KEM-768 keypair held natively for long-lived processes
+  ///
+  /// Keeps the secret key out of JS-managed memory: only the public key is
+  /// readable, and the secret key is used through `decapsulate`. Supports
+  /// in-place key rotation: `rotate()` zeroizes the old secret key and
+  /// replaces both keys while the object identity stays the same, so callers
+  /// holding a reference transparently get the new keys.
+  #[napi]
+  pub struct MlKemKeyPair {
+    pub(super) public_key: Vec<u8>,
+    pub(super) secret_key: Vec<u8>,
+  }
+}
+
+#[napi]
+impl MlKemKeyPair {
+  /// Generate a new ML-KEM-768 keypair
+  #[napi(constructor)]
+  pub fn new() -> Result<Self> {
+    let keypair = mlkem768_generate_keypair()?;
+    Ok(Self {
+      public_key: keypair.public_key.to_vec(),
+      secret_key: keypair.secret_key.to_vec(),
+    })
+  }
+
+  /// Current public key (1184 bytes)
+  #[napi(getter)]
+  pub fn public_key(&self) -> Buffer {
+    self.public_key.clone().into()
+  }
+
+  /// Decapsulate a shared secret with the current secret key
+  #[napi]
+  pub fn decapsulate(&self, ciphertext: Buffer) -> Result<Buffer> {
+    mlkem768_decapsulate(ciphertext, self.secret_key.clone().into())
+  }
+
+  /// Zeroize the current secret key and replace both keys with a fresh pair
+  #[napi]
+  pub fn rotate(&mut self) -> Result<()> {
+    let keypair = mlkem768_generate_keypair()?;
+    self.secret_key.zeroize();
+    self.public_key = keypair.public_key.to_vec();
+    self.secret_key = keypair.secret_key.to_vec();
+    Ok(())
+  }
+}
+
+impl Drop for MlKemKeyPair {
+  fn drop(&mut self) {
+    self.secret_key.zeroize();
+  }
+}
+
+/// Keypairs generated by a `KeyPairGenerator` between reseeds, by default
+const DEFAULT_RESEED_INTERVAL: u32 = 1024;
+
+pub use key_pair_generator_class::KeyPairGenerator;
+
+#[allow(missing_docs)]
+mod key_pair_generator_class {
+  use super::*;
+
+  /// High-throughput ML-KEM-768 keypair generator
+  ///
+  /// Holds a ChaCha20 CSPRNG seeded once from the OS RNG and generates
+  /// keypairs from it, avoiding per-call RNG setup. After `reseed_interval`
+  /// keypairs (1024 by default) the generator replaces its state with a fresh
+  /// OS seed, so a captured generator state exposes at most the keypairs of
+  /// the current interval.
+  #[napi]
+  pub struct KeyPairGenerator {
+    pub(super) rng: ChaCha20Rng,
+    pub(super) reseed_interval: u32,
+    pub(super) since_reseed: u32,
+    pub(super) reseeds: u32,
+  }
+}
+
+#[napi]
+impl KeyPairGenerator {
+  /// Create a generator, reseeding every `reseed_interval` keypairs (default 1024)
+  #[napi(constructor)]
+  pub fn new(reseed_interval: Option<u32>) -> Result<Self> {
+    let reseed_interval = reseed_interval.unwrap_or(DEFAULT_RESEED_INTERVAL);
+    if reseed_interval == 0 {
+      return Err(Error::from_reason("Reseed interval must be at least 1 keypair"));
+    }
+
+    Ok(Self {
+      rng: Self::fresh_rng()?,
+      reseed_interval,
+      since_reseed: 0,
+      reseeds: 0,
+    })
+  }
+
+  /// Generate an ML-KEM-768 keypair (1184-byte public, 2400-byte secret key)
+  #[napi]
+  pub fn generate(&mut self) -> Result<KeyPair> {
+    if self.since_reseed == self.reseed_interval {
+      self.rng = Self::fresh_rng()?;
+      self.since_reseed = 0;
+      self.reseeds += 1;
+    }
+
+    let (dk, ek) = MlKem768::generate(&mut self.rng);
+    self.since_reseed += 1;
+
+    Ok(KeyPair {
+      public_key: ek.as_bytes().to_vec().into(),
+      secret_key: dk.as_bytes().to_vec().into(),
+    })
+  }
+
+  /// Number of times the generator has reseeded since construction
+  #[napi(getter)]
+  pub fn reseeds(&self) -> u32 {
+    self.reseeds
+  }
+
+  fn fresh_rng() -> Result<ChaCha20Rng> {
+    ChaCha20Rng::from_rng(module_rng())
+      .map_err(|e| Error::from_reason(format!("Failed to seed generator: {}", e)))
+  }
+}
+
+pub use encapsulated_secret_builder_class::EncapsulatedSecretBuilder;
+
+#[allow(missing_docs)]
+mod encapsulated_secret_builder_class {
+  use super::*;
+
+  /// Assembles an ML-KEM-768 ciphertext received in fragments
+  ///
+  /// Fragments are appended in order with `push`; `build` returns the
+  /// assembled ciphertext once exactly 1088 bytes have been received.
+  #[napi]
+  pub struct EncapsulatedSecretBuilder {
+    pub(super) ciphertext: Vec<u8>,
+  }
+}
+
+#[napi]
+impl EncapsulatedSecretBuilder {
+  /// Create an empty builder
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Self {
+      ciphertext: Vec::with_capacity(1088),
+    }
+  }
+
+  /// Append the next ciphertext fragment
+  ///
+  /// Errors without appending if the fragment would take the total past
+  /// 1088 bytes.
+  #[napi]
+  pub fn push(&mut self, fragment: Buffer) -> Result<()> {
+    let total = self.ciphertext.len() + fragment.len();
+    if total > 1088 {
+      return Err(Error::from_reason(format!(
+        "Invalid ciphertext length: expected 1088 bytes, got at least {}",
+        total
+      )));
+    }
+
+    self.ciphertext.extend_from_slice(fragment.as_ref());
+    Ok(())
+  }
+
+  /// Bytes received so far
+  #[napi(getter)]
+  pub fn length(&self) -> u32 {
+    self.ciphertext.len() as u32
+  }
+
+  /// Return the assembled 1088-byte ciphertext
+  #[napi]
+  pub fn build(&self) -> Result<Buffer> {
+    if self.ciphertext.len() != 1088 {
+      return Err(Error::from_reason(format!(
+        "Invalid ciphertext length: expected 1088 bytes, got {}",
+        self.ciphertext.len()
+      )));
+    }
+
+    Ok(self.ciphertext.clone().into())
+  }
+}
+
+impl Default for EncapsulatedSecretBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// ============================================================================
+// ML-DSA-65 (NIST FIPS 204) - Quantum-Resistant Digital Signatures
+// ============================================================================
+
+/// Generate a new ML-DSA-65 keypair
+///
+/// Returns a KeyPair with:
+/// - Public key: 1952 bytes
+/// - Secret key: 4032 bytes
+///
+/// # Example
+/// ```javascript
+/// const { mldsa65GenerateKeypair } = require('@daa/qudag-native');
+/// const { publicKey, secretKey } = mldsa65GenerateKeypair();
+/// ```
+#[napi]
+pub fn mldsa65_generate_keypair() -> Result<KeyPair> {
+  // Generate ML-DSA-65 keypair using pqcrypto-dilithium
+  let (pk, sk) = keypair();
+
+  Ok(KeyPair {
+    public_key: pk.as_bytes().to_vec().into(),
+    secret_key: sk.as_bytes().to_vec().into(),
+  })
+}
+
+/// Default for `max_direct_sign_bytes`: 1 MiB
+const DEFAULT_MAX_DIRECT_SIGN_BYTES: u32 = 1024 * 1024;
+
+static MAX_DIRECT_SIGN_BYTES: AtomicU32 = AtomicU32::new(DEFAULT_MAX_DIRECT_SIGN_BYTES);
+
+/// Largest message `mldsa65_sign` accepts, in bytes
+///
+/// # Returns
+/// Current limit; 1 MiB unless changed with `set_max_direct_sign_bytes`
+#[napi]
+pub fn max_direct_sign_bytes() -> u32 {
+  MAX_DIRECT_SIGN_BYTES.load(Ordering::Relaxed)
+}
+
+/// Change the largest message `mldsa65_sign` accepts
+///
+/// **This is a single process-wide setting, not a per-call option.** Every
+/// caller in the process shares it, including other libraries that load
+/// this module, and any of them can change it at any time; set it once at
+/// startup and don't rely on it as a security boundary. It also bounds the
+/// wrappers that sign the message directly: `mldsa65_sign_to_sig_format`
+/// and `mldsa65_sign_attached`.
+///
+/// Larger inputs should be signed in pre-hash mode with `StreamSigner`,
+/// `mldsa65_sign_stream` or `mldsa65_sign_file`, which never hold the whole
+/// message at once. Pass `u32::MAX` (4294967295) to disable the check.
+///
+/// # Arguments
+/// * `limit` - New limit in bytes
+#[napi]
+pub fn set_max_direct_sign_bytes(limit: u32) {
+  MAX_DIRECT_SIGN_BYTES.store(limit, Ordering::Relaxed);
+}
+
+/// Sign a message with ML-DSA-65
+///
+/// Messages longer than `max_direct_sign_bytes` (1 MiB by default) are
+/// rejected; sign those with `StreamSigner` or `mldsa65_sign_stream`.
+///
+/// # Arguments
+/// * `message` - Message to sign
+/// * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+///
+/// # Returns
+/// Signature (3309 bytes)
+#[napi]
+pub fn mldsa65_sign(message: Buffer, secret_key: Buffer) -> Result<Buffer> {
+  let limit = max_direct_sign_bytes();
+  if message.len() > limit as usize {
+    return Err(Error::from_reason(format!(
+      "Message too large for direct signing: {} bytes exceeds the {} byte limit; use StreamSigner or mldsa65_sign_stream to sign in pre-hash mode",
+      message.len(),
+      limit
+    )));
+  }
+
+  if secret_key.len() != 4032 {
+    return Err(Error::from_reason(format!(
+      "Invalid secret key length: expected 4032 bytes, got {}",
+      secret_key.len()
+    )));
+  }
+
+  // Parse secret key from bytes
+  let sk = SecretKey::from_bytes(secret_key.as_ref())
+    .map_err(|_| Error::from_reason("Invalid secret key format"))?;
+
+  // Sign the message
+  let signature = detached_sign(message.as_ref(), &sk);
+
+  Ok(signature.as_bytes().to_vec().into())
+}
+
+/// Verify a signature with ML-DSA-65
+///
+/// # Arguments
+/// * `message` - Original message
+/// * `signature` - Signature to verify (3309 bytes)
+/// * `public_key` - ML-DSA-65 public key (1952 bytes)
+///
+/// # Returns
+/// true if signature is valid, false otherwise
+#[napi]
+pub fn mldsa65_verify(message: Buffer, signature: Buffer, public_key: Buffer) -> Result<bool> {
+  if public_key.len() != 1952 {
+    return Err(Error::from_reason(format!(
+      "Invalid public key length: expected 1952 bytes, got {}",
+      public_key.len()
+    )));
+  }
+
+  if signature.len() != 3309 {
+    return Err(Error::from_reason(format!(
+      "Invalid signature length: expected 3309 bytes, got {}",
+      signature.len()
+    )));
+  }
+
+  // Parse public key and signature from bytes
+  let pk = PublicKey::from_bytes(public_key.as_ref())
+    .map_err(|_| Error::from_reason("Invalid public key format"))?;
+  let sig = DetachedSignature::from_bytes(signature.as_ref())
+    .map_err(|_| Error::from_reason("Invalid signature format"))?;
+
+  // Verify the signature
+  Ok(verify_detached_signature(&sig, message.as_ref(), &pk).is_ok())
+}
+
+/// Verify an ML-DSA-65 signature and report why it failed
+///
+/// A debugging aid: unlike `mldsa65_verify` it never throws, and a failure
+/// carries a reason distinguishing malformed inputs from a signature that
+/// simply does not verify.
+///
+/// # Arguments
+/// * `message` - Original message
+/// * `signature` - Signature to verify (3309 bytes)
+/// * `public_key` - ML-DSA-65 public key (1952 bytes)
+///
+/// # Returns
+/// VerifyReport with `valid` and, on failure, a `reason`
+#[napi]
+pub fn mldsa65_verify_verbose(message: Buffer, signature: Buffer, public_key: Buffer) -> VerifyReport {
+  let failure = |reason: &str| VerifyReport {
+    valid: false,
+    reason: Some(reason.to_string()),
+    diagnosis: None,
+  };
+
+  if public_key.len() != 1952 {
+    return failure("bad public key length");
+  }
+  if signature.len() != 3309 {
+    return failure("bad signature length");
+  }
+
+  match mldsa65_verify(message, signature, public_key) {
+    Ok(true) => VerifyReport {
+      valid: true,
+      reason: None,
+      diagnosis: None,
+    },
+    _ => failure("signature does not verify"),
+  }
+}
+
+/// Verify an ML-DSA-65 signature and diagnose a signer/message mismatch
+///
+/// Runs the same verification as `mldsa65_verify_verbose`, then adds the
+/// likely cause as `diagnosis`:
+/// - "bound": the signature verifies for this message and key
+/// - "key_mismatch": the key is not an ML-DSA-65 public key, e.g. an
+///   ML-KEM key or an all-zero placeholder passed by mistake
+/// - "corrupt_signature": the signature has the wrong length or is all zeros
+/// - "message_or_key_mismatch": a well-formed signature that does not verify
+///
+/// An ML-DSA signature carries nothing about the message or the key beyond
+/// what verification checks, so a substituted message and a signature from
+/// a different genuine ML-DSA-65 key fail identically and both end up in
+/// "message_or_key_mismatch". To narrow that case down, verify against
+/// other candidate keys or the original message.
+///
+/// # Arguments
+/// * `message` - Message the signature is claimed to cover
+/// * `signature` - Signature to check
+/// * `public_key` - Claimed signer's public key
+///
+/// # Returns
+/// VerifyReport with `valid`, `reason` on failure, and `diagnosis`
+#[napi]
+pub fn signature_binds_message(message: Buffer, signature: Buffer, public_key: Buffer) -> VerifyReport {
+  let all_zero = |bytes: &[u8]| bytes.iter().all(|&b| b == 0);
+  let diagnosis = if public_key.len() != 1952 || all_zero(&public_key) {
+    "key_mismatch"
+  } else if signature.len() != 3309 || all_zero(&signature) {
+    "corrupt_signature"
+  } else {
+    "message_or_key_mismatch"
+  };
+
+  let mut report = mldsa65_verify_verbose(message, signature, public_key);
+  report.diagnosis = Some(if report.valid { "bound" } else { diagnosis }.to_string());
+  report
+}
+
+/// Sign a list of messages with a single ML-DSA-65 signature
+///
+/// ML-DSA has no native aggregation, so this signs a BLAKE3 digest of the
+/// length-prefixed messages instead. The signature commits to the exact
+/// messages and their order; it is not a true aggregate signature. Only the
+/// 32-byte digest is signed, so `max_direct_sign_bytes` does not limit the
+/// total size of the messages.
+///
+/// # Arguments
+/// * `messages` - Messages to commit to, in order
+/// * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+///
+/// # Returns
+/// Signature (3309 bytes)
+#[napi]
+pub fn mldsa65_sign_multi(messages: Vec<Buffer>, secret_key: Buffer) -> Result<Buffer> {
+  let digest = multi_message_digest(&messages);
+  mldsa65_sign(digest.to_vec().into(), secret_key)
+}
+
+/// Verify a signature produced by `mldsa65_sign_multi`
+///
+/// # Arguments
+/// * `messages` - Messages in the same order they were signed
+/// * `signature` - Signature to verify (3309 bytes)
+/// * `public_key` - ML-DSA-65 public key (1952 bytes)
+///
+/// # Returns
+/// true if the signature covers exactly these messages in this order
+#[napi]
+pub fn mldsa65_verify_multi(messages: Vec<Buffer>, signature: Buffer, public_key: Buffer) -> Result<bool> {
+  let digest = multi_message_digest(&messages);
+  mldsa65_verify(digest.to_vec().into(), signature, public_key)
+}
+
+/// Verify an ML-DSA-65 signature from a pinned signer
+///
+/// Checks that `quantum_fingerprint(public_key)` equals `expected_fp`
+/// (compared in constant time) before verifying the signature, so a valid
+/// signature under some other key is rejected in the same call.
+///
+/// # Arguments
+/// * `message` - Original message
+/// * `signature` - Signature to verify (3309 bytes)
+/// * `public_key` - ML-DSA-65 public key (1952 bytes)
+/// * `expected_fp` - Pinned signer fingerprint, "qf:<hex>" as produced by `quantum_fingerprint`
+///
+/// # Returns
+/// true only if the key matches the fingerprint and the signature verifies
+#[napi]
+pub fn mldsa65_verify_with_expected_fingerprint(
+  message: Buffer,
+  signature: Buffer,
+  public_key: Buffer,
+  expected_fp: String,
+) -> Result<bool> {
+  let actual_fp = quantum_fingerprint(public_key.clone())?;
+  let fingerprint_matches = crate::utils::constant_time_compare(
+    actual_fp.into_bytes().into(),
+    expected_fp.into_bytes().into(),
+  )?;
+  if !fingerprint_matches {
+    return Ok(false);
+  }
+
+  mldsa65_verify(message, signature, public_key)
+}
+
+/// Check that every signature in a set is valid
+///
+/// Every item is verified, even after one fails, and the results are
+/// combined without branching, so the running time doesn't reveal which
+/// signature was bad. A malformed item (wrong key or signature length)
+/// counts as invalid rather than throwing.
+///
+/// # Arguments
+/// * `items` - Message, signature and public key triples
+///
+/// # Returns
+/// true only if every item verifies; true for an empty list
+#[napi]
+pub fn all_signatures_valid(items: Vec<VerifyItem>) -> bool {
+  verify_each(items)
+    .into_iter()
+    .fold(1u8, |acc, valid| acc & valid as u8)
+    == 1
+}
+
+/// Verify every item, returning one result per item in order
+fn verify_each(items: Vec<VerifyItem>) -> Vec<bool> {
+  items
+    .into_iter()
+    .map(|item| matches!(mldsa65_verify(item.message, item.signature, item.public_key), Ok(true)))
+    .collect()
+}
+
+fn multi_message_digest(messages: &[Buffer]) -> [u8; 32] {
+  hash_length_prefixed("qudag-napi mldsa65 multi-message v1", messages.iter().map(|m| m.as_ref()))
+}
+
+/// BLAKE3 derive-key context for streamed ML-DSA-65 signatures
+pub(crate) const STREAM_SIGNATURE_CONTEXT: &str = "qudag-napi mldsa65 stream v1";
+
+pub use stream_signer_class::StreamSigner;
+
+#[allow(missing_docs)]
+mod stream_signer_class {
+  use super::*;
+
+  /// Incremental ML-DSA-65 signer for streamed data
+  ///
+  /// Hashes chunks with BLAKE3 (in `derive_key` mode under a dedicated
+  /// context) and signs the 32-byte digest with ML-DSA-65. This is the same
+  /// hash-then-sign construction as `mldsa65_sign_multi`, not the FIPS 204
+  /// HashML-DSA variant, which `pqcrypto-dilithium` does not provide.
+  /// Chunk boundaries do not affect the signature, so a stream signed in
+  /// pieces verifies as one message with `mldsa65_verify_stream`, and vice
+  /// versa.
+  #[napi]
+  pub struct StreamSigner {
+    pub(super) hasher: blake3::Hasher,
+  }
+}
+
+#[napi]
+impl StreamSigner {
+  /// Create a signer with an empty stream
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Self {
+      hasher: blake3::Hasher::new_derive_key(STREAM_SIGNATURE_CONTEXT),
+    }
+  }
+
+  /// Absorb the next chunk of the stream
+  #[napi]
+  pub fn update(&mut self, chunk: Buffer) {
+    self.hasher.update(chunk.as_ref());
+  }
+
+  /// Sign everything absorbed so far with an ML-DSA-65 secret key (4032 bytes)
+  #[napi(js_name = "finalize")]
+  pub fn finalize_signature(&self, secret_key: Buffer) -> Result<Buffer> {
+    mldsa65_sign(self.hasher.finalize().as_bytes().to_vec().into(), secret_key)
+  }
+}
+
+impl Default for StreamSigner {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+pub use stream_verifier_class::StreamVerifier;
+
+#[allow(missing_docs)]
+mod stream_verifier_class {
+  use super::*;
+
+  /// Incremental verifier for `StreamSigner` signatures
+  #[napi]
+  pub struct StreamVerifier {
+    pub(super) hasher: blake3::Hasher,
+  }
+}
+
+#[napi]
+impl StreamVerifier {
+  /// Create a verifier with an empty stream
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Self {
+      hasher: blake3::Hasher::new_derive_key(STREAM_SIGNATURE_CONTEXT),
+    }
+  }
+
+  /// Absorb the next chunk of the stream
+  #[napi]
+  pub fn update(&mut self, chunk: Buffer) {
+    self.hasher.update(chunk.as_ref());
+  }
+
+  /// Verify a 3309-byte signature over everything absorbed so far
+  #[napi]
+  pub fn verify(&self, signature: Buffer, public_key: Buffer) -> Result<bool> {
+    mldsa65_verify(self.hasher.finalize().as_bytes().to_vec().into(), signature, public_key)
+  }
+}
+
+impl Default for StreamVerifier {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Sign a whole message as a single-chunk stream
+///
+/// # Arguments
+/// * `message` - Complete message
+/// * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+///
+/// # Returns
+/// Signature (3309 bytes), interchangeable with `StreamSigner` output
+#[napi]
+pub fn mldsa65_sign_stream(message: Buffer, secret_key: Buffer) -> Result<Buffer> {
+  let mut signer = StreamSigner::new();
+  signer.update(message);
+  signer.finalize_signature(secret_key)
+}
+
+/// Verify a stream signature over a whole message
+///
+/// # Arguments
+/// * `message` - Complete message
+/// * `signature` - Signature from `StreamSigner` or `mldsa65_sign_stream` (3309 bytes)
+/// * `public_key` - ML-DSA-65 public key (1952 bytes)
+///
+/// # Returns
+/// true if the signature is valid for the message
+#[napi]
+pub fn mldsa65_verify_stream(message: Buffer, signature: Buffer, public_key: Buffer) -> Result<bool> {
+  let mut verifier = StreamVerifier::new();
+  verifier.update(message);
+  verifier.verify(signature, public_key)
+}
+
+// ============================================================================
+// X25519 (RFC 7748) - Classical Key Agreement
+// ============================================================================
+
+/// Generate a new X25519 keypair
+///
+/// Returns a KeyPair with:
+/// - Public key: 32 bytes
+/// - Secret key: 32 bytes
+#[napi]
+pub fn x25519_generate_keypair() -> Result<KeyPair> {
+  let secret = X25519Secret::random_from_rng(module_rng());
+  let public = X25519PublicKey::from(&secret);
+
+  Ok(KeyPair {
+    public_key: public.as_bytes().to_vec().into(),
+    secret_key: secret.to_bytes().to_vec().into(),
+  })
+}
+
+/// Compute an X25519 shared secret
+///
+/// Both parties get the same secret from their own secret key and the
+/// other's public key. Feed it, together with the ML-KEM secret, to
+/// `hybrid_kem_combine` rather than using it as a key directly.
+///
+/// # Arguments
+/// * `my_secret` - Own X25519 secret key (32 bytes)
+/// * `their_public` - Peer's X25519 public key (32 bytes)
+///
+/// # Returns
+/// Shared secret (32 bytes); errors if the peer key is a low-order point
+#[napi]
+pub fn x25519_diffie_hellman(my_secret: Buffer, their_public: Buffer) -> Result<Buffer> {
+  let mut secret_bytes: [u8; 32] = my_secret.as_ref()
+    .try_into()
+    .map_err(|_| Error::from_reason(format!(
+      "Invalid secret key length: expected 32 bytes, got {}",
+      my_secret.len()
+    )))?;
+  let public_bytes: [u8; 32] = their_public.as_ref()
+    .try_into()
+    .map_err(|_| Error::from_reason(format!(
+      "Invalid public key length: expected 32 bytes, got {}",
+      their_public.len()
+    )))?;
+
+  let secret = X25519Secret::from(secret_bytes);
+  secret_bytes.zeroize();
+  let shared = secret.diffie_hellman(&X25519PublicKey::from(public_bytes));
+
+  // A low-order peer key forces an all-zero secret regardless of our key
+  if !shared.was_contributory() {
+    return Err(Error::from_reason("Invalid public key: low-order point"));
+  }
+
+  Ok(shared.as_bytes().to_vec().into())
+}
+
+// ============================================================================
+// BLAKE3 - Cryptographic Hashing
+// ============================================================================
+
+/// Hash a sequence of byte strings, each prefixed with its u64 LE length
+///
+/// The length prefixes make the encoding injective, so no two different
+/// sequences hash the same. `context` domain-separates each use.
+pub(crate) fn hash_length_prefixed<'a>(context: &str, parts: impl IntoIterator<Item = &'a [u8]>) -> [u8; 32] {
+  let mut hasher = blake3::Hasher::new_derive_key(context);
+  for part in parts {
+    hasher.update(&(part.len() as u64).to_le_bytes());
+    hasher.update(part);
+  }
+  *hasher.finalize().as_bytes()
+}
+
+/// Compute BLAKE3 hash of data
+///
+/// # Arguments
+/// * `data` - Data to hash
+///
+/// # Returns
+/// 32-byte hash
+#[napi]
+pub fn blake3_hash(data: Buffer) -> Result<Buffer> {
+  let hash = blake3::hash(data.as_ref());
+  Ok(hash.as_bytes().to_vec().into())
+}
+
+/// Compute BLAKE3 hash of data into a caller-provided buffer
+///
+/// Writes the digest into `out` instead of allocating a new Buffer, so a
+/// hashing loop can reuse one output buffer.
+///
+/// # Arguments
+/// * `data` - Data to hash
+/// * `out` - Destination buffer (exactly 32 bytes), overwritten in place
+#[napi]
+pub fn blake3_hash_into(data: Buffer, mut out: Buffer) -> Result<()> {
+  if out.len() != 32 {
+    return Err(Error::from_reason(format!(
+      "Invalid output buffer length: expected 32 bytes, got {}",
+      out.len()
+    )));
+  }
+
+  out.copy_from_slice(blake3::hash(data.as_ref()).as_bytes());
+  Ok(())
+}
+
+/// Compute BLAKE3 hash as hex string
+///
+/// # Arguments
+/// * `data` - Data to hash
+///
+/// # Returns
+/// 64-character hex string
+#[napi]
+pub fn blake3_hash_hex(data: Buffer) -> Result<String> {
+  let hash = blake3::hash(data.as_ref());
+  Ok(hash.to_hex().to_string())
+}
+
+/// Check many blobs against expected BLAKE3 digests
+///
+/// # Arguments
+/// * `items` - Data and expected digest pairs
+///
+/// # Returns
+/// One result per item, in order; each digest is compared in constant time,
+/// and an expected digest that is not 32 bytes yields false
+#[napi]
+pub fn blake3_verify_many(items: Vec<DigestCheck>) -> Vec<bool> {
+  items
+    .into_iter()
+    .map(|item| {
+      let actual = blake3::hash(item.data.as_ref()).as_bytes().to_vec();
+      matches!(crate::utils::constant_time_compare(actual.into(), item.expected), Ok(true))
+    })
+    .collect()
+}
+
+/// Compute SHA3-256 hash of data (FIPS 202)
+///
+/// # Arguments
+/// * `data` - Data to hash
+///
+/// # Returns
+/// 32-byte hash
+#[napi]
+pub fn sha3_256_hash(data: Buffer) -> Result<Buffer> {
+  Ok(Sha3_256::digest(data.as_ref()).to_vec().into())
+}
+
+/// Compute a 64-byte composite hash: BLAKE3(data) || SHA3-256(data)
+///
+/// For content addressing that must survive a break of either hash
+/// function: finding a collision requires colliding both at once.
+///
+/// # Arguments
+/// * `data` - Data to hash
+///
+/// # Returns
+/// 64-byte composite hash
+#[napi]
+pub fn composite_hash(data: Buffer) -> Result<Buffer> {
+  Ok(composite_digest(data.as_ref()).to_vec().into())
+}
+
+/// Check data against a composite hash from `composite_hash`
+///
+/// Both halves are compared, in constant time.
+///
+/// # Arguments
+/// * `data` - Data to check
+/// * `expected` - 64-byte composite hash
+///
+/// # Returns
+/// true only if both the BLAKE3 and SHA3-256 halves match; false for a
+/// hash that is not 64 bytes
+#[napi]
+pub fn verify_composite_hash(data: Buffer, expected: Buffer) -> Result<bool> {
+  crate::utils::constant_time_compare(composite_digest(data.as_ref()).to_vec().into(), expected)
+}
+
+fn composite_digest(data: &[u8]) -> [u8; 64] {
+  let mut out = [0u8; 64];
+  out[..32].copy_from_slice(blake3::hash(data).as_bytes());
+  out[32..].copy_from_slice(&Sha3_256::digest(data));
+  out
+}
+
+/// Largest BLAKE3 XOF output returned by one call, in bytes (64 KiB)
+pub(crate) const MAX_XOF_OUTPUT_BYTES: u32 = 64 * 1024;
+
+/// Reject an XOF output length above `MAX_XOF_OUTPUT_BYTES`
+pub(crate) fn check_xof_length(length: u32) -> Result<()> {
+  if length > MAX_XOF_OUTPUT_BYTES {
+    return Err(Error::from_reason(format!(
+      "XOF output too large: at most {} bytes, got {}",
+      MAX_XOF_OUTPUT_BYTES, length
+    )));
+  }
+  Ok(())
+}
+
+/// Compute BLAKE3 extendable output (XOF) of data
+///
+/// # Arguments
+/// * `data` - Data to hash
+/// * `length` - Number of output bytes (at most 65536; use `Blake3Xof` for
+///   longer streams)
+///
+/// # Returns
+/// `length` bytes of BLAKE3 output; the first 32 bytes equal `blake3_hash`
+#[napi]
+pub fn blake3_hash_xof(data: Buffer, length: u32) -> Result<Buffer> {
+  check_xof_length(length)?;
+  let mut output = vec![0u8; length as usize];
+  blake3::Hasher::new()
+    .update(data.as_ref())
+    .finalize_xof()
+    .fill(&mut output);
+  Ok(output.into())
+}
+
+/// Compute BLAKE3 hash of a range of a buffer
+///
+/// Hashes `data[offset..offset + len]` without the caller having to create
+/// a `subarray()` on the JS side.
+///
+/// # Arguments
+/// * `data` - Buffer containing the range
+/// * `offset` - Start of the range in bytes
+/// * `len` - Length of the range in bytes
+///
+/// # Returns
+/// 32-byte hash
+#[napi]
+pub fn blake3_hash_range(data: Buffer, offset: u32, len: u32) -> Result<Buffer> {
+  let start = offset as usize;
+  let end = start.checked_add(len as usize)
+    .filter(|&end| end <= data.len())
+    .ok_or_else(|| Error::from_reason(format!(
+      "Range out of bounds: offset {} + length {} exceeds buffer length {}",
+      offset,
+      len,
+      data.len()
+    )))?;
+
+  let hash = blake3::hash(&data[start..end]);
+  Ok(hash.as_bytes().to_vec().into())
+}
+
+/// BLAKE3 digest of zero-length input
+const BLAKE3_EMPTY_HEX: &str = "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262";
+/// SHA3-256 digest of zero-length input (FIPS 202)
+const SHA3_256_EMPTY_HEX: &str = "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a";
+
+/// Return the published digest of zero-length input
+///
+/// Handy for assertions and sanity checks: the values are the published
+/// constants, not recomputed, so comparing them with `blake3_hash` or
+/// `sha3_256_hash` of an empty buffer checks the linked implementation.
+///
+/// # Arguments
+/// * `algorithm` - "blake3" or "sha3-256"
+///
+/// # Returns
+/// 32-byte digest of the empty input
+#[napi]
+pub fn empty_digest(algorithm: String) -> Result<Buffer> {
+  let digest_hex = match algorithm.parse::<Algorithm>() {
+    Ok(Algorithm::Blake3) => BLAKE3_EMPTY_HEX,
+    Ok(Algorithm::Sha3_256) => SHA3_256_EMPTY_HEX,
+    _ => return Err(Error::from_reason(format!("Unsupported hash algorithm: {}", algorithm))),
+  };
+  Ok(hex::decode(digest_hex).expect("constant is valid hex").into())
+}
+
+/// Compute BLAKE3 hash of data, feeding the hasher in fixed-size updates
+///
+/// A tuning aid: `chunk_size` only changes how the input is batched into
+/// `update` calls, never the digest, which always equals `blake3_hash`.
+///
+/// # Arguments
+/// * `data` - Data to hash
+/// * `chunk_size` - Bytes per update (at least 1)
+///
+/// # Returns
+/// 32-byte hash
+#[napi]
+pub fn blake3_hash_chunked(data: Buffer, chunk_size: u32) -> Result<Buffer> {
+  if chunk_size == 0 {
+    return Err(Error::from_reason("Chunk size must be at least 1 byte"));
+  }
+
+  let mut hasher = blake3::Hasher::new();
+  for chunk in data.chunks(chunk_size as usize) {
+    hasher.update(chunk);
+  }
+  Ok(hasher.finalize().as_bytes().to_vec().into())
+}
+
+/// Compute BLAKE3 keyed hash (MAC) of data
+///
+/// # Arguments
+/// * `key` - Secret key (32 bytes)
+/// * `data` - Data to authenticate
+///
+/// # Returns
+/// 32-byte tag
+#[napi]
+pub fn blake3_keyed_hash(key: Buffer, data: Buffer) -> Result<Buffer> {
+  let key: [u8; 32] = key.as_ref()
+    .try_into()
+    .map_err(|_| Error::from_reason(format!(
+      "Invalid key length: expected 32 bytes, got {}",
+      key.len()
+    )))?;
+
+  let hash = blake3::keyed_hash(&key, data.as_ref());
+  Ok(hash.as_bytes().to_vec().into())
+}
+
+/// Verify a BLAKE3 keyed hash (MAC) tag in constant time
+///
+/// Recomputes the keyed hash of `data` and compares it with `tag` using the
+/// constant-time equality of `blake3::Hash`, so callers don't need to roll
+/// their own comparison.
+///
+/// # Arguments
+/// * `key` - Secret key (32 bytes)
+/// * `data` - Authenticated data
+/// * `tag` - Tag to check
+///
+/// # Returns
+/// true if the tag is valid; false on mismatch or if the tag is not 32 bytes
+#[napi]
+pub fn blake3_verify_mac(key: Buffer, data: Buffer, tag: Buffer) -> Result<bool> {
+  let expected = blake3_keyed_hash(key, data)?;
+  let Ok(tag) = <[u8; 32]>::try_from(tag.as_ref()) else {
+    return Ok(false);
+  };
+  let expected: [u8; 32] = expected.as_ref().try_into().expect("32-byte BLAKE3 output");
+
+  Ok(blake3::Hash::from(expected) == tag)
+}
+
+/// Derive a 32-byte key with BLAKE3 `derive_key` mode
+///
+/// # Arguments
+/// * `context` - Hardcoded, globally unique, application-specific context string
+/// * `key_material` - Input key material
+///
+/// # Returns
+/// 32-byte derived key
+#[napi]
+pub fn blake3_derive_key(context: String, key_material: Buffer) -> Result<Buffer> {
+  let key = blake3::derive_key(&context, key_material.as_ref());
+  Ok(key.to_vec().into())
+}
+
+/// Compute BLAKE3 hash of data within a named domain
+///
+/// A key is derived from `context` and used to keyed-hash `data`, so the
+/// same data hashed under different contexts gives unrelated digests.
+///
+/// Unlike `blake3_keyed_hash`, the key comes from a public context string
+/// rather than a caller-held secret, so the result separates domains but
+/// does not authenticate anything. Unlike `blake3_derive_key`, the output is
+/// a digest of `data`, not key material.
+///
+/// # Arguments
+/// * `data` - Data to hash
+/// * `context` - Domain the hash belongs to
+///
+/// # Returns
+/// 32-byte hash
+#[napi]
+pub fn blake3_hash_with_context(data: Buffer, context: String) -> Result<Buffer> {
+  let key = blake3::derive_key("qudag-napi blake3_hash_with_context v1", context.as_bytes());
+  let hash = blake3::keyed_hash(&key, data.as_ref());
+  Ok(hash.as_bytes().to_vec().into())
+}
+
+/// Generate quantum-resistant fingerprint
+///
+/// # Arguments
+/// * `data` - Data to fingerprint
+///
+/// # Returns
+/// Fingerprint string in format "qf:{hash}"
 #[napi]
 pub fn quantum_fingerprint(data: Buffer) -> Result<String> {
   let hash = blake3::hash(data.as_ref());
   Ok(format!("qf:{}", hash.to_hex()))
 }
 
+/// Longest SHAKE256 fingerprint digest produced or parsed, in bytes
+const MAX_SHAKE_FINGERPRINT_BYTES: usize = 1024;
+
+/// Generate a variable-length quantum fingerprint using SHAKE256
+///
+/// FIPS 202-aligned alternative to `quantum_fingerprint`. Like any XOF, a
+/// shorter fingerprint of the same data is a prefix of a longer one, so
+/// compare fingerprints of the same length only.
+///
+/// # Arguments
+/// * `data` - Data to fingerprint
+/// * `bytes` - Digest length in bytes (1 to 1024)
+///
+/// # Returns
+/// Fingerprint string in format "qf:sk:{hex}"
+#[napi]
+pub fn quantum_fingerprint_shake(data: Buffer, bytes: u32) -> Result<String> {
+  if bytes == 0 {
+    return Err(Error::from_reason("Fingerprint length must be at least 1 byte"));
+  }
+  if bytes as usize > MAX_SHAKE_FINGERPRINT_BYTES {
+    return Err(Error::from_reason(format!(
+      "Fingerprint length too large: at most {} bytes, got {}",
+      MAX_SHAKE_FINGERPRINT_BYTES, bytes
+    )));
+  }
+
+  let mut digest = vec![0u8; bytes as usize];
+  Shake256::digest_xof(data.as_ref(), &mut digest);
+  Ok(format!("qf:sk:{}", hex::encode(digest)))
+}
+
+/// Generate a quantum fingerprint using SHA3-256
+///
+/// # Arguments
+/// * `data` - Data to fingerprint
+///
+/// # Returns
+/// Fingerprint string in format "qf:s3:{hex}"
+#[napi]
+pub fn quantum_fingerprint_sha3(data: Buffer) -> Result<String> {
+  Ok(format!("qf:s3:{}", hex::encode(Sha3_256::digest(data.as_ref()))))
+}
+
+/// Compute fingerprints of one input under several algorithms
+///
+/// All names are checked before any hashing, and an algorithm requested
+/// more than once is only computed once.
+///
+/// # Arguments
+/// * `data` - Data to fingerprint
+/// * `algorithms` - "blake3" (as `quantum_fingerprint`) or "sha3-256" (as
+///   `quantum_fingerprint_sha3`)
+///
+/// # Returns
+/// One fingerprint per requested algorithm, in order
+#[napi]
+pub fn multi_fingerprint(data: Buffer, algorithms: Vec<String>) -> Result<Vec<String>> {
+  let parsed = algorithms
+    .iter()
+    .map(|name| match name.parse::<Algorithm>() {
+      Ok(alg @ (Algorithm::Blake3 | Algorithm::Sha3_256)) => Ok(alg),
+      _ => Err(Error::from_reason(format!("Unsupported fingerprint algorithm: {}", name))),
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  let mut blake3 = None;
+  let mut sha3 = None;
+  parsed
+    .into_iter()
+    .map(|alg| {
+      let (slot, compute): (_, fn(Buffer) -> Result<String>) = match alg {
+        Algorithm::Blake3 => (&mut blake3, quantum_fingerprint),
+        _ => (&mut sha3, quantum_fingerprint_sha3),
+      };
+      if slot.is_none() {
+        *slot = Some(compute(data.clone())?);
+      }
+      Ok(slot.clone().expect("slot filled above"))
+    })
+    .collect()
+}
+
+/// Generate a domain-separated quantum fingerprint
+///
+/// The digest is BLAKE3 in `derive_key` mode over the length-prefixed
+/// domain and data, so the same data fingerprints differently in every
+/// domain and fingerprints from different subsystems never collide.
+///
+/// # Arguments
+/// * `data` - Data to fingerprint
+/// * `domain` - Subsystem name: lowercase ASCII letters, digits, `.`, `_`
+///   or `-`; "sk" and "s3" are reserved for SHAKE256 and SHA3-256 fingerprints
+///
+/// # Returns
+/// Fingerprint string in format "qf:{domain}:{hash}"
+#[napi]
+pub fn quantum_fingerprint_domain(data: Buffer, domain: String) -> Result<String> {
+  Ok(format!("qf:{}:{}", domain, hex::encode(domain_fingerprint_digest(data.as_ref(), &domain)?)))
+}
+
+/// Check data against a fingerprint from `quantum_fingerprint_domain`
+///
+/// # Arguments
+/// * `data` - Data to check
+/// * `domain` - Domain the fingerprint must belong to
+/// * `fingerprint` - Fingerprint string
+///
+/// # Returns
+/// true only if the fingerprint is for `data` in `domain`; a fingerprint
+/// from any other domain is rejected
+#[napi]
+pub fn verify_fingerprint_domain(data: Buffer, domain: String, fingerprint: String) -> Result<bool> {
+  let expected = quantum_fingerprint_domain(data, domain)?;
+  crate::utils::constant_time_compare(expected.into_bytes().into(), fingerprint.into_bytes().into())
+}
+
+fn domain_fingerprint_digest(data: &[u8], domain: &str) -> Result<[u8; 32]> {
+  let valid = !domain.is_empty()
+    && domain != "sk"
+    && domain != "s3"
+    && domain.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"._-".contains(&b));
+  if !valid {
+    return Err(Error::from_reason(format!("Invalid fingerprint domain: {:?}", domain)));
+  }
+
+  Ok(hash_length_prefixed("qudag-napi domain fingerprint v1", [domain.as_bytes(), data]))
+}
+
+/// Parse a fingerprint produced by `quantum_fingerprint`, `quantum_fingerprint_shake`
+/// or `quantum_fingerprint_sha3`
+///
+/// # Arguments
+/// * `fingerprint` - Fingerprint string ("qf:{hex}", "qf:sk:{hex}" or "qf:s3:{hex}")
+///
+/// SHAKE256 digests are limited to 1024 bytes, like `quantum_fingerprint_shake`.
+///
+/// # Returns
+/// ParsedFingerprint with the algorithm and digest bytes
+#[napi]
+pub fn parse_fingerprint(fingerprint: String) -> Result<ParsedFingerprint> {
+  let (algorithm, digest_hex) = if let Some(hex) = fingerprint.strip_prefix("qf:sk:") {
+    ("shake256", hex)
+  } else if let Some(hex) = fingerprint.strip_prefix("qf:s3:") {
+    ("sha3-256", hex)
+  } else if let Some(hex) = fingerprint.strip_prefix("qf:") {
+    ("blake3", hex)
+  } else {
+    return Err(Error::from_reason("Invalid fingerprint: missing qf: prefix"));
+  };
+
+  if digest_hex.len() > 2 * MAX_SHAKE_FINGERPRINT_BYTES {
+    return Err(Error::from_reason(format!(
+      "Invalid {} fingerprint length: more than {} bytes",
+      algorithm, MAX_SHAKE_FINGERPRINT_BYTES
+    )));
+  }
+
+  let digest = hex::decode(digest_hex)
+    .map_err(|e| Error::from_reason(format!("Invalid fingerprint digest: {}", e)))?;
+  if digest.is_empty() || (algorithm != "shake256" && digest.len() != 32) {
+    return Err(Error::from_reason(format!(
+      "Invalid {} fingerprint length: {} bytes",
+      algorithm,
+      digest.len()
+    )));
+  }
+
+  Ok(ParsedFingerprint {
+    algorithm: algorithm.to_string(),
+    digest: digest.into(),
+  })
+}
+
+/// Shortest fingerprint digest `fingerprint_equals_data` will accept, in bytes
+const MIN_COMPARED_FINGERPRINT_BYTES: usize = 32;
+
+/// Check data against a user-supplied fingerprint
+///
+/// The expected fingerprint is trimmed and lowercased, and a missing `qf:`
+/// prefix is added back, so "QF:ABCD..." and a bare hex digest both match.
+/// Any format accepted by `parse_fingerprint` works; the digest is
+/// recomputed with the matching algorithm and compared in constant time.
+/// SHAKE256 fingerprints shorter than 32 bytes never match, so a truncated
+/// or guessed `qf:sk:` value can't pass the comparison by chance.
+///
+/// # Arguments
+/// * `data` - Data to fingerprint
+/// * `expected` - Fingerprint as entered by a user
+///
+/// # Returns
+/// true if the fingerprint matches; false on mismatch or unparseable input
+#[napi]
+pub fn fingerprint_equals_data(data: Buffer, expected: String) -> bool {
+  let normalized = expected.trim().to_ascii_lowercase();
+  let tagged = if normalized.starts_with("qf:") {
+    normalized
+  } else {
+    format!("qf:{}", normalized)
+  };
+  let Ok(parsed) = parse_fingerprint(tagged) else {
+    return false;
+  };
+  if parsed.digest.len() < MIN_COMPARED_FINGERPRINT_BYTES {
+    return false;
+  }
+
+  let actual = match parsed.algorithm.as_str() {
+    "shake256" => {
+      let mut digest = vec![0u8; parsed.digest.len()];
+      Shake256::digest_xof(data.as_ref(), &mut digest);
+      digest
+    }
+    "sha3-256" => Sha3_256::digest(data.as_ref()).to_vec(),
+    _ => blake3::hash(data.as_ref()).as_bytes().to_vec(),
+  };
+  matches!(crate::utils::constant_time_compare(actual.into(), parsed.digest), Ok(true))
+}
+
+/// Compute an order-independent commitment to a set of public keys
+///
+/// Keys are sorted by their bytes, length-prefixed and hashed with BLAKE3 in
+/// `derive_key` mode, so the same committee or validator set always yields
+/// the same commitment regardless of input order. Duplicate keys are kept,
+/// so a set containing a key twice commits differently from one containing
+/// it once.
+///
+/// # Arguments
+/// * `keys` - Public keys of any algorithm
+///
+/// # Returns
+/// 32-byte commitment
+#[napi]
+pub fn commit_public_key_set(keys: Vec<Buffer>) -> Result<Buffer> {
+  Ok(public_key_set_commitment(&keys).to_vec().into())
+}
+
+/// Check a public key set against a commitment from `commit_public_key_set`
+///
+/// # Arguments
+/// * `keys` - Public keys, in any order
+/// * `commitment` - Expected 32-byte commitment
+///
+/// # Returns
+/// true if the keys match the commitment; false otherwise (including a
+/// commitment that is not 32 bytes)
+#[napi]
+pub fn verify_set_commitment(keys: Vec<Buffer>, commitment: Buffer) -> Result<bool> {
+  let Ok(expected) = <[u8; 32]>::try_from(commitment.as_ref()) else {
+    return Ok(false);
+  };
+  Ok(blake3::Hash::from(public_key_set_commitment(&keys)) == expected)
+}
+
+fn public_key_set_commitment(keys: &[Buffer]) -> [u8; 32] {
+  let mut sorted: Vec<&[u8]> = keys.iter().map(|k| k.as_ref()).collect();
+  sorted.sort_unstable();
+  hash_length_prefixed("qudag-napi public key set commitment v1", sorted)
+}
+
+/// Compute a stable deduplication key for a keypair
+///
+/// BLAKE3 over the length-prefixed public and secret keys, domain-separated
+/// from other hashes in this module. Identical keypairs always hash equal
+/// and any byte difference in either key changes the hash. The result is
+/// derived from the secret key, so treat it as sensitive and don't publish
+/// it as a key identifier; use `quantum_fingerprint` of the public key for
+/// that.
+///
+/// # Arguments
+/// * `kp` - Keypair of any algorithm
+///
+/// # Returns
+/// 32-byte hash
+#[napi]
+pub fn keypair_hash(kp: KeyPair) -> Result<Buffer> {
+  let hash = hash_length_prefixed(
+    "qudag-napi keypair hash v1",
+    [kp.public_key.as_ref(), kp.secret_key.as_ref()],
+  );
+  Ok(hash.to_vec().into())
+}
+
+// ============================================================================
+// Algorithm Dispatch
+// ============================================================================
+
+/// Generate a keypair for the given algorithm
+///
+/// # Arguments
+/// * `algorithm` - "ml-kem-512", "ml-kem-768" or "ml-dsa-65"
+///
+/// # Returns
+/// KeyPair for the requested algorithm; hash algorithms are rejected
+#[napi]
+pub fn generate_keypair(algorithm: Algorithm) -> Result<KeyPair> {
+  match algorithm {
+    Algorithm::MlKem512 => {
+      let (dk, ek) = MlKem512::generate(&mut module_rng());
+      Ok(KeyPair {
+        public_key: ek.as_bytes().to_vec().into(),
+        secret_key: dk.as_bytes().to_vec().into(),
+      })
+    }
+    Algorithm::MlKem768 => mlkem768_generate_keypair(),
+    Algorithm::MlDsa65 => mldsa65_generate_keypair(),
+    Algorithm::Blake3 | Algorithm::Sha3_256 => Err(Error::from_reason(format!(
+      "{} is a hash algorithm and has no keypair",
+      algorithm.as_str()
+    ))),
+  }
+}
+
+/// List every algorithm this build exposes, for capability negotiation
+///
+/// `ready` is false for algorithms that are present only as placeholders.
+/// Every algorithm in this build, including ML-DSA-65 (backed by
+/// `pqcrypto-dilithium`), has a real implementation and reports ready.
+///
+/// # Returns
+/// One AlgorithmStatus per algorithm, in `Algorithm` declaration order
+#[napi]
+pub fn supported_algorithms() -> Vec<AlgorithmStatus> {
+  Algorithm::ALL
+    .into_iter()
+    .map(|algorithm| {
+      let kind = match algorithm {
+        Algorithm::MlKem512 | Algorithm::MlKem768 => "kem",
+        Algorithm::MlDsa65 => "signature",
+        Algorithm::Blake3 | Algorithm::Sha3_256 => "hash",
+      };
+      AlgorithmStatus {
+        name: algorithm.as_str().to_string(),
+        kind: kind.to_string(),
+        ready: true,
+      }
+    })
+    .collect()
+}
+
+/// Approximate per-keypair overhead beyond the raw key bytes: the KeyPair
+/// object plus two Buffer headers and their backing-store bookkeeping
+const KEYPAIR_OVERHEAD_BYTES: u32 = 256;
+
+/// Estimate the memory a keypair of the given algorithm occupies
+///
+/// Intended for sizing key caches. The estimate is the public and secret
+/// key lengths plus a fixed allowance for the JS objects holding them; it is
+/// an approximation, not a measurement of the engine's actual allocations.
+///
+/// # Arguments
+/// * `algorithm` - "ml-kem-512", "ml-kem-768" or "ml-dsa-65"
+///
+/// # Returns
+/// Approximate bytes per keypair; hash algorithms are rejected
+#[napi]
+pub fn estimated_memory(algorithm: Algorithm) -> Result<u32> {
+  let (public_key_len, secret_key_len) = keypair_lengths(algorithm)?;
+  Ok(public_key_len + secret_key_len + KEYPAIR_OVERHEAD_BYTES)
+}
+
+/// Public and secret key lengths in bytes for a keypair algorithm
+pub(crate) fn keypair_lengths(algorithm: Algorithm) -> Result<(u32, u32)> {
+  match algorithm {
+    Algorithm::MlKem512 => Ok((800, 1632)),
+    Algorithm::MlKem768 => Ok((1184, 2400)),
+    Algorithm::MlDsa65 => Ok((1952, 4032)),
+    Algorithm::Blake3 | Algorithm::Sha3_256 => Err(Error::from_reason(format!(
+      "{} is a hash algorithm and has no keypair",
+      algorithm.as_str()
+    ))),
+  }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // ML-KEM-768 Tests
+  #[test]
+  fn test_mlkem_keygen() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    assert_eq!(keypair.public_key.len(), 1184);
+    assert_eq!(keypair.secret_key.len(), 2400);
+  }
+
+  #[test]
+  fn test_mlkem_generate_keypair_owned() {
+    let mut first = mlkem768_generate_keypair_owned().unwrap();
+    let second = mlkem768_generate_keypair_owned().unwrap();
+    assert_eq!(first.public_key.len(), 1184);
+    assert_eq!(first.secret_key.len(), 2400);
+
+    // The public key is embedded in the secret key; the arrays must still be separate
+    assert!(mlkem768_validate_keypair(KeyPair {
+      public_key: first.public_key.to_vec().into(),
+      secret_key: first.secret_key.to_vec().into(),
+    }).unwrap());
+    let snapshot = second.public_key.to_vec();
+    first.public_key[0] ^= 0xff;
+    assert_eq!(&first.secret_key[1152..1153], &[first.public_key[0] ^ 0xff]);
+    assert_eq!(second.public_key.to_vec(), snapshot);
+    assert_ne!(first.public_key.as_ptr(), second.public_key.as_ptr());
+  }
+
+  #[test]
+  fn test_mlkem_expand_secret_key_from_seed() {
+    let keypair = mlkem768_generate_keypair_with_seed().unwrap();
+    assert_eq!(keypair.seed.len(), 64);
+
+    let expanded = mlkem768_expand_secret_key(keypair.seed).unwrap();
+    assert_eq!(expanded.as_ref(), keypair.secret_key.as_ref());
+    assert!(mlkem768_validate_keypair(KeyPair {
+      public_key: keypair.public_key,
+      secret_key: expanded,
+    }).unwrap());
+
+    assert!(mlkem768_expand_secret_key(vec![0u8; 32].into()).is_err());
+  }
+
+  #[test]
+  fn test_mlkem_derive_keypair() {
+    let master: Buffer = vec![0x11u8; 32].into();
+    let a = mlkem768_derive_keypair(master.clone(), "qudag test signing v1".into()).unwrap();
+    let again = mlkem768_derive_keypair(master.clone(), "qudag test signing v1".into()).unwrap();
+    assert_eq!(a.public_key.as_ref(), again.public_key.as_ref());
+    assert_eq!(a.secret_key.as_ref(), again.secret_key.as_ref());
+
+    let b = mlkem768_derive_keypair(master, "qudag test transport v1".into()).unwrap();
+    assert_ne!(a.public_key.as_ref(), b.public_key.as_ref());
+    assert_ne!(a.secret_key.as_ref(), b.secret_key.as_ref());
+    assert!(mlkem768_validate_keypair(b).unwrap());
+
+    assert!(mlkem768_derive_keypair(vec![0u8; 16].into(), "ctx".into()).is_err());
+  }
+
+  #[test]
+  fn test_mlkem_encapsulate_decapsulate() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let encapsulated = mlkem768_encapsulate(keypair.public_key.clone()).unwrap();
+
+    assert_eq!(encapsulated.ciphertext.len(), 1088);
+    assert_eq!(encapsulated.shared_secret.len(), 32);
+
+    let decapsulated_secret = mlkem768_decapsulate(
+      encapsulated.ciphertext.clone(),
+      keypair.secret_key.clone()
+    ).unwrap();
+
+    assert_eq!(decapsulated_secret.len(), 32);
+    assert_eq!(
+      encapsulated.shared_secret.as_ref(),
+      decapsulated_secret.as_ref(),
+      "Shared secrets must match after encapsulation/decapsulation"
+    );
+  }
+
+  #[test]
+  fn test_keypair_generator_reseeds() {
+    let mut generator = KeyPairGenerator::new(Some(3)).unwrap();
+    let mut public_keys = std::collections::HashSet::new();
+
+    for _ in 0..7 {
+      let kp = generator.generate().unwrap();
+      assert!(mlkem768_validate_keypair(KeyPair {
+        public_key: kp.public_key.clone(),
+        secret_key: kp.secret_key.clone(),
+      }).unwrap());
+      assert!(public_keys.insert(kp.public_key.to_vec()));
+    }
+    // Reseeded before the 4th and 7th keypairs
+    assert_eq!(generator.reseeds(), 2);
+
+    assert_eq!(KeyPairGenerator::new(None).unwrap().reseed_interval, DEFAULT_RESEED_INTERVAL);
+    assert!(KeyPairGenerator::new(Some(0)).is_err());
+  }
+
+  #[test]
+  fn test_ciphertexts_share_secret() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let a = mlkem768_encapsulate(keypair.public_key.clone()).unwrap();
+    let b = mlkem768_encapsulate(keypair.public_key).unwrap();
+
+    assert!(!ciphertexts_share_secret(a.ciphertext.clone(), b.ciphertext, keypair.secret_key.clone()).unwrap());
+    assert!(ciphertexts_share_secret(a.ciphertext.clone(), a.ciphertext.clone(), keypair.secret_key.clone()).unwrap());
+    assert!(ciphertexts_share_secret(a.ciphertext, vec![0u8; 10].into(), keypair.secret_key).is_err());
+  }
+
+  #[test]
+  fn test_ciphertext_matches_key() {
+    let recipient = mlkem768_generate_keypair().unwrap();
+    let other = mlkem768_generate_keypair().unwrap();
+
+    let for_recipient = mlkem768_encapsulate(recipient.public_key.clone()).unwrap();
+    assert!(ciphertext_matches_key(for_recipient.ciphertext.clone(), recipient.secret_key.clone()).unwrap());
+
+    let for_other = mlkem768_encapsulate(other.public_key).unwrap();
+    assert!(!ciphertext_matches_key(for_other.ciphertext, recipient.secret_key.clone()).unwrap());
+
+    let mut tampered = for_recipient.ciphertext.to_vec();
+    tampered[0] ^= 1;
+    assert!(!ciphertext_matches_key(tampered.into(), recipient.secret_key.clone()).unwrap());
+    assert!(ciphertext_matches_key(vec![0u8; 10].into(), recipient.secret_key).is_err());
+  }
+
+  #[test]
+  fn test_mlkem_channel_bound_secrets() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let channel: Buffer = b"channel-a".to_vec().into();
+    let enc = mlkem768_encapsulate_bound(keypair.public_key, channel.clone()).unwrap();
+
+    let same = mlkem768_decapsulate_bound(enc.ciphertext.clone(), keypair.secret_key.clone(), channel).unwrap();
+    assert_eq!(same.as_ref(), enc.shared_secret.as_ref());
+
+    let other = mlkem768_decapsulate_bound(enc.ciphertext.clone(), keypair.secret_key.clone(), b"channel-b".to_vec().into()).unwrap();
+    assert_ne!(other.as_ref(), enc.shared_secret.as_ref());
+
+    // The bound secret is not the raw KEM secret
+    let raw = mlkem768_decapsulate(enc.ciphertext, keypair.secret_key).unwrap();
+    assert_ne!(raw.as_ref(), enc.shared_secret.as_ref());
+  }
+
+  #[test]
+  fn test_mlkem_encapsulate_batch_parallel() {
+    let keypairs: Vec<KeyPair> = (0..8).map(|_| mlkem768_generate_keypair().unwrap()).collect();
+    let public_keys: Vec<Buffer> = keypairs.iter().map(|kp| kp.public_key.clone()).collect();
+
+    for threads in [0, 1, 4] {
+      let results = mlkem768_encapsulate_batch_parallel(public_keys.clone(), threads).unwrap();
+      assert_eq!(results.len(), keypairs.len());
+
+      // Each result must decapsulate under the key at the same index
+      for (kp, enc) in keypairs.iter().zip(&results) {
+        let serial = mlkem768_decapsulate(enc.ciphertext.clone(), kp.secret_key.clone()).unwrap();
+        assert_eq!(serial.as_ref(), enc.shared_secret.as_ref());
+      }
+    }
+
+    let mut bad = public_keys;
+    bad[5] = vec![0u8; 10].into();
+    let err = mlkem768_encapsulate_batch_parallel(bad, 2).err().unwrap();
+    assert!(err.reason.starts_with("Public key 5:"));
+  }
+
+  #[test]
+  fn test_mlkem_encapsulate_batch_parallel_rejects_too_many_threads() {
+    let public_keys = vec![mlkem768_generate_keypair().unwrap().public_key];
+
+    assert!(mlkem768_encapsulate_batch_parallel(public_keys.clone(), MAX_BATCH_THREADS).is_ok());
+    let err = mlkem768_encapsulate_batch_parallel(public_keys.clone(), MAX_BATCH_THREADS + 1).err().unwrap();
+    assert!(err.reason.starts_with("Too many threads"));
+    assert!(mlkem768_encapsulate_batch_parallel(public_keys, u32::MAX).is_err());
+  }
+
+  #[test]
+  fn test_mlkem_encapsulate_with_coins_reproducible() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let audited = mlkem768_encapsulate_with_coins(keypair.public_key.clone()).unwrap();
+    assert_eq!(audited.coins.len(), 32);
+
+    let replayed = mlkem768_encapsulate_deterministic(
+      keypair.public_key.clone(),
+      audited.coins.clone()
+    ).unwrap();
+
+    assert_eq!(audited.ciphertext.as_ref(), replayed.ciphertext.as_ref());
+    assert_eq!(audited.shared_secret.as_ref(), replayed.shared_secret.as_ref());
+
+    let decapsulated = mlkem768_decapsulate(audited.ciphertext, keypair.secret_key).unwrap();
+    assert_eq!(audited.shared_secret.as_ref(), decapsulated.as_ref());
+  }
+
+  #[test]
+  fn test_example_keypair_is_fixed() {
+    let keypair = example_keypair();
+    let again = example_keypair();
+    assert_eq!(keypair.public_key.as_ref(), again.public_key.as_ref());
+    assert_eq!(keypair.secret_key.as_ref(), again.secret_key.as_ref());
+    assert_eq!(keypair.secret_key.len(), 2400);
+
+    assert_eq!(
+      quantum_fingerprint(keypair.public_key.clone()).unwrap(),
+      "qf:cc4b207d7b69fc89154fcfc6f0b96f989939873391969bc1fe2eea03e6f389aa"
+    );
+
+    let encapsulated = mlkem768_encapsulate(keypair.public_key).unwrap();
+    let shared_secret = mlkem768_decapsulate(encapsulated.ciphertext, keypair.secret_key).unwrap();
+    assert_eq!(shared_secret.as_ref(), encapsulated.shared_secret.as_ref());
+  }
+
+  #[test]
+  fn test_mlkem_encapsulate_deterministic_invalid_coins() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    assert!(mlkem768_encapsulate_deterministic(keypair.public_key, vec![0u8; 16].into()).is_err());
+  }
+
+  #[test]
+  fn test_mlkem_invalid_public_key_length() {
+    let invalid_key = vec![0u8; 100].into();
+    assert!(mlkem768_encapsulate(invalid_key).is_err());
+  }
 
-  // ML-KEM-768 Tests
   #[test]
-  fn test_mlkem_keygen() {
+  fn test_mlkem_invalid_secret_key_length() {
+    let invalid_ciphertext = vec![0u8; 1088].into();
+    let invalid_key = vec![0u8; 100].into();
+    assert!(mlkem768_decapsulate(invalid_ciphertext, invalid_key).is_err());
+  }
+
+  #[test]
+  fn test_mlkem_validate_keypair() {
     let keypair = mlkem768_generate_keypair().unwrap();
-    assert_eq!(keypair.public_key.len(), 1184);
-    assert_eq!(keypair.secret_key.len(), 2400);
+    assert!(mlkem768_validate_keypair(keypair).unwrap());
+
+    let a = mlkem768_generate_keypair().unwrap();
+    let b = mlkem768_generate_keypair().unwrap();
+    let mismatched = KeyPair {
+      public_key: b.public_key,
+      secret_key: a.secret_key,
+    };
+    assert!(!mlkem768_validate_keypair(mismatched).unwrap());
+
+    let truncated = KeyPair {
+      public_key: vec![0u8; 100].into(),
+      secret_key: vec![0u8; 2400].into(),
+    };
+    assert!(!mlkem768_validate_keypair(truncated).unwrap());
   }
 
   #[test]
-  fn test_mlkem_encapsulate_decapsulate() {
+  fn test_mlkem_public_key_reencode() {
     let keypair = mlkem768_generate_keypair().unwrap();
-    let encapsulated = mlkem768_encapsulate(keypair.public_key.clone()).unwrap();
+    let pk = keypair.public_key.to_vec();
 
-    assert_eq!(encapsulated.ciphertext.len(), 1088);
-    assert_eq!(encapsulated.shared_secret.len(), 32);
+    let raw = mlkem768_public_key_reencode(pk.clone().into(), "fips203".into(), "raw".into()).unwrap();
+    assert_eq!(raw.to_vec(), pk);
+    let back = mlkem768_public_key_reencode(raw, "raw".into(), "fips203".into()).unwrap();
+    assert_eq!(back.to_vec(), pk);
 
-    let decapsulated_secret = mlkem768_decapsulate(
-      encapsulated.ciphertext.clone(),
-      keypair.secret_key.clone()
-    ).unwrap();
+    assert!(mlkem768_public_key_reencode(pk.clone().into(), "fips203".into(), "der".into()).is_err());
+    assert!(mlkem768_public_key_reencode(vec![0u8; 100].into(), "raw".into(), "raw".into()).is_err());
 
-    assert_eq!(decapsulated_secret.len(), 32);
+    // 0xfff is not a valid coefficient (q = 3329)
+    let mut unreduced = pk;
+    unreduced[0] = 0xff;
+    unreduced[1] |= 0x0f;
+    assert!(mlkem768_public_key_reencode(unreduced.into(), "raw".into(), "fips203".into()).is_err());
+  }
+
+  #[test]
+  fn test_secret_key_metadata() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let metadata = secret_key_metadata(keypair.secret_key.clone()).unwrap();
+    assert_eq!(metadata.algorithm, "ML-KEM-768");
+    assert_eq!(metadata.length, 2400);
     assert_eq!(
-      encapsulated.shared_secret.as_ref(),
-      decapsulated_secret.as_ref(),
-      "Shared secrets must match after encapsulation/decapsulation"
+      metadata.public_key_fingerprint.as_deref().unwrap(),
+      quantum_fingerprint(keypair.public_key).unwrap()
     );
+
+    // The only variable-length output is a 32-byte digest of the public key
+    let rendered = format!("{}{}{:?}", metadata.algorithm, metadata.length, metadata.public_key_fingerprint);
+    assert!(!rendered.contains(&hex::encode(&keypair.secret_key[..16])));
+    assert_eq!(metadata.public_key_fingerprint.unwrap().len(), 67);
+
+    let mldsa = secret_key_metadata(mldsa65_generate_keypair().unwrap().secret_key).unwrap();
+    assert_eq!(mldsa.algorithm, "ML-DSA-65");
+    assert!(mldsa.public_key_fingerprint.is_none());
+
+    assert!(secret_key_metadata(vec![0u8; 100].into()).is_err());
   }
 
   #[test]
-  fn test_mlkem_invalid_public_key_length() {
-    let invalid_key = vec![0u8; 100].into();
-    assert!(mlkem768_encapsulate(invalid_key).is_err());
+  fn test_mlkem_keypair_rotate() {
+    let mut keypair = MlKemKeyPair::new().unwrap();
+    let old_public = keypair.public_key();
+    let to_old = mlkem768_encapsulate(old_public.clone()).unwrap();
+    assert_eq!(keypair.decapsulate(to_old.ciphertext.clone()).unwrap().as_ref(), to_old.shared_secret.as_ref());
+
+    keypair.rotate().unwrap();
+
+    assert_ne!(old_public.as_ref(), keypair.public_key().as_ref());
+    // The old secret key is gone: its ciphertexts no longer decapsulate to the same secret
+    assert_ne!(keypair.decapsulate(to_old.ciphertext).unwrap().as_ref(), to_old.shared_secret.as_ref());
+
+    let encapsulated = mlkem768_encapsulate(keypair.public_key()).unwrap();
+    let decapsulated = keypair.decapsulate(encapsulated.ciphertext).unwrap();
+    assert_eq!(encapsulated.shared_secret.as_ref(), decapsulated.as_ref());
   }
 
   #[test]
-  fn test_mlkem_invalid_secret_key_length() {
-    let invalid_ciphertext = vec![0u8; 1088].into();
-    let invalid_key = vec![0u8; 100].into();
-    assert!(mlkem768_decapsulate(invalid_ciphertext, invalid_key).is_err());
+  fn test_mlkem768_decapsulate_at() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let encapsulated = mlkem768_encapsulate(keypair.public_key).unwrap();
+
+    let mut framed = vec![0xaa; 37];
+    framed.extend_from_slice(&encapsulated.ciphertext);
+    framed.extend_from_slice(&[0xbb; 11]);
+    let framed: Buffer = framed.into();
+
+    let recovered = mlkem768_decapsulate_at(framed.clone(), 37, keypair.secret_key.clone()).unwrap();
+    assert_eq!(recovered.as_ref(), encapsulated.shared_secret.as_ref());
+
+    let shifted = mlkem768_decapsulate_at(framed.clone(), 36, keypair.secret_key.clone()).unwrap();
+    assert_ne!(shifted.as_ref(), encapsulated.shared_secret.as_ref());
+
+    assert!(mlkem768_decapsulate_at(framed.clone(), 49, keypair.secret_key.clone()).is_err());
+    assert!(mlkem768_decapsulate_at(framed, u32::MAX, keypair.secret_key).is_err());
+  }
+
+  #[test]
+  fn test_encapsulated_secret_builder() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let encapsulated = mlkem768_encapsulate(keypair.public_key.clone()).unwrap();
+    let ciphertext = encapsulated.ciphertext.as_ref();
+
+    let mut builder = EncapsulatedSecretBuilder::new();
+    for fragment in [&ciphertext[..100], &ciphertext[100..700], &ciphertext[700..]] {
+      builder.push(fragment.to_vec().into()).unwrap();
+    }
+    let assembled = builder.build().unwrap();
+    let recovered = mlkem768_decapsulate(assembled, keypair.secret_key).unwrap();
+    assert_eq!(recovered.as_ref(), encapsulated.shared_secret.as_ref());
+
+    // Overflow is rejected without consuming the fragment
+    assert!(builder.push(vec![0u8; 1].into()).is_err());
+    assert_eq!(builder.length(), 1088);
+
+    let mut short = EncapsulatedSecretBuilder::new();
+    short.push(ciphertext[..1000].to_vec().into()).unwrap();
+    assert!(short.build().is_err());
   }
 
   // ML-DSA Tests
@@ -367,6 +2691,295 @@ mod tests {
     assert!(!is_invalid, "Tampered message must fail verification");
   }
 
+  #[test]
+  fn test_mldsa_sign_enforces_max_direct_sign_bytes() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    assert_eq!(max_direct_sign_bytes(), DEFAULT_MAX_DIRECT_SIGN_BYTES);
+
+    let at_limit = vec![1u8; DEFAULT_MAX_DIRECT_SIGN_BYTES as usize];
+    let signature = mldsa65_sign(at_limit.clone().into(), keypair.secret_key.clone()).unwrap();
+    assert!(mldsa65_verify(at_limit.into(), signature, keypair.public_key.clone()).unwrap());
+
+    let over_limit = vec![1u8; DEFAULT_MAX_DIRECT_SIGN_BYTES as usize + 1];
+    let err = mldsa65_sign(over_limit.clone().into(), keypair.secret_key.clone()).err().unwrap();
+    assert!(err.reason.contains("exceeds the 1048576 byte limit"));
+    assert!(err.reason.contains("pre-hash"));
+    assert!(mldsa65_sign_stream(over_limit.clone().into(), keypair.secret_key.clone()).is_ok());
+
+    // Only raise the limit here so concurrently running tests are unaffected
+    set_max_direct_sign_bytes(2 * DEFAULT_MAX_DIRECT_SIGN_BYTES);
+    let result = mldsa65_sign(over_limit.into(), keypair.secret_key);
+    set_max_direct_sign_bytes(DEFAULT_MAX_DIRECT_SIGN_BYTES);
+    assert!(result.is_ok());
+  }
+
+  #[test]
+  fn test_mldsa_verify_verbose() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let message: Buffer = b"report".to_vec().into();
+    let signature = mldsa65_sign(message.clone(), keypair.secret_key).unwrap();
+
+    let ok = mldsa65_verify_verbose(message.clone(), signature.clone(), keypair.public_key.clone());
+    assert!(ok.valid);
+    assert!(ok.reason.is_none());
+
+    let reason = |r: VerifyReport| (r.valid, r.reason.unwrap());
+    assert_eq!(
+      reason(mldsa65_verify_verbose(message.clone(), signature.clone(), vec![0u8; 10].into())),
+      (false, "bad public key length".to_string())
+    );
+    assert_eq!(
+      reason(mldsa65_verify_verbose(message.clone(), signature[..100].to_vec().into(), keypair.public_key.clone())),
+      (false, "bad signature length".to_string())
+    );
+    assert_eq!(
+      reason(mldsa65_verify_verbose(b"other".to_vec().into(), signature, keypair.public_key)),
+      (false, "signature does not verify".to_string())
+    );
+  }
+
+  #[test]
+  fn test_signature_binds_message() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let message: Buffer = b"invoice 42".to_vec().into();
+    let signature = mldsa65_sign(message.clone(), keypair.secret_key).unwrap();
+    let diagnose = |m: &Buffer, s: &Buffer, k: Buffer| signature_binds_message(m.clone(), s.clone(), k).diagnosis.unwrap();
+
+    let bound = signature_binds_message(message.clone(), signature.clone(), keypair.public_key.clone());
+    assert!(bound.valid);
+    assert_eq!(bound.diagnosis.as_deref(), Some("bound"));
+
+    let substituted = signature_binds_message(b"invoice 43".to_vec().into(), signature.clone(), keypair.public_key.clone());
+    assert!(!substituted.valid);
+    assert_eq!(substituted.reason.as_deref(), Some("signature does not verify"));
+    assert_eq!(substituted.diagnosis.as_deref(), Some("message_or_key_mismatch"));
+
+    // Handing over the signer's ML-KEM key instead of its ML-DSA key
+    let kem_key = mlkem768_generate_keypair().unwrap().public_key;
+    assert_eq!(diagnose(&message, &signature, kem_key), "key_mismatch");
+    assert_eq!(diagnose(&message, &signature, vec![0u8; 1952].into()), "key_mismatch");
+
+    // A different genuine ML-DSA-65 key is indistinguishable from a substituted message
+    let other_key = mldsa65_generate_keypair().unwrap().public_key;
+    assert_eq!(diagnose(&message, &signature, other_key), "message_or_key_mismatch");
+
+    assert_eq!(diagnose(&message, &vec![0u8; 3309].into(), keypair.public_key.clone()), "corrupt_signature");
+    assert_eq!(diagnose(&message, &signature[..3000].to_vec().into(), keypair.public_key), "corrupt_signature");
+  }
+
+  #[test]
+  fn test_mldsa_keygen_not_stubbed() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    assert!(keypair.public_key.iter().any(|&b| b != 0), "Public key must not be the all-zero stub");
+    assert!(keypair.secret_key.iter().any(|&b| b != 0), "Secret key must not be the all-zero stub");
+  }
+
+  #[test]
+  fn test_mldsa_verify_rejects_zero_signature() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let is_valid = mldsa65_verify(
+      b"stub regression".to_vec().into(),
+      vec![0u8; 3309].into(),
+      keypair.public_key
+    ).unwrap();
+
+    assert!(!is_valid, "All-zero signature must never verify");
+  }
+
+  #[test]
+  fn test_stream_signer() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let message: Vec<u8> = (0..5000u32).map(|i| i as u8).collect();
+
+    let mut signer = StreamSigner::new();
+    for chunk in message.chunks(777) {
+      signer.update(chunk.to_vec().into());
+    }
+    let chunked = signer.finalize_signature(keypair.secret_key.clone()).unwrap();
+    let whole = mldsa65_sign_stream(message.clone().into(), keypair.secret_key.clone()).unwrap();
+
+    for signature in [chunked, whole] {
+      assert!(mldsa65_verify_stream(message.clone().into(), signature.clone(), keypair.public_key.clone()).unwrap());
+
+      let mut verifier = StreamVerifier::new();
+      for chunk in message.chunks(1000) {
+        verifier.update(chunk.to_vec().into());
+      }
+      assert!(verifier.verify(signature.clone(), keypair.public_key.clone()).unwrap());
+
+      // Stream signatures are not plain signatures over the message
+      assert!(!mldsa65_verify(message.clone().into(), signature.clone(), keypair.public_key.clone()).unwrap());
+      assert!(!mldsa65_verify_stream(message[1..].to_vec().into(), signature, keypair.public_key.clone()).unwrap());
+    }
+  }
+
+  #[test]
+  fn test_mldsa_sign_multi() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let messages: Vec<Buffer> = vec![b"first".to_vec().into(), b"second".to_vec().into()];
+
+    let signature = mldsa65_sign_multi(messages.clone(), keypair.secret_key.clone()).unwrap();
+    assert_eq!(signature.len(), 3309);
+    assert!(mldsa65_verify_multi(messages.clone(), signature.clone(), keypair.public_key.clone()).unwrap());
+
+    let reordered: Vec<Buffer> = messages.into_iter().rev().collect();
+    assert!(!mldsa65_verify_multi(reordered, signature.clone(), keypair.public_key.clone()).unwrap());
+
+    // Moving bytes across the message boundary must also fail
+    let reframed: Vec<Buffer> = vec![b"firsts".to_vec().into(), b"econd".to_vec().into()];
+    assert!(!mldsa65_verify_multi(reframed, signature, keypair.public_key).unwrap());
+  }
+
+  #[test]
+  fn test_keypair_hash() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let (public_key, secret_key) = (keypair.public_key.to_vec(), keypair.secret_key.to_vec());
+    let hash_of = |pk: &[u8], sk: &[u8]| {
+      keypair_hash(KeyPair { public_key: pk.to_vec().into(), secret_key: sk.to_vec().into() }).unwrap()
+    };
+
+    let hash = hash_of(&public_key, &secret_key);
+    assert_eq!(hash.len(), 32);
+    assert_eq!(hash.as_ref(), keypair_hash(keypair).unwrap().as_ref());
+
+    let mut flipped_pk = public_key.clone();
+    flipped_pk[0] ^= 1;
+    assert_ne!(hash.as_ref(), hash_of(&flipped_pk, &secret_key).as_ref());
+
+    let mut flipped_sk = secret_key.clone();
+    flipped_sk[2399] ^= 1;
+    assert_ne!(hash.as_ref(), hash_of(&public_key, &flipped_sk).as_ref());
+  }
+
+  #[test]
+  fn test_mldsa_verify_with_expected_fingerprint() {
+    let signer = mldsa65_generate_keypair().unwrap();
+    let other = mldsa65_generate_keypair().unwrap();
+    let pinned = quantum_fingerprint(signer.public_key.clone()).unwrap();
+    let message: Buffer = b"pinned message".to_vec().into();
+    let signature = mldsa65_sign(message.clone(), signer.secret_key.clone()).unwrap();
+
+    assert!(mldsa65_verify_with_expected_fingerprint(message.clone(), signature.clone(), signer.public_key.clone(), pinned.clone()).unwrap());
+
+    let wrong_sig = mldsa65_sign(b"other message".to_vec().into(), signer.secret_key).unwrap();
+    assert!(!mldsa65_verify_with_expected_fingerprint(message.clone(), wrong_sig, signer.public_key, pinned.clone()).unwrap());
+
+    // A valid signature by a different key is rejected by the pin
+    let other_sig = mldsa65_sign(message.clone(), other.secret_key).unwrap();
+    assert!(mldsa65_verify(message.clone(), other_sig.clone(), other.public_key.clone()).unwrap());
+    assert!(!mldsa65_verify_with_expected_fingerprint(message, other_sig, other.public_key, pinned).unwrap());
+  }
+
+  #[test]
+  fn test_all_signatures_valid() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let item = |message: &[u8]| VerifyItem {
+      message: message.to_vec().into(),
+      signature: mldsa65_sign(message.to_vec().into(), keypair.secret_key.clone()).unwrap(),
+      public_key: keypair.public_key.clone(),
+    };
+
+    assert!(all_signatures_valid(vec![item(b"one"), item(b"two"), item(b"three")]));
+    assert!(all_signatures_valid(Vec::new()));
+
+    // A bad first item must not stop the later items from being checked
+    let mut forged = item(b"one");
+    forged.message = b"forged".to_vec().into();
+    let results = verify_each(vec![forged, item(b"two"), item(b"three")]);
+    assert_eq!(results, vec![false, true, true]);
+
+    let mut forged = item(b"one");
+    forged.message = b"forged".to_vec().into();
+    assert!(!all_signatures_valid(vec![forged, item(b"two"), item(b"three")]));
+
+    let mut malformed = item(b"two");
+    malformed.public_key = vec![0u8; 10].into();
+    assert!(!all_signatures_valid(vec![item(b"one"), malformed]));
+  }
+
+  // X25519 Tests
+  #[test]
+  fn test_x25519_shared_secret() {
+    let alice = x25519_generate_keypair().unwrap();
+    let bob = x25519_generate_keypair().unwrap();
+    assert_eq!(alice.public_key.len(), 32);
+    assert_eq!(alice.secret_key.len(), 32);
+
+    let alice_ss = x25519_diffie_hellman(alice.secret_key.clone(), bob.public_key.clone()).unwrap();
+    let bob_ss = x25519_diffie_hellman(bob.secret_key, alice.public_key).unwrap();
+    assert_eq!(alice_ss.as_ref(), bob_ss.as_ref());
+
+    // The all-zero point has low order
+    assert!(x25519_diffie_hellman(alice.secret_key.clone(), vec![0u8; 32].into()).is_err());
+    assert!(x25519_diffie_hellman(alice.secret_key, vec![9u8; 31].into()).is_err());
+  }
+
+  // Algorithm Dispatch Tests
+  #[test]
+  fn test_generate_keypair_dispatch() {
+    let expected = [
+      (Algorithm::MlKem512, Some((800, 1632))),
+      (Algorithm::MlKem768, Some((1184, 2400))),
+      (Algorithm::MlDsa65, Some((1952, 4032))),
+      (Algorithm::Blake3, None),
+      (Algorithm::Sha3_256, None),
+    ];
+
+    for (algorithm, sizes) in expected {
+      match (generate_keypair(algorithm), sizes) {
+        (Ok(kp), Some((pk, sk))) => {
+          assert_eq!(kp.public_key.len(), pk);
+          assert_eq!(kp.secret_key.len(), sk);
+        }
+        (Err(_), None) => {}
+        _ => panic!("unexpected dispatch result for {}", algorithm.as_str()),
+      }
+    }
+  }
+
+  #[test]
+  fn test_supported_algorithms() {
+    let statuses = supported_algorithms();
+    assert_eq!(statuses.len(), Algorithm::ALL.len());
+
+    let find = |name: &str| statuses.iter().find(|s| s.name == name).unwrap();
+    assert_eq!(find("ml-kem-768").kind, "kem");
+    assert!(find("ml-kem-768").ready);
+
+    // ML-DSA is no longer a stub: it signs and verifies for real
+    let mldsa = find("ml-dsa-65");
+    assert_eq!(mldsa.kind, "signature");
+    assert!(mldsa.ready);
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let signature = mldsa65_sign(b"ready".to_vec().into(), keypair.secret_key).unwrap();
+    assert!(mldsa65_verify(b"ready".to_vec().into(), signature, keypair.public_key).unwrap());
+
+    assert_eq!(find("sha3-256").kind, "hash");
+  }
+
+  #[test]
+  fn test_estimated_memory() {
+    assert!(estimated_memory(Algorithm::MlKem768).unwrap() >= 1184 + 2400);
+
+    // The estimate must cover the keys generate_keypair actually returns
+    for algorithm in [Algorithm::MlKem512, Algorithm::MlKem768, Algorithm::MlDsa65] {
+      let kp = generate_keypair(algorithm).unwrap();
+      let key_bytes = (kp.public_key.len() + kp.secret_key.len()) as u32;
+      assert!(estimated_memory(algorithm).unwrap() > key_bytes);
+    }
+    assert!(estimated_memory(Algorithm::Blake3).is_err());
+  }
+
+  #[test]
+  fn test_algorithm_from_str() {
+    for algorithm in Algorithm::ALL {
+      let parsed: Algorithm = algorithm.as_str().parse().unwrap();
+      assert_eq!(parsed.as_str(), algorithm.as_str());
+    }
+    assert!("md5".parse::<Algorithm>().is_err());
+    assert!("MlKem768".parse::<Algorithm>().is_err());
+  }
+
   // BLAKE3 Tests
   #[test]
   fn test_blake3() {
@@ -375,6 +2988,73 @@ mod tests {
     assert_eq!(hash.len(), 32);
   }
 
+  #[test]
+  fn test_quantum_fingerprint_domain() {
+    let data: Buffer = b"shared payload".to_vec().into();
+    let gossip = quantum_fingerprint_domain(data.clone(), "gossip".to_string()).unwrap();
+    let storage = quantum_fingerprint_domain(data.clone(), "storage".to_string()).unwrap();
+
+    assert!(gossip.starts_with("qf:gossip:"));
+    assert_ne!(gossip.rsplit(':').next(), storage.rsplit(':').next());
+    assert_ne!(gossip, quantum_fingerprint(data.clone()).unwrap());
+
+    assert!(verify_fingerprint_domain(data.clone(), "gossip".to_string(), gossip.clone()).unwrap());
+    assert!(!verify_fingerprint_domain(data.clone(), "storage".to_string(), gossip).unwrap());
+
+    for bad in ["", "sk", "s3", "Gossip", "a:b"] {
+      assert!(quantum_fingerprint_domain(data.clone(), bad.to_string()).is_err());
+    }
+  }
+
+  #[test]
+  fn test_composite_hash() {
+    let data: Buffer = b"content".to_vec().into();
+    let composite = composite_hash(data.clone()).unwrap();
+    assert_eq!(composite.len(), 64);
+    assert_eq!(&composite[..32], blake3_hash(data.clone()).unwrap().as_ref());
+    assert_eq!(&composite[32..], sha3_256_hash(data.clone()).unwrap().as_ref());
+
+    assert!(verify_composite_hash(data.clone(), composite.clone()).unwrap());
+    for index in [0, 63] {
+      let mut tampered = composite.to_vec();
+      tampered[index] ^= 1;
+      assert!(!verify_composite_hash(data.clone(), tampered.into()).unwrap());
+    }
+    assert!(!verify_composite_hash(data, composite[..32].to_vec().into()).unwrap());
+  }
+
+  #[test]
+  fn test_blake3_hash_into() {
+    let data: Buffer = b"reused output".to_vec().into();
+    let out: Buffer = vec![0u8; 32].into();
+
+    // `out` shares memory with its clone, as a JS Buffer would
+    blake3_hash_into(data.clone(), out.clone()).unwrap();
+    assert_eq!(out.as_ref(), blake3_hash(data.clone()).unwrap().as_ref());
+
+    assert!(blake3_hash_into(data.clone(), vec![0u8; 31].into()).is_err());
+    assert!(blake3_hash_into(data, vec![0u8; 64].into()).is_err());
+  }
+
+  #[test]
+  fn test_blake3_verify_many() {
+    let check = |data: &[u8], expected: Vec<u8>| DigestCheck {
+      data: data.to_vec().into(),
+      expected: expected.into(),
+    };
+    let digest = |data: &[u8]| blake3::hash(data).as_bytes().to_vec();
+
+    let results = blake3_verify_many(vec![
+      check(b"first", digest(b"first")),
+      check(b"second", digest(b"other")),
+      check(b"third", digest(b"third")[..31].to_vec()),
+      check(b"", digest(b"")),
+      check(b"fifth", Vec::new()),
+    ]);
+    assert_eq!(results, vec![true, false, false, true, false]);
+    assert!(blake3_verify_many(Vec::new()).is_empty());
+  }
+
   #[test]
   fn test_blake3_hex() {
     let data = b"test data";
@@ -391,6 +3071,217 @@ mod tests {
     assert_eq!(fingerprint.len(), 67);
   }
 
+  #[test]
+  fn test_quantum_fingerprint_shake() {
+    let data: Buffer = b"fingerprint test".to_vec().into();
+    let short = quantum_fingerprint_shake(data.clone(), 16).unwrap();
+    let long = quantum_fingerprint_shake(data.clone(), 64).unwrap();
+
+    assert!(short.starts_with("qf:sk:"));
+    assert_eq!(short.len(), 6 + 32);
+    assert_eq!(long.len(), 6 + 128);
+    assert_ne!(short, long);
+    assert!(long.starts_with(&short));
+
+    let parsed = parse_fingerprint(long).unwrap();
+    assert_eq!(parsed.algorithm, "shake256");
+    assert_eq!(parsed.digest.len(), 64);
+
+    assert!(quantum_fingerprint_shake(data.clone(), 0).is_err());
+    assert_eq!(quantum_fingerprint_shake(data.clone(), 1024).unwrap().len(), 6 + 2048);
+    assert!(quantum_fingerprint_shake(data, 1025).is_err());
+    assert!(quantum_fingerprint_shake(b"x".to_vec().into(), u32::MAX).is_err());
+
+    assert!(parse_fingerprint(format!("qf:sk:{}", "ab".repeat(1024))).is_ok());
+    assert!(parse_fingerprint(format!("qf:sk:{}", "ab".repeat(1025))).is_err());
+  }
+
+  #[test]
+  fn test_multi_fingerprint() {
+    let data: Buffer = b"fingerprint me".to_vec().into();
+    let names = ["blake3", "sha3-256", "blake3"].map(String::from).to_vec();
+    let fingerprints = multi_fingerprint(data.clone(), names).unwrap();
+
+    assert_eq!(fingerprints[0], quantum_fingerprint(data.clone()).unwrap());
+    assert_eq!(fingerprints[1], quantum_fingerprint_sha3(data.clone()).unwrap());
+    assert_eq!(fingerprints[2], fingerprints[0]);
+    assert_eq!(parse_fingerprint(fingerprints[1].clone()).unwrap().algorithm, "sha3-256");
+
+    let err = multi_fingerprint(data, vec!["blake3".to_string(), "md5".to_string()]).err().unwrap();
+    assert!(err.reason.contains("md5"));
+  }
+
+  #[test]
+  fn test_parse_fingerprint() {
+    let fingerprint = quantum_fingerprint(b"data".to_vec().into()).unwrap();
+    let parsed = parse_fingerprint(fingerprint).unwrap();
+    assert_eq!(parsed.algorithm, "blake3");
+    assert_eq!(parsed.digest.as_ref(), blake3::hash(b"data").as_bytes());
+
+    assert!(parse_fingerprint("xx:abcd".to_string()).is_err());
+    assert!(parse_fingerprint("qf:abcd".to_string()).is_err());
+    assert!(parse_fingerprint("qf:sk:zz".to_string()).is_err());
+  }
+
+  #[test]
+  fn test_verify_secret_matches_fingerprint() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let other = mlkem768_generate_keypair().unwrap();
+    let fingerprint = quantum_fingerprint(keypair.public_key.clone()).unwrap();
+
+    assert!(verify_secret_matches_fingerprint(keypair.secret_key.clone(), fingerprint.clone()).unwrap());
+    assert!(verify_secret_matches_fingerprint(keypair.secret_key.clone(), fingerprint.to_ascii_uppercase()).unwrap());
+    assert!(!verify_secret_matches_fingerprint(other.secret_key, fingerprint.clone()).unwrap());
+
+    // A truncated SHAKE fingerprint would otherwise match 1 in 256 unrelated keys
+    let shake = quantum_fingerprint_shake(keypair.public_key.clone(), 32).unwrap();
+    assert!(verify_secret_matches_fingerprint(keypair.secret_key.clone(), shake.clone()).unwrap());
+    assert!(!verify_secret_matches_fingerprint(keypair.secret_key.clone(), shake[..8].to_string()).unwrap());
+    assert!(!verify_secret_matches_fingerprint(keypair.secret_key.clone(), shake[3..8].to_string()).unwrap());
+
+    let dsa = mldsa65_generate_keypair().unwrap();
+    assert!(verify_secret_matches_fingerprint(dsa.secret_key, fingerprint.clone()).is_err());
+    assert!(verify_secret_matches_fingerprint(vec![0u8; 100].into(), fingerprint).is_err());
+  }
+
+  #[test]
+  fn test_fingerprint_equals_data() {
+    let data: Buffer = b"pinned key".to_vec().into();
+    let fingerprint = quantum_fingerprint(data.clone()).unwrap();
+
+    assert!(fingerprint_equals_data(data.clone(), fingerprint.clone()));
+    assert!(fingerprint_equals_data(data.clone(), fingerprint.to_ascii_uppercase()));
+    assert!(fingerprint_equals_data(data.clone(), format!("  {}\n", &fingerprint[3..])));
+    assert!(fingerprint_equals_data(data.clone(), quantum_fingerprint_sha3(data.clone()).unwrap()));
+    assert!(fingerprint_equals_data(data.clone(), quantum_fingerprint_shake(data.clone(), 32).unwrap()));
+    assert!(fingerprint_equals_data(data.clone(), quantum_fingerprint_shake(data.clone(), 64).unwrap()));
+
+    // Short SHAKE fingerprints are rejected even when they are a correct prefix
+    let short = quantum_fingerprint_shake(data.clone(), 16).unwrap();
+    assert!(!fingerprint_equals_data(data.clone(), short.clone()));
+    assert!(!fingerprint_equals_data(data.clone(), short[3..8].to_string()));
+
+    assert!(!fingerprint_equals_data(b"other key".to_vec().into(), fingerprint.clone()));
+    assert!(!fingerprint_equals_data(data.clone(), fingerprint[..40].to_string()));
+    assert!(!fingerprint_equals_data(data.clone(), "not a fingerprint".to_string()));
+    assert!(!fingerprint_equals_data(data, String::new()));
+  }
+
+  #[test]
+  fn test_commit_public_key_set_order_independent() {
+    let keys: Vec<Buffer> = (0..3).map(|_| mldsa65_generate_keypair().unwrap().public_key).collect();
+    let reversed: Vec<Buffer> = keys.iter().rev().cloned().collect();
+
+    let commitment = commit_public_key_set(keys.clone()).unwrap();
+    assert_eq!(commitment.as_ref(), commit_public_key_set(reversed.clone()).unwrap().as_ref());
+    assert!(verify_set_commitment(reversed, commitment).unwrap());
+  }
+
+  #[test]
+  fn test_verify_set_commitment_detects_changes() {
+    let keys: Vec<Buffer> = (0..3).map(|_| mldsa65_generate_keypair().unwrap().public_key).collect();
+    let commitment = commit_public_key_set(keys.clone()).unwrap();
+
+    let mut added = keys.clone();
+    added.push(mldsa65_generate_keypair().unwrap().public_key);
+    assert!(!verify_set_commitment(added, commitment.clone()).unwrap());
+
+    assert!(!verify_set_commitment(keys[..2].to_vec(), commitment.clone()).unwrap());
+    assert!(!verify_set_commitment(keys, commitment[..16].to_vec().into()).unwrap());
+  }
+
+  #[test]
+  fn test_blake3_hash_xof() {
+    let data = b"xof test".to_vec();
+    let long = blake3_hash_xof(data.clone().into(), 100).unwrap();
+    assert_eq!(long.len(), 100);
+    assert_eq!(&long[..32], blake3_hash(data.clone().into()).unwrap().as_ref());
+
+    assert_eq!(blake3_hash_xof(data.clone().into(), MAX_XOF_OUTPUT_BYTES).unwrap().len(), 65536);
+    assert!(blake3_hash_xof(data.clone().into(), MAX_XOF_OUTPUT_BYTES + 1).is_err());
+    assert!(blake3_hash_xof(data.into(), u32::MAX).is_err());
+  }
+
+  #[test]
+  fn test_blake3_hash_range() {
+    let data: Vec<u8> = (0..64).collect();
+    let hash = blake3_hash_range(data.clone().into(), 8, 16).unwrap();
+    assert_eq!(hash.as_ref(), blake3::hash(&data[8..24]).as_bytes());
+
+    let empty = blake3_hash_range(data.clone().into(), 64, 0).unwrap();
+    assert_eq!(empty.as_ref(), blake3::hash(&[]).as_bytes());
+
+    assert!(blake3_hash_range(data.clone().into(), 60, 8).is_err());
+    assert!(blake3_hash_range(data.into(), u32::MAX, u32::MAX).is_err());
+  }
+
+  #[test]
+  fn test_blake3_keyed_hash() {
+    let key = vec![7u8; 32];
+    let tag = blake3_keyed_hash(key.clone().into(), b"message".to_vec().into()).unwrap();
+    assert_eq!(tag.as_ref(), blake3::keyed_hash(&[7u8; 32], b"message").as_bytes());
+
+    assert!(blake3_keyed_hash(vec![7u8; 16].into(), b"message".to_vec().into()).is_err());
+  }
+
+  #[test]
+  fn test_blake3_verify_mac() {
+    let key = vec![7u8; 32];
+    let tag = blake3_keyed_hash(key.clone().into(), b"message".to_vec().into()).unwrap();
+    assert!(blake3_verify_mac(key.clone().into(), b"message".to_vec().into(), tag.clone()).unwrap());
+
+    let mut tampered = tag.to_vec();
+    tampered[0] ^= 1;
+    assert!(!blake3_verify_mac(key.clone().into(), b"message".to_vec().into(), tampered.into()).unwrap());
+
+    assert!(!blake3_verify_mac(key.into(), b"message".to_vec().into(), tag[..16].to_vec().into()).unwrap());
+  }
+
+  #[test]
+  fn test_empty_digest() {
+    let blake3_empty = empty_digest("blake3".to_string()).unwrap();
+    assert_eq!(blake3_empty.as_ref(), blake3_hash(Vec::new().into()).unwrap().as_ref());
+
+    let sha3_empty = empty_digest("sha3-256".to_string()).unwrap();
+    assert_eq!(sha3_empty.as_ref(), sha3_256_hash(Vec::new().into()).unwrap().as_ref());
+
+    assert!(empty_digest("ml-kem-768".to_string()).is_err());
+    assert!(empty_digest("md5".to_string()).is_err());
+  }
+
+  #[test]
+  fn test_blake3_hash_chunked() {
+    let data: Vec<u8> = (0..10_000u32).map(|i| (i % 253) as u8).collect();
+    let expected = blake3_hash(data.clone().into()).unwrap();
+
+    for chunk_size in [1, 7, 64, 1024, 1025, 4096, 10_000, u32::MAX] {
+      let digest = blake3_hash_chunked(data.clone().into(), chunk_size).unwrap();
+      assert_eq!(digest.as_ref(), expected.as_ref(), "chunk size {}", chunk_size);
+    }
+    assert!(blake3_hash_chunked(data.into(), 0).is_err());
+  }
+
+  #[test]
+  fn test_blake3_derive_key() {
+    let a = blake3_derive_key("context a".to_string(), b"material".to_vec().into()).unwrap();
+    let b = blake3_derive_key("context b".to_string(), b"material".to_vec().into()).unwrap();
+    assert_eq!(a.len(), 32);
+    assert_ne!(a.as_ref(), b.as_ref());
+  }
+
+  #[test]
+  fn test_blake3_hash_with_context() {
+    let data = b"domain separated".to_vec();
+    let a = blake3_hash_with_context(data.clone().into(), "orders".to_string()).unwrap();
+    let a_again = blake3_hash_with_context(data.clone().into(), "orders".to_string()).unwrap();
+    let b = blake3_hash_with_context(data.clone().into(), "payments".to_string()).unwrap();
+
+    assert_eq!(a.len(), 32);
+    assert_eq!(a.as_ref(), a_again.as_ref());
+    assert_ne!(a.as_ref(), b.as_ref());
+    assert_ne!(a.as_ref(), blake3_hash(data.into()).unwrap().as_ref());
+  }
+
   #[test]
   fn test_blake3_consistency() {
     let data = b"consistency test";
@@ -0,0 +1,1242 @@
+//! Binary encodings for keys and encapsulated secrets
+//!
+//! CBOR counterparts to the base64/JSON representations used on the JS side.
+//! Every encoding carries an algorithm tag so field lengths can be validated
+//! on import.
+//!
+//! Detached signature files (`.sig`) and wire-format ciphertexts share a
+//! fixed binary header:
+//! `magic (4) || version (1) || tag length (1) || tag || body`
+//! with magic "QSIG" for signatures and "QKCT" for KEM ciphertexts.
+//!
+//! Attached signatures start with a fixed 13-byte header so a streaming
+//! parser can split signature and message without scanning:
+//! `magic "QATT" (4) || version (1) || signature length (4, LE) ||
+//! message length (4, LE) || signature || message`
+//!
+//! Identity documents are self-signed with the embedded ML-DSA key:
+//! `magic "QIDN" (4) || version (1) || ML-KEM-768 public key (1184) ||
+//! ML-DSA-65 public key (1952) || signature (3309)`, where the signature
+//! covers every byte before it.
+//!
+//! Notarization receipts follow the same pattern:
+//! `magic "QNTR" (4) || version (1) || BLAKE3 digest (32) ||
+//! timestamp ms (8, LE, signed) || signature (3309)`.
+//!
+//! Self-signed certificates bind an ML-DSA-65 key to its own fingerprint
+//! and a validity window:
+//! `magic "QCRT" (4) || version (1) || subject BLAKE3(public key) (32) ||
+//! not before ms (8, LE, signed) || not after ms (8, LE, signed) ||
+//! ML-DSA-65 public key (1952) || signature (3309)`.
+//!
+//! Node addresses are `"qd" || base32(BLAKE3(public key) (32) || checksum (4))`
+//! in lowercase unpadded RFC 4648 base32, where the checksum is the first
+//! four bytes of a domain-separated BLAKE3 hash of the key digest.
+//!
+//! ML-KEM-768 secret keys can also be exported as DER-encoded PKCS#8
+//! `PrivateKeyInfo` (RFC 5958) under `id-alg-ml-kem-768`
+//! (2.16.840.1.101.3.4.4.2) with absent parameters, carrying the 2400-byte
+//! expanded key as the `expandedKey` OCTET STRING choice of the IETF ML-KEM
+//! private key format.
+
+use base64::Engine;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::{keypair_lengths, mldsa65_sign, mldsa65_verify, Algorithm, EncapsulatedSecret, KeyPair};
+
+/// Public keys carried by a verified identity document
+#[napi(object)]
+pub struct IdentityKeys {
+  /// ML-KEM-768 public key (1184 bytes)
+  pub kem_public: Buffer,
+  /// ML-DSA-65 public key (1952 bytes)
+  pub dsa_public: Buffer,
+}
+
+/// Contents of a verified self-signed certificate
+#[napi(object)]
+pub struct SelfSignedCertificate {
+  /// Subject: `quantum_fingerprint` of the public key
+  pub subject: String,
+  /// Subject ML-DSA-65 public key (1952 bytes)
+  pub public_key: Buffer,
+  /// Start of validity, milliseconds since the Unix epoch
+  pub not_before: i64,
+  /// End of validity (inclusive), milliseconds since the Unix epoch
+  pub not_after: i64,
+}
+
+/// Signed contents of a verified notarization receipt
+#[napi(object)]
+pub struct NotarizationRecord {
+  /// BLAKE3 digest of the notarized data (32 bytes)
+  pub digest: Buffer,
+  /// Timestamp in milliseconds since the Unix epoch, as given to `notarize`
+  pub timestamp: i64,
+}
+
+/// Lengths read from an attached signature header
+#[napi(object)]
+pub struct AttachedHeader {
+  /// Signature length in bytes
+  pub sig_len: u32,
+  /// Message length in bytes
+  pub msg_len: u32,
+}
+
+/// zstd frame magic number (little-endian 0xFD2FB528); never a valid CBOR map prefix
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const ZSTD_LEVEL: i32 = 19;
+/// Largest decompressed keypair CBOR accepted; an ML-DSA-65 keypair is ~6 KB
+const MAX_KEYPAIR_CBOR_LEN: usize = 8 * 1024;
+
+const TAGGED_VERSION: u8 = 1;
+const SIG_MAGIC: &[u8; 4] = b"QSIG";
+const SIG_ALG_MLDSA65: &str = "ML-DSA-65";
+const WIRE_CT_MAGIC: &[u8; 4] = b"QKCT";
+const WIRE_ALG_MLKEM768: &str = "ML-KEM-768";
+const ATTACHED_MAGIC: &[u8; 4] = b"QATT";
+const ATTACHED_HEADER_LEN: usize = 13;
+const IDENTITY_MAGIC: &[u8; 4] = b"QIDN";
+const IDENTITY_SIGNED_LEN: usize = 5 + 1184 + 1952;
+const RECEIPT_MAGIC: &[u8; 4] = b"QNTR";
+const RECEIPT_SIGNED_LEN: usize = 5 + 32 + 8;
+const CERT_MAGIC: &[u8; 4] = b"QCRT";
+const CERT_SIGNED_LEN: usize = 5 + 32 + 8 + 8 + 1952;
+const ADDRESS_PREFIX: &str = "qd";
+const ADDRESS_CHECKSUM_CONTEXT: &str = "qudag-napi address checksum v1";
+const OID_MLKEM768: [u8; 9] = [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x04, 0x02];
+
+const DER_INTEGER: u8 = 0x02;
+const DER_OCTET_STRING: u8 = 0x04;
+const DER_OID: u8 = 0x06;
+const DER_SEQUENCE: u8 = 0x30;
+
+/// Map a public/secret key length pair to the algorithm that produces it
+fn keypair_algorithm(public_len: usize, secret_len: usize) -> Option<&'static str> {
+  match (public_len, secret_len) {
+    (1184, 2400) => Some("ML-KEM-768"),
+    (1952, 4032) => Some("ML-DSA-65"),
+    _ => None,
+  }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CborKeyPair {
+  alg: String,
+  #[serde(with = "serde_bytes")]
+  public_key: Vec<u8>,
+  #[serde(with = "serde_bytes")]
+  secret_key: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CborEncapsulatedSecret {
+  alg: String,
+  #[serde(with = "serde_bytes")]
+  ciphertext: Vec<u8>,
+  #[serde(with = "serde_bytes")]
+  shared_secret: Vec<u8>,
+}
+
+fn to_cbor<T: Serialize>(value: &T) -> Result<Buffer> {
+  let mut out = Vec::new();
+  ciborium::into_writer(value, &mut out)
+    .map_err(|e| Error::from_reason(format!("CBOR encoding failed: {}", e)))?;
+  Ok(out.into())
+}
+
+fn from_cbor<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<T> {
+  ciborium::from_reader(bytes)
+    .map_err(|e| Error::from_reason(format!("Invalid CBOR: {}", e)))
+}
+
+/// Serialize a keypair to CBOR
+///
+/// The keypair is encoded as a map with an `alg` tag ("ML-KEM-768" or
+/// "ML-DSA-65") and the public and secret keys as byte strings. With
+/// `compress` set, the CBOR is wrapped in a zstd frame; the zstd magic makes
+/// the blob self-identifying, and `keypair_from_cbor` accepts both forms.
+/// ML-KEM secret keys embed the public key and compress well; if
+/// compression would not shrink the blob, the plain CBOR is returned.
+///
+/// # Arguments
+/// * `kp` - ML-KEM-768 or ML-DSA-65 keypair
+/// * `compress` - Apply zstd compression (default false)
+///
+/// # Returns
+/// CBOR-encoded keypair, optionally zstd-compressed
+#[napi]
+pub fn keypair_to_cbor(kp: KeyPair, compress: Option<bool>) -> Result<Buffer> {
+  let alg = keypair_algorithm(kp.public_key.len(), kp.secret_key.len())
+    .ok_or_else(|| Error::from_reason(format!(
+      "Unrecognized keypair: public key {} bytes, secret key {} bytes",
+      kp.public_key.len(),
+      kp.secret_key.len()
+    )))?;
+
+  let encoded = to_cbor(&CborKeyPair {
+    alg: alg.to_string(),
+    public_key: kp.public_key.to_vec(),
+    secret_key: kp.secret_key.to_vec(),
+  })?;
+
+  if !compress.unwrap_or(false) {
+    return Ok(encoded);
+  }
+  let compressed = zstd::encode_all(encoded.as_ref(), ZSTD_LEVEL)
+    .map_err(|e| Error::from_reason(format!("Compression failed: {}", e)))?;
+  if compressed.len() < encoded.len() {
+    Ok(compressed.into())
+  } else {
+    Ok(encoded)
+  }
+}
+
+/// Deserialize a keypair from CBOR
+///
+/// zstd-compressed blobs are detected by their frame magic and decompressed
+/// transparently, up to 8 KiB of decompressed CBOR.
+///
+/// # Arguments
+/// * `bytes` - CBOR produced by `keypair_to_cbor`
+///
+/// # Returns
+/// KeyPair, after checking both key lengths against the `alg` tag
+#[napi]
+pub fn keypair_from_cbor(bytes: Buffer) -> Result<KeyPair> {
+  let decoded: CborKeyPair = if bytes.starts_with(&ZSTD_MAGIC) {
+    let decompressed = zstd::bulk::decompress(bytes.as_ref(), MAX_KEYPAIR_CBOR_LEN)
+      .map_err(|e| Error::from_reason(format!("Invalid compressed keypair: {}", e)))?;
+    from_cbor(&decompressed)?
+  } else {
+    from_cbor(bytes.as_ref())?
+  };
+
+  if keypair_algorithm(decoded.public_key.len(), decoded.secret_key.len()) != Some(decoded.alg.as_str()) {
+    return Err(Error::from_reason(format!(
+      "Invalid {} keypair: public key {} bytes, secret key {} bytes",
+      decoded.alg,
+      decoded.public_key.len(),
+      decoded.secret_key.len()
+    )));
+  }
+
+  Ok(KeyPair {
+    public_key: decoded.public_key.into(),
+    secret_key: decoded.secret_key.into(),
+  })
+}
+
+/// Serialize an ML-KEM-768 encapsulated secret to CBOR
+///
+/// # Arguments
+/// * `enc` - Encapsulated secret (1088-byte ciphertext, 32-byte shared secret)
+///
+/// # Returns
+/// CBOR-encoded encapsulated secret
+#[napi]
+pub fn encapsulated_secret_to_cbor(enc: EncapsulatedSecret) -> Result<Buffer> {
+  validate_encapsulated_secret(enc.ciphertext.len(), enc.shared_secret.len())?;
+
+  to_cbor(&CborEncapsulatedSecret {
+    alg: "ML-KEM-768".to_string(),
+    ciphertext: enc.ciphertext.to_vec(),
+    shared_secret: enc.shared_secret.to_vec(),
+  })
+}
+
+/// Deserialize an ML-KEM-768 encapsulated secret from CBOR
+///
+/// # Arguments
+/// * `bytes` - CBOR produced by `encapsulated_secret_to_cbor`
+///
+/// # Returns
+/// EncapsulatedSecret, after checking the `alg` tag and field lengths
+#[napi]
+pub fn encapsulated_secret_from_cbor(bytes: Buffer) -> Result<EncapsulatedSecret> {
+  let decoded: CborEncapsulatedSecret = from_cbor(bytes.as_ref())?;
+
+  if decoded.alg != "ML-KEM-768" {
+    return Err(Error::from_reason(format!("Unsupported algorithm: {}", decoded.alg)));
+  }
+  validate_encapsulated_secret(decoded.ciphertext.len(), decoded.shared_secret.len())?;
+
+  Ok(EncapsulatedSecret {
+    ciphertext: decoded.ciphertext.into(),
+    shared_secret: decoded.shared_secret.into(),
+  })
+}
+
+fn validate_encapsulated_secret(ciphertext_len: usize, shared_secret_len: usize) -> Result<()> {
+  if ciphertext_len != 1088 {
+    return Err(Error::from_reason(format!(
+      "Invalid ciphertext length: expected 1088 bytes, got {}",
+      ciphertext_len
+    )));
+  }
+
+  if shared_secret_len != 32 {
+    return Err(Error::from_reason(format!(
+      "Invalid shared secret length: expected 32 bytes, got {}",
+      shared_secret_len
+    )));
+  }
+
+  Ok(())
+}
+
+/// Sign a message and wrap the signature in the `.sig` file format
+///
+/// The message is signed directly, so it is subject to the process-wide
+/// `max_direct_sign_bytes` limit (1 MiB by default).
+///
+/// # Arguments
+/// * `message` - Message to sign
+/// * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+///
+/// # Returns
+/// `QSIG` header tagged "ML-DSA-65" followed by the 3309-byte signature
+#[napi]
+pub fn mldsa65_sign_to_sig_format(message: Buffer, secret_key: Buffer) -> Result<Buffer> {
+  let signature = mldsa65_sign(message, secret_key)?;
+  Ok(write_tagged(SIG_MAGIC, SIG_ALG_MLDSA65, &signature).into())
+}
+
+/// Verify a message against a `.sig` file produced by `mldsa65_sign_to_sig_format`
+///
+/// The header is validated before any verification runs: a blob with the
+/// wrong magic, an unknown version, or an algorithm tag other than
+/// "ML-DSA-65" is an error rather than a failed verification.
+///
+/// # Arguments
+/// * `message` - Signed message
+/// * `sig_blob` - `.sig` file contents
+/// * `public_key` - ML-DSA-65 public key (1952 bytes)
+///
+/// # Returns
+/// true if the signature is valid for the message
+#[napi]
+pub fn mldsa65_verify_sig_format(message: Buffer, sig_blob: Buffer, public_key: Buffer) -> Result<bool> {
+  let signature = parse_tagged(sig_blob.as_ref(), SIG_MAGIC, "signature file", SIG_ALG_MLDSA65)?;
+  mldsa65_verify(message, signature.to_vec().into(), public_key)
+}
+
+/// Sign a message and produce an attached signature (signature + message)
+///
+/// The message is signed directly, so it is subject to the process-wide
+/// `max_direct_sign_bytes` limit (1 MiB by default) and errors beyond it.
+/// The format itself allows messages up to 4 GiB.
+///
+/// # Arguments
+/// * `message` - Message to sign (up to `max_direct_sign_bytes`)
+/// * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+///
+/// # Returns
+/// `QATT` header || signature || message
+#[napi]
+pub fn mldsa65_sign_attached(message: Buffer, secret_key: Buffer) -> Result<Buffer> {
+  let msg_len = u32::try_from(message.len())
+    .map_err(|_| Error::from_reason("Message too large for an attached signature"))?;
+  let signature = mldsa65_sign(message.clone(), secret_key)?;
+
+  let mut out = Vec::with_capacity(ATTACHED_HEADER_LEN + signature.len() + message.len());
+  out.extend_from_slice(ATTACHED_MAGIC);
+  out.push(TAGGED_VERSION);
+  out.extend_from_slice(&(signature.len() as u32).to_le_bytes());
+  out.extend_from_slice(&msg_len.to_le_bytes());
+  out.extend_from_slice(&signature);
+  out.extend_from_slice(&message);
+  Ok(out.into())
+}
+
+/// Read the fixed-size header of an attached signature
+///
+/// Only the first 13 bytes are inspected, so a streaming parser can call
+/// this as soon as the header has arrived and then read exactly
+/// `sig_len + msg_len` more bytes.
+///
+/// # Arguments
+/// * `blob` - Attached signature, or at least its first 13 bytes
+///
+/// # Returns
+/// AttachedHeader with the signature and message lengths
+#[napi]
+pub fn parse_attached_header(blob: Buffer) -> Result<AttachedHeader> {
+  if blob.len() < ATTACHED_HEADER_LEN {
+    return Err(Error::from_reason(format!(
+      "Invalid attached signature: header needs {} bytes, got {}",
+      ATTACHED_HEADER_LEN,
+      blob.len()
+    )));
+  }
+  if &blob[..4] != ATTACHED_MAGIC {
+    return Err(Error::from_reason("Invalid attached signature: missing QATT magic"));
+  }
+  if blob[4] != TAGGED_VERSION {
+    return Err(Error::from_reason(format!(
+      "Unsupported attached signature version: {}",
+      blob[4]
+    )));
+  }
+
+  let read_u32 = |at: usize| u32::from_le_bytes(blob[at..at + 4].try_into().expect("4 bytes"));
+  Ok(AttachedHeader {
+    sig_len: read_u32(5),
+    msg_len: read_u32(9),
+  })
+}
+
+/// Verify an attached signature and return the signed message
+///
+/// # Arguments
+/// * `blob` - Output of `mldsa65_sign_attached`
+/// * `public_key` - ML-DSA-65 public key (1952 bytes)
+///
+/// # Returns
+/// The message, if the signature is valid; errors otherwise
+#[napi]
+pub fn mldsa65_open_attached(blob: Buffer, public_key: Buffer) -> Result<Buffer> {
+  let header = parse_attached_header(blob.clone())?;
+  let sig_end = ATTACHED_HEADER_LEN + header.sig_len as usize;
+  let expected_len = sig_end + header.msg_len as usize;
+  if blob.len() != expected_len {
+    return Err(Error::from_reason(format!(
+      "Invalid attached signature: expected {} bytes, got {}",
+      expected_len,
+      blob.len()
+    )));
+  }
+
+  let signature = blob[ATTACHED_HEADER_LEN..sig_end].to_vec();
+  let message = blob[sig_end..].to_vec();
+  if !mldsa65_verify(message.clone().into(), signature.into(), public_key)? {
+    return Err(Error::from_reason("Signature verification failed"));
+  }
+  Ok(message.into())
+}
+
+/// Build a self-certifying identity document for a node
+///
+/// Packages the node's KEM and signing public keys and signs them with the
+/// signing key, so anyone holding the document can check that whoever
+/// controls `dsa_public` vouches for `kem_public`.
+///
+/// # Arguments
+/// * `kem_public` - ML-KEM-768 public key (1184 bytes)
+/// * `dsa_public` - ML-DSA-65 public key (1952 bytes)
+/// * `dsa_secret` - ML-DSA-65 secret key matching `dsa_public` (4032 bytes)
+///
+/// # Returns
+/// `QIDN` identity document
+#[napi]
+pub fn build_identity(kem_public: Buffer, dsa_public: Buffer, dsa_secret: Buffer) -> Result<Buffer> {
+  if kem_public.len() != 1184 {
+    return Err(Error::from_reason(format!(
+      "Invalid KEM public key length: expected 1184 bytes, got {}",
+      kem_public.len()
+    )));
+  }
+  if dsa_public.len() != 1952 {
+    return Err(Error::from_reason(format!(
+      "Invalid DSA public key length: expected 1952 bytes, got {}",
+      dsa_public.len()
+    )));
+  }
+
+  let mut out = Vec::with_capacity(IDENTITY_SIGNED_LEN + 3309);
+  out.extend_from_slice(IDENTITY_MAGIC);
+  out.push(TAGGED_VERSION);
+  out.extend_from_slice(&kem_public);
+  out.extend_from_slice(&dsa_public);
+
+  let signature = mldsa65_sign(out.clone().into(), dsa_secret)?;
+  // Catch a secret key that doesn't belong to dsa_public before publishing
+  if !mldsa65_verify(out.clone().into(), signature.clone(), dsa_public)? {
+    return Err(Error::from_reason("DSA secret key does not match the DSA public key"));
+  }
+
+  out.extend_from_slice(&signature);
+  Ok(out.into())
+}
+
+/// Verify an identity document's self-signature and return its keys
+///
+/// # Arguments
+/// * `blob` - Output of `build_identity`
+///
+/// # Returns
+/// IdentityKeys, if the self-signature is valid; errors otherwise
+#[napi]
+pub fn verify_identity(blob: Buffer) -> Result<IdentityKeys> {
+  if blob.len() != IDENTITY_SIGNED_LEN + 3309 {
+    return Err(Error::from_reason(format!(
+      "Invalid identity document: expected {} bytes, got {}",
+      IDENTITY_SIGNED_LEN + 3309,
+      blob.len()
+    )));
+  }
+  if &blob[..4] != IDENTITY_MAGIC {
+    return Err(Error::from_reason("Invalid identity document: missing QIDN magic"));
+  }
+  if blob[4] != TAGGED_VERSION {
+    return Err(Error::from_reason(format!("Unsupported identity document version: {}", blob[4])));
+  }
+
+  let (signed, signature) = blob.split_at(IDENTITY_SIGNED_LEN);
+  let kem_public = signed[5..5 + 1184].to_vec();
+  let dsa_public = signed[5 + 1184..].to_vec();
+  if !mldsa65_verify(signed.to_vec().into(), signature.to_vec().into(), dsa_public.clone().into())? {
+    return Err(Error::from_reason("Identity self-signature verification failed"));
+  }
+
+  Ok(IdentityKeys {
+    kem_public: kem_public.into(),
+    dsa_public: dsa_public.into(),
+  })
+}
+
+/// Produce a signed, timestamped receipt for data
+///
+/// The data is hashed with BLAKE3 and the digest and timestamp are signed
+/// together, so the receipt proves the key holder vouched for this exact
+/// content at this time. The timestamp is taken as given; the signer is
+/// trusted to supply an accurate clock. Only the fixed-size receipt is
+/// signed, so `max_direct_sign_bytes` does not limit the size of `data`.
+///
+/// # Arguments
+/// * `data` - Data to notarize
+/// * `dsa_secret` - Notary's ML-DSA-65 secret key (4032 bytes)
+/// * `timestamp_ms` - Milliseconds since the Unix epoch
+///
+/// # Returns
+/// `QNTR` receipt
+#[napi]
+pub fn notarize(data: Buffer, dsa_secret: Buffer, timestamp_ms: i64) -> Result<Buffer> {
+  let mut out = Vec::with_capacity(RECEIPT_SIGNED_LEN + 3309);
+  out.extend_from_slice(RECEIPT_MAGIC);
+  out.push(TAGGED_VERSION);
+  out.extend_from_slice(blake3::hash(data.as_ref()).as_bytes());
+  out.extend_from_slice(&timestamp_ms.to_le_bytes());
+
+  let signature = mldsa65_sign(out.clone().into(), dsa_secret)?;
+  out.extend_from_slice(&signature);
+  Ok(out.into())
+}
+
+/// Verify a receipt from `notarize` and return what it attests to
+///
+/// Compare the returned digest with `blake3_hash` of the data in question.
+///
+/// # Arguments
+/// * `receipt` - Output of `notarize`
+/// * `dsa_public` - Notary's ML-DSA-65 public key (1952 bytes)
+///
+/// # Returns
+/// NotarizationRecord, if the signature is valid; errors otherwise
+#[napi]
+pub fn verify_notarization(receipt: Buffer, dsa_public: Buffer) -> Result<NotarizationRecord> {
+  if receipt.len() != RECEIPT_SIGNED_LEN + 3309 {
+    return Err(Error::from_reason(format!(
+      "Invalid notarization receipt: expected {} bytes, got {}",
+      RECEIPT_SIGNED_LEN + 3309,
+      receipt.len()
+    )));
+  }
+  if &receipt[..4] != RECEIPT_MAGIC {
+    return Err(Error::from_reason("Invalid notarization receipt: missing QNTR magic"));
+  }
+  if receipt[4] != TAGGED_VERSION {
+    return Err(Error::from_reason(format!("Unsupported notarization receipt version: {}", receipt[4])));
+  }
+
+  let (signed, signature) = receipt.split_at(RECEIPT_SIGNED_LEN);
+  if !mldsa65_verify(signed.to_vec().into(), signature.to_vec().into(), dsa_public)? {
+    return Err(Error::from_reason("Notarization signature verification failed"));
+  }
+
+  Ok(NotarizationRecord {
+    digest: signed[5..37].to_vec().into(),
+    timestamp: i64::from_le_bytes(signed[37..45].try_into().expect("slice is 8 bytes")),
+  })
+}
+
+/// Issue a self-signed certificate for an ML-DSA-65 keypair
+///
+/// For bootstrapping a node identity before any CA is involved: the
+/// certificate only proves possession of the secret key for the subject
+/// fingerprint during the validity window.
+///
+/// # Arguments
+/// * `keypair` - ML-DSA-65 keypair (1952-byte public, 4032-byte secret key)
+/// * `not_before_ms` - Start of validity, milliseconds since the Unix epoch
+/// * `not_after_ms` - End of validity (inclusive), not before `not_before_ms`
+///
+/// # Returns
+/// `QCRT` certificate
+#[napi]
+pub fn generate_self_signed(keypair: KeyPair, not_before_ms: i64, not_after_ms: i64) -> Result<Buffer> {
+  if keypair.public_key.len() != 1952 {
+    return Err(Error::from_reason(format!(
+      "Invalid public key length: expected 1952 bytes, got {}",
+      keypair.public_key.len()
+    )));
+  }
+  if not_after_ms < not_before_ms {
+    return Err(Error::from_reason("Invalid validity window: not_after is before not_before"));
+  }
+
+  let mut out = Vec::with_capacity(CERT_SIGNED_LEN + 3309);
+  out.extend_from_slice(CERT_MAGIC);
+  out.push(TAGGED_VERSION);
+  out.extend_from_slice(blake3::hash(&keypair.public_key).as_bytes());
+  out.extend_from_slice(&not_before_ms.to_le_bytes());
+  out.extend_from_slice(&not_after_ms.to_le_bytes());
+  out.extend_from_slice(&keypair.public_key);
+
+  let signature = mldsa65_sign(out.clone().into(), keypair.secret_key)?;
+  if !mldsa65_verify(out.clone().into(), signature.clone(), keypair.public_key)? {
+    return Err(Error::from_reason("Secret key does not match the public key"));
+  }
+  out.extend_from_slice(&signature);
+  Ok(out.into())
+}
+
+/// Verify a certificate from `generate_self_signed`
+///
+/// # Arguments
+/// * `cert` - `QCRT` certificate
+/// * `now_ms` - Optional current time; when given, a certificate outside
+///   its validity window is rejected
+///
+/// # Returns
+/// SelfSignedCertificate with the subject public key, if the self-signature
+/// is valid; errors otherwise
+#[napi]
+pub fn verify_self_signed(cert: Buffer, now_ms: Option<i64>) -> Result<SelfSignedCertificate> {
+  if cert.len() != CERT_SIGNED_LEN + 3309 {
+    return Err(Error::from_reason(format!(
+      "Invalid certificate: expected {} bytes, got {}",
+      CERT_SIGNED_LEN + 3309,
+      cert.len()
+    )));
+  }
+  if &cert[..4] != CERT_MAGIC {
+    return Err(Error::from_reason("Invalid certificate: missing QCRT magic"));
+  }
+  if cert[4] != TAGGED_VERSION {
+    return Err(Error::from_reason(format!("Unsupported certificate version: {}", cert[4])));
+  }
+
+  let (signed, signature) = cert.split_at(CERT_SIGNED_LEN);
+  let public_key = &signed[53..];
+  if &signed[5..37] != blake3::hash(public_key).as_bytes() {
+    return Err(Error::from_reason("Invalid certificate: subject does not match the public key"));
+  }
+  if !mldsa65_verify(signed.to_vec().into(), signature.to_vec().into(), public_key.to_vec().into())? {
+    return Err(Error::from_reason("Certificate self-signature verification failed"));
+  }
+
+  let not_before = i64::from_le_bytes(signed[37..45].try_into().expect("slice is 8 bytes"));
+  let not_after = i64::from_le_bytes(signed[45..53].try_into().expect("slice is 8 bytes"));
+  if let Some(now) = now_ms {
+    if now < not_before || now > not_after {
+      return Err(Error::from_reason(format!(
+        "Certificate not valid at {}: valid from {} to {}",
+        now, not_before, not_after
+      )));
+    }
+  }
+
+  Ok(SelfSignedCertificate {
+    subject: format!("qf:{}", hex::encode(&signed[5..37])),
+    public_key: public_key.to_vec().into(),
+    not_before,
+    not_after,
+  })
+}
+
+/// Encode an ML-KEM-768 encapsulation for transmission
+///
+/// Only the ciphertext is written, behind a `QKCT` header tagged
+/// "ML-KEM-768". The shared secret is deliberately left out: it must stay
+/// with the sender, and the recipient recovers it by decapsulating.
+///
+/// # Arguments
+/// * `enc` - Encapsulated secret from `mlkem768_encapsulate`
+///
+/// # Returns
+/// Wire-format ciphertext
+#[napi]
+pub fn encapsulated_secret_to_wire(enc: EncapsulatedSecret) -> Result<Buffer> {
+  validate_encapsulated_secret(enc.ciphertext.len(), enc.shared_secret.len())?;
+  Ok(write_tagged(WIRE_CT_MAGIC, WIRE_ALG_MLKEM768, &enc.ciphertext).into())
+}
+
+/// Parse a wire-format ciphertext produced by `encapsulated_secret_to_wire`
+///
+/// # Arguments
+/// * `bytes` - Wire-format ciphertext
+///
+/// # Returns
+/// The 1088-byte ML-KEM-768 ciphertext, ready for `mlkem768_decapsulate`
+#[napi]
+pub fn ciphertext_from_wire(bytes: Buffer) -> Result<Buffer> {
+  let ciphertext = parse_tagged(bytes.as_ref(), WIRE_CT_MAGIC, "wire ciphertext", WIRE_ALG_MLKEM768)?;
+  if ciphertext.len() != 1088 {
+    return Err(Error::from_reason(format!(
+      "Invalid ciphertext length: expected 1088 bytes, got {}",
+      ciphertext.len()
+    )));
+  }
+  Ok(ciphertext.to_vec().into())
+}
+
+/// Decode and validate a base64 public key from an environment variable
+///
+/// Accepts standard base64 with padding; surrounding whitespace (such as a
+/// trailing newline from a secrets file) is ignored.
+///
+/// # Arguments
+/// * `b64` - Base64-encoded public key
+/// * `expected_algo` - "ml-kem-512", "ml-kem-768" or "ml-dsa-65"
+///
+/// # Returns
+/// Decoded public key; errors name the algorithm and the lengths involved
+#[napi]
+pub fn load_public_key_env(b64: String, expected_algo: Algorithm) -> Result<Buffer> {
+  let (expected_len, _) = keypair_lengths(expected_algo)?;
+
+  let key = base64::engine::general_purpose::STANDARD
+    .decode(b64.trim())
+    .map_err(|e| Error::from_reason(format!("Invalid base64 public key: {}", e)))?;
+
+  if key.len() != expected_len as usize {
+    return Err(Error::from_reason(format!(
+      "Invalid {} public key length: expected {} bytes, got {}",
+      expected_algo.as_str(),
+      expected_len,
+      key.len()
+    )));
+  }
+
+  Ok(key.into())
+}
+
+/// Derive a human-readable node address from a public key
+///
+/// # Arguments
+/// * `key` - Public key of any algorithm
+///
+/// # Returns
+/// Address string "qd" followed by 58 lowercase base32 characters
+#[napi]
+pub fn public_key_to_address(key: Buffer) -> String {
+  let mut payload = blake3::hash(key.as_ref()).as_bytes().to_vec();
+  payload.extend_from_slice(&address_checksum(&payload));
+
+  format!(
+    "{}{}",
+    ADDRESS_PREFIX,
+    data_encoding::BASE32_NOPAD.encode(&payload).to_ascii_lowercase()
+  )
+}
+
+/// Check the checksum of an address from `public_key_to_address`
+///
+/// Letter case is ignored, so an address read back in uppercase still
+/// validates.
+///
+/// # Arguments
+/// * `addr` - Address string
+///
+/// # Returns
+/// true if the address is well-formed and its checksum matches
+#[napi]
+pub fn validate_address(addr: String) -> bool {
+  let Some(encoded) = addr.strip_prefix(ADDRESS_PREFIX) else {
+    return false;
+  };
+  let Ok(payload) = data_encoding::BASE32_NOPAD.decode(encoded.to_ascii_uppercase().as_bytes()) else {
+    return false;
+  };
+  if payload.len() != 36 {
+    return false;
+  }
+
+  let (digest, checksum) = payload.split_at(32);
+  address_checksum(digest) == checksum
+}
+
+fn address_checksum(digest: &[u8]) -> [u8; 4] {
+  let hash = blake3::derive_key(ADDRESS_CHECKSUM_CONTEXT, digest);
+  [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Export an ML-KEM-768 secret key as PKCS#8
+///
+/// # Arguments
+/// * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+///
+/// # Returns
+/// DER-encoded PKCS#8 PrivateKeyInfo for tools and HSMs that only import PKCS#8
+#[napi]
+pub fn mlkem768_secret_key_to_pkcs8(secret_key: Buffer) -> Result<Buffer> {
+  if secret_key.len() != 2400 {
+    return Err(Error::from_reason(format!(
+      "Invalid secret key length: expected 2400 bytes, got {}",
+      secret_key.len()
+    )));
+  }
+
+  let algorithm = write_der(DER_SEQUENCE, &write_der(DER_OID, &OID_MLKEM768));
+  let private_key = write_der(DER_OCTET_STRING, &write_der(DER_OCTET_STRING, &secret_key));
+
+  let mut info = write_der(DER_INTEGER, &[0]);
+  info.extend_from_slice(&algorithm);
+  info.extend_from_slice(&private_key);
+  Ok(write_der(DER_SEQUENCE, &info).into())
+}
+
+/// Import an ML-KEM-768 secret key from PKCS#8
+///
+/// # Arguments
+/// * `der` - DER-encoded PKCS#8 PrivateKeyInfo from `mlkem768_secret_key_to_pkcs8`
+///
+/// # Returns
+/// The 2400-byte secret key; errors on a foreign algorithm OID, an
+/// unexpected inner key length, or malformed DER
+#[napi]
+pub fn mlkem768_secret_key_from_pkcs8(der: Buffer) -> Result<Buffer> {
+  let (info, trailing) = read_der(der.as_ref(), DER_SEQUENCE)?;
+  if !trailing.is_empty() {
+    return Err(Error::from_reason("Invalid PKCS#8: trailing data after PrivateKeyInfo"));
+  }
+
+  let (version, rest) = read_der(info, DER_INTEGER)?;
+  if version != [0] {
+    return Err(Error::from_reason("Unsupported PKCS#8 version"));
+  }
+
+  let (algorithm, rest) = read_der(rest, DER_SEQUENCE)?;
+  let (oid, parameters) = read_der(algorithm, DER_OID)?;
+  if oid != OID_MLKEM768 || !parameters.is_empty() {
+    return Err(Error::from_reason("Unsupported PKCS#8 algorithm: expected id-alg-ml-kem-768"));
+  }
+
+  // Optional attributes and public key after privateKey are not emitted by
+  // the exporter and are rejected rather than silently dropped
+  let (private_key, rest) = read_der(rest, DER_OCTET_STRING)?;
+  if !rest.is_empty() {
+    return Err(Error::from_reason("Invalid PKCS#8: unexpected fields after privateKey"));
+  }
+
+  let (expanded, rest) = read_der(private_key, DER_OCTET_STRING)?;
+  if !rest.is_empty() || expanded.len() != 2400 {
+    return Err(Error::from_reason(format!(
+      "Invalid secret key length: expected 2400 bytes, got {}",
+      expanded.len()
+    )));
+  }
+
+  Ok(expanded.to_vec().into())
+}
+
+/// DER-encode one TLV with a definite-length header
+fn write_der(tag: u8, content: &[u8]) -> Vec<u8> {
+  let len = content.len();
+  let mut out = Vec::with_capacity(4 + len);
+  out.push(tag);
+  match len {
+    0..=0x7f => out.push(len as u8),
+    0x80..=0xff => out.extend_from_slice(&[0x81, len as u8]),
+    _ => out.extend_from_slice(&[0x82, (len >> 8) as u8, len as u8]),
+  }
+  out.extend_from_slice(content);
+  out
+}
+
+/// Read one DER TLV with the expected tag and return its content and the rest
+fn read_der(input: &[u8], tag: u8) -> Result<(&[u8], &[u8])> {
+  let malformed = || Error::from_reason("Invalid PKCS#8: malformed DER");
+  if input.len() < 2 || input[0] != tag {
+    return Err(malformed());
+  }
+
+  // Minimal definite lengths only, up to two length bytes
+  let (len, header) = match input[1] {
+    len @ 0..=0x7f => (len as usize, 2),
+    0x81 if input.len() >= 3 && input[2] >= 0x80 => (input[2] as usize, 3),
+    0x82 if input.len() >= 4 && input[2] != 0 => (u16::from_be_bytes([input[2], input[3]]) as usize, 4),
+    _ => return Err(malformed()),
+  };
+
+  let content = input.get(header..header + len).ok_or_else(malformed)?;
+  Ok((content, &input[header + len..]))
+}
+
+/// Write `body` behind a `magic || version || tag` header
+fn write_tagged(magic: &[u8; 4], alg: &str, body: &[u8]) -> Vec<u8> {
+  let mut out = Vec::with_capacity(6 + alg.len() + body.len());
+  out.extend_from_slice(magic);
+  out.push(TAGGED_VERSION);
+  out.push(alg.len() as u8);
+  out.extend_from_slice(alg.as_bytes());
+  out.extend_from_slice(body);
+  out
+}
+
+/// Check a tagged header against `magic` and `expected_alg` and return the body
+fn parse_tagged<'a>(blob: &'a [u8], magic: &[u8; 4], kind: &str, expected_alg: &str) -> Result<&'a [u8]> {
+  if blob.len() < 6 || &blob[..4] != magic {
+    return Err(Error::from_reason(format!(
+      "Invalid {}: missing {} magic",
+      kind,
+      String::from_utf8_lossy(magic)
+    )));
+  }
+  if blob[4] != TAGGED_VERSION {
+    return Err(Error::from_reason(format!("Unsupported {} version: {}", kind, blob[4])));
+  }
+
+  let tag_end = 6 + blob[5] as usize;
+  let tag = blob
+    .get(6..tag_end)
+    .ok_or_else(|| Error::from_reason(format!("Invalid {}: truncated algorithm tag", kind)))?;
+  if tag != expected_alg.as_bytes() {
+    return Err(Error::from_reason(format!(
+      "Unsupported {} algorithm: {}",
+      kind,
+      String::from_utf8_lossy(tag)
+    )));
+  }
+
+  Ok(&blob[tag_end..])
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::crypto::*;
+
+  #[test]
+  fn test_keypair_cbor_roundtrip() {
+    for keypair in [mlkem768_generate_keypair().unwrap(), mldsa65_generate_keypair().unwrap()] {
+      let encoded = keypair_to_cbor(KeyPair {
+        public_key: keypair.public_key.clone(),
+        secret_key: keypair.secret_key.clone(),
+      }, None).unwrap();
+      let decoded = keypair_from_cbor(encoded).unwrap();
+
+      assert_eq!(decoded.public_key.as_ref(), keypair.public_key.as_ref());
+      assert_eq!(decoded.secret_key.as_ref(), keypair.secret_key.as_ref());
+    }
+  }
+
+  #[test]
+  fn test_keypair_cbor_compressed_roundtrip() {
+    for keypair in [mlkem768_generate_keypair().unwrap(), mldsa65_generate_keypair().unwrap()] {
+      let copy = || KeyPair {
+        public_key: keypair.public_key.clone(),
+        secret_key: keypair.secret_key.clone(),
+      };
+      let plain = keypair_to_cbor(copy(), Some(false)).unwrap();
+      let compressed = keypair_to_cbor(copy(), Some(true)).unwrap();
+      assert!(compressed.len() <= plain.len());
+
+      for blob in [plain, compressed] {
+        let decoded = keypair_from_cbor(blob).unwrap();
+        assert_eq!(decoded.public_key.as_ref(), keypair.public_key.as_ref());
+        assert_eq!(decoded.secret_key.as_ref(), keypair.secret_key.as_ref());
+      }
+    }
+
+    // The ML-KEM secret key repeats the public key, so zstd always wins
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let compressed = keypair_to_cbor(keypair, Some(true)).unwrap();
+    assert!(compressed.starts_with(&ZSTD_MAGIC));
+  }
+
+  #[test]
+  fn test_keypair_cbor_rejects_oversized_frame() {
+    let oversized = zstd::encode_all(vec![0u8; MAX_KEYPAIR_CBOR_LEN + 1].as_slice(), ZSTD_LEVEL).unwrap();
+    assert!(oversized.len() < 64);
+
+    let err = keypair_from_cbor(oversized.into()).err().unwrap();
+    assert!(err.reason.starts_with("Invalid compressed keypair"));
+  }
+
+  #[test]
+  fn test_encapsulated_secret_cbor_roundtrip() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let enc = mlkem768_encapsulate(keypair.public_key).unwrap();
+
+    let encoded = encapsulated_secret_to_cbor(EncapsulatedSecret {
+      ciphertext: enc.ciphertext.clone(),
+      shared_secret: enc.shared_secret.clone(),
+    }).unwrap();
+    let decoded = encapsulated_secret_from_cbor(encoded).unwrap();
+
+    assert_eq!(decoded.ciphertext.as_ref(), enc.ciphertext.as_ref());
+    assert_eq!(decoded.shared_secret.as_ref(), enc.shared_secret.as_ref());
+  }
+
+  #[test]
+  fn test_keypair_cbor_rejects_wrong_length_public_key() {
+    let forged = to_cbor(&CborKeyPair {
+      alg: "ML-KEM-768".to_string(),
+      public_key: vec![0u8; 1183],
+      secret_key: vec![0u8; 2400],
+    }).unwrap();
+
+    assert!(keypair_from_cbor(forged).is_err());
+  }
+
+  #[test]
+  fn test_keypair_cbor_rejects_garbage() {
+    assert!(keypair_from_cbor(vec![0xff, 0x00, 0x01].into()).is_err());
+  }
+
+  #[test]
+  fn test_sig_format_roundtrip() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let message = b"release-1.0.tar.gz".to_vec();
+
+    let blob = mldsa65_sign_to_sig_format(message.clone().into(), keypair.secret_key).unwrap();
+    assert_eq!(&blob[..4], b"QSIG");
+    assert_eq!(blob.len(), 6 + 9 + 3309);
+
+    assert!(mldsa65_verify_sig_format(message.into(), blob.clone(), keypair.public_key.clone()).unwrap());
+    assert!(!mldsa65_verify_sig_format(b"other".to_vec().into(), blob, keypair.public_key).unwrap());
+  }
+
+  #[test]
+  fn test_sig_format_rejects_malformed_header() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let message: Buffer = b"message".to_vec().into();
+    let blob = mldsa65_sign_to_sig_format(message.clone(), keypair.secret_key).unwrap().to_vec();
+
+    let mut bad_magic = blob.clone();
+    bad_magic[0] = b'X';
+    assert!(mldsa65_verify_sig_format(message.clone(), bad_magic.into(), keypair.public_key.clone()).is_err());
+
+    let mut bad_alg = blob.clone();
+    bad_alg[6..15].copy_from_slice(b"ML-DSA-87");
+    assert!(mldsa65_verify_sig_format(message.clone(), bad_alg.into(), keypair.public_key.clone()).is_err());
+
+    let mut bad_version = blob;
+    bad_version[4] = 9;
+    assert!(mldsa65_verify_sig_format(message.clone(), bad_version.into(), keypair.public_key.clone()).is_err());
+
+    assert!(mldsa65_verify_sig_format(message, b"QSIG".to_vec().into(), keypair.public_key).is_err());
+  }
+
+  #[test]
+  fn test_wire_format_excludes_shared_secret() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let enc = mlkem768_encapsulate(keypair.public_key).unwrap();
+    let shared_secret = enc.shared_secret.to_vec();
+    let ciphertext = enc.ciphertext.to_vec();
+
+    let wire = encapsulated_secret_to_wire(enc).unwrap();
+    assert_eq!(&wire[..4], b"QKCT");
+    assert_eq!(wire.len(), 6 + 10 + 1088);
+    assert!(!wire.windows(32).any(|w| w == shared_secret.as_slice()));
+
+    let parsed = ciphertext_from_wire(wire.clone()).unwrap();
+    assert_eq!(parsed.as_ref(), ciphertext.as_slice());
+    let recovered = mlkem768_decapsulate(parsed, keypair.secret_key).unwrap();
+    assert_eq!(recovered.as_ref(), shared_secret.as_slice());
+
+    // A signature file is not a wire ciphertext
+    let mut wrong_magic = wire.to_vec();
+    wrong_magic[..4].copy_from_slice(b"QSIG");
+    assert!(ciphertext_from_wire(wrong_magic.into()).is_err());
+    assert!(ciphertext_from_wire(wire[..100].to_vec().into()).is_err());
+  }
+
+  #[test]
+  fn test_attached_signature_header() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let message = b"attached payload".to_vec();
+    let blob = mldsa65_sign_attached(message.clone().into(), keypair.secret_key).unwrap();
+
+    let header = parse_attached_header(blob[..ATTACHED_HEADER_LEN].to_vec().into()).unwrap();
+    assert_eq!(header.sig_len, 3309);
+    assert_eq!(header.msg_len, message.len() as u32);
+    assert_eq!(blob.len(), ATTACHED_HEADER_LEN + 3309 + message.len());
+
+    let opened = mldsa65_open_attached(blob.clone(), keypair.public_key.clone()).unwrap();
+    assert_eq!(opened.as_ref(), message.as_slice());
+
+    assert!(parse_attached_header(blob[..10].to_vec().into()).is_err());
+    assert!(mldsa65_open_attached(blob[..blob.len() - 1].to_vec().into(), keypair.public_key.clone()).is_err());
+
+    let mut tampered = blob.to_vec();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(mldsa65_open_attached(tampered.into(), keypair.public_key).is_err());
+  }
+
+  #[test]
+  fn test_attached_signature_over_sign_limit() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    // Above the default limit even while another test has it raised to 2 MiB
+    let message = vec![0u8; 4 * 1024 * 1024];
+
+    let err = mldsa65_sign_attached(message.clone().into(), keypair.secret_key.clone()).err().unwrap();
+    assert!(err.reason.contains("Message too large for direct signing"));
+    assert!(err.reason.contains("pre-hash"));
+    assert!(mldsa65_sign_to_sig_format(message.into(), keypair.secret_key).is_err());
+  }
+
+  #[test]
+  fn test_load_public_key_env() {
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let public_key = mlkem768_generate_keypair().unwrap().public_key;
+
+    let loaded = load_public_key_env(format!("{}\n", b64.encode(&public_key)), Algorithm::MlKem768).unwrap();
+    assert_eq!(loaded.as_ref(), public_key.as_ref());
+
+    let err = load_public_key_env(b64.encode(&public_key), Algorithm::MlDsa65).err().unwrap();
+    assert!(err.reason.contains("expected 1952 bytes, got 1184"));
+
+    let err = load_public_key_env("not base64!".to_string(), Algorithm::MlKem768).err().unwrap();
+    assert!(err.reason.contains("Invalid base64"));
+
+    assert!(load_public_key_env(b64.encode(&public_key), Algorithm::Blake3).is_err());
+  }
+
+  #[test]
+  fn test_identity_roundtrip_and_tamper() {
+    let kem = mlkem768_generate_keypair().unwrap();
+    let dsa = mldsa65_generate_keypair().unwrap();
+    let blob = build_identity(kem.public_key.clone(), dsa.public_key.clone(), dsa.secret_key.clone()).unwrap();
+
+    let keys = verify_identity(blob.clone()).unwrap();
+    assert_eq!(keys.kem_public.as_ref(), kem.public_key.as_ref());
+    assert_eq!(keys.dsa_public.as_ref(), dsa.public_key.as_ref());
+
+    // Swapping in another KEM key breaks the self-signature
+    let mut tampered = blob.to_vec();
+    tampered[5 + 100] ^= 1;
+    assert!(verify_identity(tampered.into()).is_err());
+
+    let other = mldsa65_generate_keypair().unwrap();
+    assert!(build_identity(kem.public_key.clone(), dsa.public_key, other.secret_key).is_err());
+    assert!(verify_identity(blob[..blob.len() - 1].to_vec().into()).is_err());
+  }
+
+  #[test]
+  fn test_notarize_roundtrip_and_tamper() {
+    let notary = mldsa65_generate_keypair().unwrap();
+    let data: Buffer = b"contract v3".to_vec().into();
+    let timestamp = 1_760_000_000_000i64;
+
+    let receipt = notarize(data.clone(), notary.secret_key, timestamp).unwrap();
+    let record = verify_notarization(receipt.clone(), notary.public_key.clone()).unwrap();
+    assert_eq!(record.digest.as_ref(), blake3_hash(data).unwrap().as_ref());
+    assert_eq!(record.timestamp, timestamp);
+
+    // Backdating the receipt breaks the signature
+    let mut backdated = receipt.to_vec();
+    backdated[37..45].copy_from_slice(&(timestamp - 86_400_000).to_le_bytes());
+    assert!(verify_notarization(backdated.into(), notary.public_key.clone()).is_err());
+
+    let other = mldsa65_generate_keypair().unwrap();
+    assert!(verify_notarization(receipt, other.public_key).is_err());
+  }
+
+  #[test]
+  fn test_self_signed_roundtrip() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let (not_before, not_after) = (1_760_000_000_000i64, 1_790_000_000_000i64);
+    let cert = generate_self_signed(KeyPair {
+      public_key: keypair.public_key.clone(),
+      secret_key: keypair.secret_key.clone(),
+    }, not_before, not_after).unwrap();
+
+    let verified = verify_self_signed(cert.clone(), None).unwrap();
+    assert_eq!(verified.subject, quantum_fingerprint(keypair.public_key.clone()).unwrap());
+    assert_eq!(verified.public_key.as_ref(), keypair.public_key.as_ref());
+    assert_eq!((verified.not_before, verified.not_after), (not_before, not_after));
+
+    assert!(verify_self_signed(cert.clone(), Some(not_before)).is_ok());
+    assert!(verify_self_signed(cert.clone(), Some(not_after + 1)).is_err());
+
+    let other = mldsa65_generate_keypair().unwrap();
+    assert!(generate_self_signed(KeyPair {
+      public_key: other.public_key,
+      secret_key: keypair.secret_key,
+    }, not_before, not_after).is_err());
+  }
+
+  #[test]
+  fn test_self_signed_rejects_tampered_validity() {
+    let keypair = mldsa65_generate_keypair().unwrap();
+    let cert = generate_self_signed(keypair, 0, 1_000).unwrap();
+
+    // Extending the validity window breaks the self-signature
+    let mut extended = cert.to_vec();
+    extended[45..53].copy_from_slice(&i64::MAX.to_le_bytes());
+    let err = verify_self_signed(extended.into(), None).err().unwrap();
+    assert!(err.reason.contains("self-signature"));
+
+    let mut resubjected = cert.to_vec();
+    resubjected[5] ^= 1;
+    assert!(verify_self_signed(resubjected.into(), None).is_err());
+  }
+
+  #[test]
+  fn test_public_key_address() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let address = public_key_to_address(keypair.public_key.clone());
+
+    assert!(address.starts_with("qd"));
+    assert_eq!(address.len(), 60);
+    assert_eq!(address, public_key_to_address(keypair.public_key));
+    assert!(validate_address(address.clone()));
+    assert!(validate_address(address.to_ascii_uppercase().replacen("QD", "qd", 1)));
+
+    // A single mistyped character anywhere breaks the checksum
+    for index in [2, 30, 59] {
+      let mut mutated = address.clone().into_bytes();
+      mutated[index] = if mutated[index] == b'a' { b'b' } else { b'a' };
+      assert!(!validate_address(String::from_utf8(mutated).unwrap()));
+    }
+
+    assert!(!validate_address(address[..58].to_string()));
+    assert!(!validate_address(format!("xx{}", &address[2..])));
+    assert!(!validate_address(String::new()));
+  }
+
+  #[test]
+  fn test_mlkem768_pkcs8_roundtrip() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let der = mlkem768_secret_key_to_pkcs8(keypair.secret_key.clone()).unwrap();
+
+    assert_eq!(der.len(), 2428);
+    assert_eq!(&der[..4], &[0x30, 0x82, 0x09, 0x78]);
+    assert_eq!(&der[11..20], &OID_MLKEM768);
+
+    let imported = mlkem768_secret_key_from_pkcs8(der.clone()).unwrap();
+    assert_eq!(imported.as_ref(), keypair.secret_key.as_ref());
+
+    assert!(mlkem768_secret_key_to_pkcs8(vec![0u8; 32].into()).is_err());
+    assert!(mlkem768_secret_key_from_pkcs8(der[..der.len() - 1].to_vec().into()).is_err());
+  }
+
+  #[test]
+  fn test_mlkem768_pkcs8_rejects_wrong_oid() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let mut der = mlkem768_secret_key_to_pkcs8(keypair.secret_key).unwrap().to_vec();
+
+    // Rewrite the OID arc to id-ml-dsa-65 (2.16.840.1.101.3.4.3.18)
+    der[18] = 0x03;
+    der[19] = 0x12;
+    let err = mlkem768_secret_key_from_pkcs8(der.into()).err().unwrap();
+    assert!(err.reason.contains("id-alg-ml-kem-768"));
+  }
+}
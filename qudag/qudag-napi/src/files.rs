@@ -0,0 +1,218 @@
+//! Filesystem hashing
+//!
+//! Content-addressing for files and directory trees. Files are streamed
+//! through BLAKE3 rather than read into a single Buffer. File signatures
+//! reuse the `StreamSigner` construction, so signing a file and streaming
+//! its contents through a `StreamSigner` give interchangeable signatures.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use crate::crypto::{mldsa65_sign, mldsa65_verify, STREAM_SIGNATURE_CONTEXT};
+
+const TREE_CONTEXT: &str = "qudag-napi blake3 tree v1";
+
+/// Stream a file through BLAKE3
+pub(crate) fn hash_file(path: &Path) -> Result<blake3::Hash> {
+  hash_file_with(path, blake3::Hasher::new())
+}
+
+/// Stream a file through an already-configured BLAKE3 hasher
+fn hash_file_with(path: &Path, mut hasher: blake3::Hasher) -> Result<blake3::Hash> {
+  let file = File::open(path)
+    .map_err(|e| Error::from_reason(format!("Cannot read {}: {}", path.display(), e)))?;
+
+  hasher
+    .update_reader(file)
+    .map_err(|e| Error::from_reason(format!("Cannot read {}: {}", path.display(), e)))?;
+  Ok(hasher.finalize())
+}
+
+/// Collect every regular file under `dir`, rejecting symlinks
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+  let entries = fs::read_dir(dir)
+    .map_err(|e| Error::from_reason(format!("Cannot read directory {}: {}", dir.display(), e)))?;
+
+  for entry in entries {
+    let entry = entry
+      .map_err(|e| Error::from_reason(format!("Cannot read directory {}: {}", dir.display(), e)))?;
+    let path = entry.path();
+    let file_type = entry.file_type()
+      .map_err(|e| Error::from_reason(format!("Cannot stat {}: {}", path.display(), e)))?;
+
+    if file_type.is_symlink() {
+      return Err(Error::from_reason(format!("Symlinks are not supported: {}", path.display())));
+    } else if file_type.is_dir() {
+      collect_files(&path, files)?;
+    } else if file_type.is_file() {
+      files.push(path);
+    } else {
+      return Err(Error::from_reason(format!("Unsupported file type: {}", path.display())));
+    }
+  }
+
+  Ok(())
+}
+
+/// Hash a directory tree into a single BLAKE3 root
+///
+/// Walks `root_path` recursively, hashes every file, and combines the
+/// sorted (relative path, file hash) pairs, each length-prefixed, with
+/// BLAKE3 in `derive_key` mode. Paths use `/` separators on every platform,
+/// so the root depends only on file names and contents. Empty directories
+/// do not contribute to the root.
+///
+/// # Arguments
+/// * `root_path` - Directory to hash
+///
+/// # Returns
+/// 32-byte root hash; errors on symlinks, unreadable entries, or non-UTF-8 names
+#[napi]
+pub fn blake3_hash_tree(root_path: String) -> Result<Buffer> {
+  let root = Path::new(&root_path);
+  let mut files = Vec::new();
+  collect_files(root, &mut files)?;
+
+  let mut entries = files
+    .iter()
+    .map(|path| {
+      let relative = path.strip_prefix(root).expect("walked paths live under the root");
+      let components = relative
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::from_reason(format!("Non-UTF-8 path: {}", path.display())))?;
+      Ok((components.join("/"), path))
+    })
+    .collect::<Result<Vec<_>>>()?;
+  entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+  let mut hasher = blake3::Hasher::new_derive_key(TREE_CONTEXT);
+  for (relative, path) in entries {
+    let file_hash = hash_file(path)?;
+    hasher.update(&(relative.len() as u64).to_le_bytes());
+    hasher.update(relative.as_bytes());
+    hasher.update(file_hash.as_bytes());
+  }
+
+  Ok(hasher.finalize().as_bytes().to_vec().into())
+}
+
+/// Hash a file the way `StreamSigner` hashes a stream
+fn stream_digest_file(path: &str) -> Result<Buffer> {
+  let hasher = blake3::Hasher::new_derive_key(STREAM_SIGNATURE_CONTEXT);
+  Ok(hash_file_with(Path::new(path), hasher)?.as_bytes().to_vec().into())
+}
+
+/// Sign a file with ML-DSA-65 without loading it into memory
+///
+/// Streams the file through BLAKE3 and signs the digest, exactly as
+/// `StreamSigner` would for the same bytes, so the signature also verifies
+/// with `mldsa65_verify_stream` over the file contents. This is
+/// hash-then-sign under a dedicated context, not FIPS 204 HashML-DSA.
+///
+/// # Arguments
+/// * `path` - File to sign
+/// * `secret_key` - ML-DSA-65 secret key (4032 bytes)
+///
+/// # Returns
+/// Signature (3309 bytes); errors if the file cannot be read
+#[napi]
+pub fn mldsa65_sign_file(path: String, secret_key: Buffer) -> Result<Buffer> {
+  mldsa65_sign(stream_digest_file(&path)?, secret_key)
+}
+
+/// Verify a `mldsa65_sign_file` signature by re-hashing the file
+///
+/// # Arguments
+/// * `path` - File to verify
+/// * `signature` - Signature (3309 bytes)
+/// * `public_key` - ML-DSA-65 public key (1952 bytes)
+///
+/// # Returns
+/// true if the signature is valid for the file's current contents; errors
+/// if the file cannot be read
+#[napi]
+pub fn mldsa65_verify_file(path: String, signature: Buffer, public_key: Buffer) -> Result<bool> {
+  mldsa65_verify(stream_digest_file(&path)?, signature, public_key)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("qudag-napi-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+  }
+
+  #[test]
+  fn test_blake3_hash_tree_changes_with_contents() {
+    let dir = temp_dir("tree");
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::write(dir.join("a.txt"), b"alpha").unwrap();
+    fs::write(dir.join("sub/b.txt"), b"beta").unwrap();
+
+    let path = dir.to_str().unwrap().to_string();
+    let root = blake3_hash_tree(path.clone()).unwrap();
+    assert_eq!(root.len(), 32);
+    assert_eq!(root.as_ref(), blake3_hash_tree(path.clone()).unwrap().as_ref());
+
+    fs::write(dir.join("sub/b.txt"), b"beta!").unwrap();
+    let modified = blake3_hash_tree(path.clone()).unwrap();
+    assert_ne!(root.as_ref(), modified.as_ref());
+
+    fs::rename(dir.join("a.txt"), dir.join("c.txt")).unwrap();
+    assert_ne!(modified.as_ref(), blake3_hash_tree(path).unwrap().as_ref());
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_blake3_hash_tree_rejects_symlinks() {
+    let dir = temp_dir("symlink");
+    fs::write(dir.join("a.txt"), b"alpha").unwrap();
+    std::os::unix::fs::symlink(dir.join("a.txt"), dir.join("link")).unwrap();
+
+    let err = blake3_hash_tree(dir.to_str().unwrap().to_string()).err().unwrap();
+    assert!(err.reason.contains("Symlinks are not supported"));
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_mldsa65_sign_file() {
+    let dir = temp_dir("sign");
+    let file = dir.join("release.tar");
+    fs::write(&file, vec![0x5au8; 100_000]).unwrap();
+    let path = file.to_str().unwrap().to_string();
+    let keypair = crate::crypto::mldsa65_generate_keypair().unwrap();
+
+    let signature = mldsa65_sign_file(path.clone(), keypair.secret_key).unwrap();
+    assert!(mldsa65_verify_file(path.clone(), signature.clone(), keypair.public_key.clone()).unwrap());
+    assert!(crate::crypto::mldsa65_verify_stream(
+      vec![0x5au8; 100_000].into(),
+      signature.clone(),
+      keypair.public_key.clone()
+    ).unwrap());
+
+    let mut contents = fs::read(&file).unwrap();
+    contents[50_000] ^= 1;
+    fs::write(&file, contents).unwrap();
+    assert!(!mldsa65_verify_file(path, signature.clone(), keypair.public_key.clone()).unwrap());
+
+    assert!(mldsa65_verify_file(dir.join("missing").to_str().unwrap().to_string(), signature, keypair.public_key).is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_blake3_hash_tree_missing_root() {
+    assert!(blake3_hash_tree("/nonexistent/qudag-napi".to_string()).is_err());
+  }
+}
@@ -0,0 +1,787 @@
+//! Stateful BLAKE3 hashers
+//!
+//! Classes for incremental hashing workloads where one-shot functions would
+//! force the whole input or output through a single Buffer, plus Bao
+//! encodings that let a receiver verify streamed data against a BLAKE3 root
+//! chunk by chunk. `RollingHasher` provides a non-cryptographic window hash
+//! for content-defined chunking.
+
+use blake3::hazmat::{merge_subtrees_non_root, merge_subtrees_root, ChainingValue, HasherExt, Mode};
+use blake3::CHUNK_LEN;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use zeroize::Zeroize;
+
+use crate::crypto::check_xof_length;
+
+const REKEY_CONTEXT: &str = "qudag-napi rekeying hasher v1";
+const CHECKPOINT_MAGIC: &[u8; 4] = b"QB3C";
+const CHECKPOINT_VERSION: u8 = 1;
+/// Largest `RollingHasher` window, in bytes; CDC windows are typically 32-64
+const MAX_ROLLING_WINDOW: u32 = 4096;
+
+/// Per-byte values for the rolling hash, from a fixed SplitMix64 stream
+const BUZHASH_TABLE: [u32; 256] = {
+  let mut table = [0u32; 256];
+  let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+  let mut i = 0;
+  while i < 256 {
+    state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    table[i] = ((z ^ (z >> 31)) >> 32) as u32;
+    i += 1;
+  }
+  table
+};
+
+/// Bao encoding of some data with its BLAKE3 root
+#[napi(object)]
+pub struct VerifiedEncoding {
+  /// Combined Bao encoding: data interleaved with the BLAKE3 tree
+  pub encoded: Buffer,
+  /// BLAKE3 hash of the data (32 bytes), equal to `blake3_hash`
+  pub root: Buffer,
+}
+
+/// Encode data for verified streaming
+///
+/// The encoding interleaves the data with the parent nodes of its BLAKE3
+/// tree, so a receiver holding only the 32-byte root can check every chunk
+/// as it arrives instead of buffering the whole input first.
+///
+/// # Arguments
+/// * `data` - Data to encode
+///
+/// # Returns
+/// VerifiedEncoding with the encoded stream and its root
+#[napi]
+pub fn blake3_encode_verified(data: Buffer) -> VerifiedEncoding {
+  let (encoded, root) = bao::encode::encode(data.as_ref());
+  VerifiedEncoding {
+    encoded: encoded.into(),
+    root: root.as_bytes().to_vec().into(),
+  }
+}
+
+/// Verify and decode a stream from `blake3_encode_verified`
+///
+/// # Arguments
+/// * `encoded` - Bao-encoded stream
+/// * `root` - Trusted BLAKE3 root (32 bytes)
+///
+/// # Returns
+/// The original data; errors if any part of the stream fails to verify
+#[napi]
+pub fn blake3_decode_verified(encoded: Buffer, root: Buffer) -> Result<Buffer> {
+  let root: [u8; 32] = root.as_ref().try_into().map_err(|_| {
+    Error::from_reason(format!("Invalid root length: expected 32 bytes, got {}", root.len()))
+  })?;
+
+  bao::decode::decode(encoded.as_ref(), &root.into())
+    .map(Buffer::from)
+    .map_err(|e| Error::from_reason(format!("Verified stream rejected: {}", e)))
+}
+
+pub use blake3_hasher_class::Blake3Hasher;
+
+#[allow(missing_docs)]
+mod blake3_hasher_class {
+  use super::*;
+
+  /// Incremental BLAKE3 hasher whose state can be saved and restored
+  ///
+  /// Produces the same digest as `blake3_hash` over the concatenated input.
+  /// The hasher tracks the BLAKE3 tree itself (chaining values of completed
+  /// subtrees plus the unfinished last chunk), so `checkpoint` can serialize
+  /// it and `resume` can carry on in another process after a restart.
+  ///
+  /// Checkpoint layout:
+  /// `magic "QB3C" (4) || version (1) || chunk count (8, LE) ||
+  /// stack depth (1) || chaining values (32 each) || pending length (2, LE) ||
+  /// pending bytes`
+  ///
+  /// The format is specific to this version of the module and is not a
+  /// stable interchange format; only resume checkpoints written by the same
+  /// release. A checkpoint holds up to 1 KiB of recent input in the clear.
+  #[napi]
+  pub struct Blake3Hasher {
+    /// Chaining values of completed subtrees, largest first
+    pub(super) cv_stack: Vec<ChainingValue>,
+    /// Number of 1 KiB chunks covered by `cv_stack`
+    pub(super) chunks_done: u64,
+    /// Input not yet committed to the tree; never a full chunk's worth more
+    pub(super) pending: Vec<u8>,
+  }
+}
+
+#[napi]
+impl Blake3Hasher {
+  /// Create an empty hasher
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Self {
+      cv_stack: Vec::new(),
+      chunks_done: 0,
+      pending: Vec::new(),
+    }
+  }
+
+  /// Absorb more input
+  #[napi]
+  pub fn update(&mut self, data: Buffer) {
+    self.absorb(data.as_ref());
+  }
+
+  /// Read up to `length` bytes from an open file descriptor and absorb them
+  ///
+  /// Reads from the descriptor's current position, as `fs.readSync` does
+  /// without a position, and leaves the descriptor open. Short reads are
+  /// retried; EOF ends the read early. Returns the number of bytes absorbed,
+  /// which is less than `length` only at EOF. Unix only.
+  #[napi]
+  pub fn update_from_fd(&mut self, fd: i32, length: u32) -> Result<u32> {
+    #[cfg(unix)]
+    {
+      use std::io::{ErrorKind, Read};
+      use std::os::fd::FromRawFd;
+
+      if fd < 0 {
+        return Err(Error::from_reason(format!("Invalid file descriptor: {}", fd)));
+      }
+      // Borrow the descriptor: ManuallyDrop keeps the File from closing it
+      // SAFETY: the caller owns `fd` and keeps it open for this call. The
+      // File is never dropped, so the descriptor is only read from, never
+      // closed, and ownership stays with the caller.
+      let mut file = std::mem::ManuallyDrop::new(unsafe { std::fs::File::from_raw_fd(fd) });
+
+      let mut buf = vec![0u8; (length as usize).min(64 * 1024)];
+      let mut total = 0usize;
+      while total < length as usize {
+        let want = (length as usize - total).min(buf.len());
+        match file.read(&mut buf[..want]) {
+          Ok(0) => break,
+          Ok(n) => {
+            self.absorb(&buf[..n]);
+            total += n;
+          }
+          Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+          Err(e) => return Err(Error::from_reason(format!("Cannot read fd {}: {}", fd, e))),
+        }
+      }
+      Ok(total as u32)
+    }
+
+    #[cfg(not(unix))]
+    {
+      let _ = (fd, length);
+      Err(Error::from_reason("updateFromFd is only supported on Unix platforms"))
+    }
+  }
+
+  /// Return the 32-byte digest of everything absorbed so far
+  #[napi(js_name = "finalize")]
+  pub fn finalize_hash(&self) -> Buffer {
+    self.root().as_bytes().to_vec().into()
+  }
+
+  /// Return the digest of everything absorbed so far as lowercase hex
+  ///
+  /// Like `finalize`, this leaves the hasher usable, so progress displays
+  /// can show the running digest between updates.
+  #[napi]
+  pub fn finalize_hex(&self) -> String {
+    self.root().to_hex().to_string()
+  }
+
+  /// Serialize the current state for `resume`
+  #[napi]
+  pub fn checkpoint(&self) -> Buffer {
+    let mut out = Vec::with_capacity(16 + 32 * self.cv_stack.len() + self.pending.len());
+    out.extend_from_slice(CHECKPOINT_MAGIC);
+    out.push(CHECKPOINT_VERSION);
+    out.extend_from_slice(&self.chunks_done.to_le_bytes());
+    out.push(self.cv_stack.len() as u8);
+    for cv in &self.cv_stack {
+      out.extend_from_slice(cv);
+    }
+    out.extend_from_slice(&(self.pending.len() as u16).to_le_bytes());
+    out.extend_from_slice(&self.pending);
+    out.into()
+  }
+
+  /// Rebuild a hasher from a `checkpoint` written by this module version
+  #[napi(factory)]
+  pub fn resume(checkpoint: Buffer) -> Result<Self> {
+    let invalid = |what: &str| Error::from_reason(format!("Invalid hasher checkpoint: {}", what));
+    let bytes = checkpoint.as_ref();
+
+    if bytes.len() < 14 || &bytes[..4] != CHECKPOINT_MAGIC {
+      return Err(invalid("missing QB3C magic"));
+    }
+    if bytes[4] != CHECKPOINT_VERSION {
+      return Err(Error::from_reason(format!("Unsupported hasher checkpoint version: {}", bytes[4])));
+    }
+
+    let chunks_done = u64::from_le_bytes(bytes[5..13].try_into().expect("slice is 8 bytes"));
+    let depth = bytes[13] as usize;
+    // The byte offset `chunks_done * CHUNK_LEN` must fit in a u64
+    if depth != chunks_done.count_ones() as usize || chunks_done > u64::MAX / CHUNK_LEN as u64 {
+      return Err(invalid("inconsistent tree state"));
+    }
+
+    let stack_end = 14 + 32 * depth;
+    let stack = bytes.get(14..stack_end).ok_or_else(|| invalid("truncated"))?;
+    let cv_stack = stack
+      .chunks_exact(32)
+      .map(|cv| cv.try_into().expect("chunk is 32 bytes"))
+      .collect();
+
+    let len_bytes = bytes.get(stack_end..stack_end + 2).ok_or_else(|| invalid("truncated"))?;
+    let pending_len = u16::from_le_bytes([len_bytes[0], len_bytes[1]]) as usize;
+    let pending = &bytes[stack_end + 2..];
+    if pending.len() != pending_len {
+      return Err(invalid("pending input length mismatch"));
+    }
+    // Committed chunks are always followed by at least one pending byte
+    if pending_len > CHUNK_LEN || (chunks_done > 0 && pending_len == 0) {
+      return Err(invalid("inconsistent tree state"));
+    }
+
+    Ok(Self {
+      cv_stack,
+      chunks_done,
+      pending: pending.to_vec(),
+    })
+  }
+
+  /// Commit every complete subtree that is certain not to be the root
+  ///
+  /// The last byte of input so far always stays pending, so the final chunk
+  /// is only hashed by `root`, once it is known to be last.
+  fn absorb(&mut self, data: &[u8]) {
+    self.pending.extend_from_slice(data);
+
+    let mut offset = 0;
+    while self.pending.len() - offset > CHUNK_LEN {
+      let available = (self.pending.len() - offset - 1) / CHUNK_LEN;
+      let mut chunks = 1u64 << (usize::BITS - 1 - available.leading_zeros());
+      if self.chunks_done > 0 {
+        // A subtree must start at a multiple of its own size
+        chunks = chunks.min(1 << self.chunks_done.trailing_zeros());
+      }
+
+      let len = chunks as usize * CHUNK_LEN;
+      let cv = blake3::Hasher::new()
+        .set_input_offset(self.chunks_done * CHUNK_LEN as u64)
+        .update(&self.pending[offset..offset + len])
+        .finalize_non_root();
+      offset += len;
+
+      self.cv_stack.push(cv);
+      self.chunks_done += chunks;
+      while self.cv_stack.len() > self.chunks_done.count_ones() as usize {
+        let right = self.cv_stack.pop().expect("stack holds at least two entries");
+        let left = self.cv_stack.pop().expect("stack holds at least two entries");
+        self.cv_stack.push(merge_subtrees_non_root(&left, &right, Mode::Hash));
+      }
+    }
+
+    self.pending.drain(..offset);
+  }
+
+  fn root(&self) -> blake3::Hash {
+    if self.chunks_done == 0 {
+      return blake3::hash(&self.pending);
+    }
+
+    let mut right = blake3::Hasher::new()
+      .set_input_offset(self.chunks_done * CHUNK_LEN as u64)
+      .update(&self.pending)
+      .finalize_non_root();
+    for left in self.cv_stack[1..].iter().rev() {
+      right = merge_subtrees_non_root(left, &right, Mode::Hash);
+    }
+    merge_subtrees_root(&self.cv_stack[0], &right, Mode::Hash)
+  }
+}
+
+impl Default for Blake3Hasher {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+pub use blake3_xof_class::Blake3Xof;
+
+#[allow(missing_docs)]
+mod blake3_xof_class {
+  use super::*;
+
+  /// Incremental reader over BLAKE3 extendable output
+  ///
+  /// Each `read` continues where the previous one stopped, so successive reads
+  /// produce the same bytes as one `blake3_hash_xof` of the combined length.
+  #[napi]
+  pub struct Blake3Xof {
+    pub(super) reader: blake3::OutputReader,
+  }
+}
+
+#[napi]
+impl Blake3Xof {
+  /// Create an XOF reader over the BLAKE3 output of `data`
+  #[napi(constructor)]
+  pub fn new(data: Buffer) -> Self {
+    let reader = blake3::Hasher::new().update(data.as_ref()).finalize_xof();
+    Self { reader }
+  }
+
+  /// Read the next `len` bytes (at most 65536) and advance the position
+  #[napi]
+  pub fn read(&mut self, len: u32) -> Result<Buffer> {
+    check_xof_length(len)?;
+    let mut output = vec![0u8; len as usize];
+    self.reader.fill(&mut output);
+    Ok(output.into())
+  }
+
+  /// Current position in the output stream, in bytes
+  #[napi(getter)]
+  pub fn position(&self) -> f64 {
+    self.reader.position() as f64
+  }
+}
+
+pub use rekeying_hasher_class::RekeyingHasher;
+
+#[allow(missing_docs)]
+mod rekeying_hasher_class {
+  use super::*;
+
+  /// Keyed BLAKE3 MAC that rekeys every `interval` bytes
+  ///
+  /// Input is split into fixed segments of `interval` bytes. Each segment is
+  /// keyed-hashed under its own key; when a segment is complete, the next key
+  /// is `derive_key("qudag-napi rekeying hasher v1", key || segment tag)` and
+  /// the old key is erased. A key captured mid-stream therefore reveals
+  /// nothing about earlier segment keys. `finalize` returns the keyed hash of
+  /// the last (possibly partial) segment, which depends on every segment
+  /// before it.
+  ///
+  /// Segment boundaries depend only on byte counts, so the output does not
+  /// depend on how input is split across `update` calls. The output is not
+  /// interoperable with plain or keyed BLAKE3.
+  #[napi]
+  pub struct RekeyingHasher {
+    pub(super) key: [u8; 32],
+    pub(super) hasher: blake3::Hasher,
+    pub(super) interval: u64,
+    pub(super) segment_len: u64,
+  }
+}
+
+#[napi]
+impl RekeyingHasher {
+  /// Create a hasher from a 32-byte key, rekeying every `interval` bytes
+  #[napi(constructor)]
+  pub fn new(key: Buffer, interval: u32) -> Result<Self> {
+    let key: [u8; 32] = key.as_ref()
+      .try_into()
+      .map_err(|_| Error::from_reason(format!(
+        "Invalid key length: expected 32 bytes, got {}",
+        key.len()
+      )))?;
+    if interval == 0 {
+      return Err(Error::from_reason("Rekey interval must be at least 1 byte"));
+    }
+
+    Ok(Self {
+      hasher: blake3::Hasher::new_keyed(&key),
+      key,
+      interval: interval as u64,
+      segment_len: 0,
+    })
+  }
+
+  /// Absorb more input
+  #[napi]
+  pub fn update(&mut self, data: Buffer) {
+    let mut rest = data.as_ref();
+    while !rest.is_empty() {
+      let take = (self.interval - self.segment_len).min(rest.len() as u64) as usize;
+      self.hasher.update(&rest[..take]);
+      self.segment_len += take as u64;
+      rest = &rest[take..];
+
+      if self.segment_len == self.interval {
+        self.rekey();
+      }
+    }
+  }
+
+  /// Return the 32-byte tag over everything absorbed so far
+  #[napi(js_name = "finalize")]
+  pub fn finalize_tag(&self) -> Buffer {
+    self.hasher.finalize().as_bytes().to_vec().into()
+  }
+
+  fn rekey(&mut self) {
+    let tag = self.hasher.finalize();
+    let mut next_key = blake3::Hasher::new_derive_key(REKEY_CONTEXT)
+      .update(&self.key)
+      .update(tag.as_bytes())
+      .finalize()
+      .into();
+
+    self.key.zeroize();
+    self.key = next_key;
+    next_key.zeroize();
+    self.hasher = blake3::Hasher::new_keyed(&self.key);
+    self.segment_len = 0;
+  }
+}
+
+impl Drop for RekeyingHasher {
+  fn drop(&mut self) {
+    self.key.zeroize();
+  }
+}
+
+pub use rolling_hasher_class::RollingHasher;
+
+#[allow(missing_docs)]
+mod rolling_hasher_class {
+  use super::*;
+
+  /// Rolling hash over a fixed-size sliding window
+  ///
+  /// A cyclic polynomial (buzhash) over 32-bit words: each `roll` moves the
+  /// window one byte in O(1), and the value always equals `rolling_hash` of
+  /// the bytes currently in the window. The window starts out filled with
+  /// zero bytes, so after `window_size` rolls from a fresh hasher (passing 0
+  /// as `byte_out`) the value covers exactly the bytes rolled in.
+  ///
+  /// This is not a cryptographic hash; use it only to pick chunk boundaries,
+  /// then hash the chunks with BLAKE3.
+  #[napi]
+  pub struct RollingHasher {
+    pub(super) window_size: u32,
+    pub(super) hash: u32,
+  }
+}
+
+#[napi]
+impl RollingHasher {
+  /// Create a hasher over a window of `window_size` bytes (1 to 4096)
+  #[napi(constructor)]
+  pub fn new(window_size: u32) -> Result<Self> {
+    if window_size == 0 {
+      return Err(Error::from_reason("Window size must be at least 1 byte"));
+    }
+    if window_size > MAX_ROLLING_WINDOW {
+      return Err(Error::from_reason(format!(
+        "Window size too large: at most {} bytes, got {}",
+        MAX_ROLLING_WINDOW, window_size
+      )));
+    }
+
+    // Same as `buzhash` over `window_size` zero bytes, without the buffer
+    let hash = (0..window_size).fold(0u32, |hash, _| hash.rotate_left(1) ^ BUZHASH_TABLE[0]);
+    Ok(Self { window_size, hash })
+  }
+
+  /// Slide the window: add `byte_in`, drop `byte_out` (the byte that entered
+  /// `window_size` rolls ago), and return the new hash
+  #[napi]
+  pub fn roll(&mut self, byte_in: u8, byte_out: u8) -> u32 {
+    self.hash = self.hash.rotate_left(1)
+      ^ BUZHASH_TABLE[byte_out as usize].rotate_left(self.window_size)
+      ^ BUZHASH_TABLE[byte_in as usize];
+    self.hash
+  }
+
+  /// Hash of the current window
+  #[napi(getter)]
+  pub fn value(&self) -> u32 {
+    self.hash
+  }
+}
+
+/// Compute the rolling hash of one window from scratch
+///
+/// # Arguments
+/// * `window` - Window contents; its length is the window size
+///
+/// # Returns
+/// The value a `RollingHasher` of that window size holds over these bytes
+#[napi]
+pub fn rolling_hash(window: Buffer) -> u32 {
+  buzhash(window.as_ref())
+}
+
+/// Find content-defined chunk boundaries
+///
+/// Slides a `RollingHasher` over `data` and cuts after every position
+/// whose full window hashes to a value with all `mask` bits set. With a
+/// mask of `k` one bits the average chunk is about `2^k` bytes. Boundaries
+/// depend only on the window contents, so an insertion early in the data
+/// leaves later boundaries where they were.
+///
+/// # Arguments
+/// * `data` - Data to chunk
+/// * `window_size` - Rolling window size in bytes (1 to 4096)
+/// * `mask` - Boundary mask, e.g. `0x1fff` for ~8 KiB chunks
+///
+/// # Returns
+/// Chunk end offsets in increasing order (each exclusive); the end of
+/// `data` is not included unless it is itself a boundary
+#[napi]
+pub fn find_chunk_boundaries(data: Buffer, window_size: u32, mask: u32) -> Result<Vec<u32>> {
+  if u32::try_from(data.len()).is_err() {
+    return Err(Error::from_reason("Data too large to chunk"));
+  }
+
+  let window = window_size as usize;
+  let mut hasher = RollingHasher::new(window_size)?;
+  let mut boundaries = Vec::new();
+  for (i, &byte) in data.iter().enumerate() {
+    let byte_out = if i >= window { data[i - window] } else { 0 };
+    let hash = hasher.roll(byte, byte_out);
+    if i + 1 >= window && hash & mask == mask {
+      boundaries.push((i + 1) as u32);
+    }
+  }
+  Ok(boundaries)
+}
+
+fn buzhash(window: &[u8]) -> u32 {
+  window.iter().fold(0u32, |hash, &byte| hash.rotate_left(1) ^ BUZHASH_TABLE[byte as usize])
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::crypto::blake3_hash_xof;
+
+  #[test]
+  fn test_blake3_xof_successive_reads() {
+    let data = b"keystream seed".to_vec();
+    let mut xof = Blake3Xof::new(data.clone().into());
+
+    let first = xof.read(16).unwrap();
+    let second = xof.read(16).unwrap();
+    assert_eq!(xof.position(), 32.0);
+
+    // Oversized reads are rejected without advancing the stream
+    assert!(xof.read(u32::MAX).is_err());
+    assert_eq!(xof.position(), 32.0);
+
+    let whole = blake3_hash_xof(data.into(), 32).unwrap();
+    assert_eq!(first.as_ref(), &whole[..16]);
+    assert_eq!(second.as_ref(), &whole[16..]);
+  }
+
+  #[test]
+  fn test_rekeying_hasher_deterministic() {
+    let key: Buffer = vec![3u8; 32].into();
+    let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+
+    let mut whole = RekeyingHasher::new(key.clone(), 64).unwrap();
+    whole.update(data.clone().into());
+    let tag = whole.finalize_tag();
+
+    let mut pieces = RekeyingHasher::new(key.clone(), 64).unwrap();
+    for chunk in data.chunks(37) {
+      pieces.update(chunk.to_vec().into());
+    }
+    assert_eq!(pieces.finalize_tag().as_ref(), tag.as_ref());
+
+    let mut other_interval = RekeyingHasher::new(key.clone(), 128).unwrap();
+    other_interval.update(data.clone().into());
+    assert_ne!(other_interval.finalize_tag().as_ref(), tag.as_ref());
+
+    // Not interoperable with keyed BLAKE3
+    assert_ne!(tag.as_ref(), blake3::keyed_hash(&[3u8; 32], &data).as_bytes());
+
+    assert!(RekeyingHasher::new(key, 0).is_err());
+    assert!(RekeyingHasher::new(vec![0u8; 16].into(), 64).is_err());
+  }
+
+  #[test]
+  fn test_blake3_hasher_matches_one_shot() {
+    let data: Vec<u8> = (0..40_000u32).map(|i| (i * 31 % 251) as u8).collect();
+
+    for len in [0, 1, 1023, 1024, 1025, 2048, 3073, 8192, 40_000] {
+      let mut hasher = Blake3Hasher::new();
+      for piece in data[..len].chunks(777) {
+        hasher.update(piece.to_vec().into());
+      }
+      assert_eq!(hasher.finalize_hash().as_ref(), blake3::hash(&data[..len]).as_bytes(), "length {}", len);
+    }
+  }
+
+  #[test]
+  fn test_blake3_hasher_finalize_hex() {
+    let mut hasher = Blake3Hasher::new();
+    assert_eq!(hasher.finalize_hex(), hex::encode(hasher.finalize_hash()));
+
+    for piece in [&b"progress"[..], &[0u8; 1500], b"done"] {
+      hasher.update(piece.to_vec().into());
+      let hex = hasher.finalize_hex();
+      assert_eq!(hex.len(), 64);
+      assert_eq!(hex, hex::encode(hasher.finalize_hash()));
+    }
+  }
+
+  #[test]
+  fn test_blake3_hasher_checkpoint_resume() {
+    let data: Vec<u8> = (0..20_000u32).map(|i| (i * 7 % 253) as u8).collect();
+    let expected = blake3::hash(&data);
+
+    for split in [0, 1, 1024, 1025, 5000, 16_384, 19_999] {
+      let mut first = Blake3Hasher::new();
+      first.update(data[..split].to_vec().into());
+      let checkpoint = first.checkpoint();
+
+      let mut resumed = Blake3Hasher::resume(checkpoint.clone()).unwrap();
+      resumed.update(data[split..].to_vec().into());
+      assert_eq!(resumed.finalize_hash().as_ref(), expected.as_bytes(), "split {}", split);
+
+      // A resumed hasher checkpoints to the same bytes
+      assert_eq!(Blake3Hasher::resume(checkpoint.clone()).unwrap().checkpoint().as_ref(), checkpoint.as_ref());
+    }
+  }
+
+  #[test]
+  fn test_blake3_hasher_rejects_bad_checkpoint() {
+    let mut hasher = Blake3Hasher::new();
+    hasher.update(vec![5u8; 3000].into());
+    let checkpoint = hasher.checkpoint().to_vec();
+
+    assert!(Blake3Hasher::resume(checkpoint[..checkpoint.len() - 1].to_vec().into()).is_err());
+
+    let mut wrong_version = checkpoint.clone();
+    wrong_version[4] = 2;
+    assert!(Blake3Hasher::resume(wrong_version.into()).is_err());
+
+    let mut wrong_count = checkpoint.clone();
+    wrong_count[5] ^= 1;
+    assert!(Blake3Hasher::resume(wrong_count.into()).is_err());
+
+    // One subtree covering 2^60 chunks: consistent depth, but the byte offset overflows
+    let mut huge = CHECKPOINT_MAGIC.to_vec();
+    huge.push(CHECKPOINT_VERSION);
+    huge.extend_from_slice(&(1u64 << 60).to_le_bytes());
+    huge.push(1);
+    huge.extend_from_slice(&[0u8; 32]);
+    huge.extend_from_slice(&1u16.to_le_bytes());
+    huge.push(0);
+    let err = Blake3Hasher::resume(huge.into()).err().unwrap();
+    assert!(err.reason.contains("inconsistent tree state"));
+
+    assert!(Blake3Hasher::resume(b"QB3C".to_vec().into()).is_err());
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_blake3_hasher_update_from_fd() {
+    use std::os::fd::AsRawFd;
+
+    let path = std::env::temp_dir().join(format!("qudag-napi-fd-{}", std::process::id()));
+    let data: Vec<u8> = (0..100_000u32).map(|i| (i % 249) as u8).collect();
+    std::fs::write(&path, &data).unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+
+    let mut hasher = Blake3Hasher::new();
+    assert_eq!(hasher.update_from_fd(file.as_raw_fd(), 70_000).unwrap(), 70_000);
+    // Only 30,000 bytes remain before EOF
+    assert_eq!(hasher.update_from_fd(file.as_raw_fd(), 50_000).unwrap(), 30_000);
+    assert_eq!(hasher.update_from_fd(file.as_raw_fd(), 10).unwrap(), 0);
+
+    let mut from_buffer = Blake3Hasher::new();
+    from_buffer.update(data.into());
+    assert_eq!(hasher.finalize_hash().as_ref(), from_buffer.finalize_hash().as_ref());
+
+    // The descriptor is still open and usable
+    assert!(file.metadata().is_ok());
+    drop(file);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(Blake3Hasher::new().update_from_fd(-1, 10).is_err());
+  }
+
+  #[test]
+  fn test_blake3_verified_roundtrip() {
+    for len in [0, 1, 1024, 1025, 10_000] {
+      let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+      let encoding = blake3_encode_verified(data.clone().into());
+
+      assert_eq!(encoding.root.as_ref(), blake3::hash(&data).as_bytes());
+      let decoded = blake3_decode_verified(encoding.encoded, encoding.root).unwrap();
+      assert_eq!(decoded.as_ref(), data.as_slice());
+    }
+  }
+
+  #[test]
+  fn test_blake3_verified_rejects_corruption() {
+    let data = vec![7u8; 5000];
+    let encoding = blake3_encode_verified(data.clone().into());
+
+    for index in [0, 8, 100, encoding.encoded.len() - 1] {
+      let mut corrupted = encoding.encoded.to_vec();
+      corrupted[index] ^= 1;
+      assert!(blake3_decode_verified(corrupted.into(), encoding.root.clone()).is_err());
+    }
+
+    let other_root = blake3::hash(b"other").as_bytes().to_vec();
+    assert!(blake3_decode_verified(encoding.encoded.clone(), other_root.into()).is_err());
+    assert!(blake3_decode_verified(encoding.encoded, vec![0u8; 31].into()).is_err());
+  }
+
+  #[test]
+  fn test_rolling_hash_matches_recomputation() {
+    let data: Vec<u8> = (0..2000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect();
+
+    for window in [1usize, 16, 32, 48, 64] {
+      let mut hasher = RollingHasher::new(window as u32).unwrap();
+      for i in 0..data.len() {
+        let byte_out = if i >= window { data[i - window] } else { 0 };
+        let rolled = hasher.roll(data[i], byte_out);
+        if i + 1 >= window {
+          assert_eq!(rolled, rolling_hash(data[i + 1 - window..=i].to_vec().into()), "window {} at {}", window, i);
+        }
+      }
+    }
+    assert!(RollingHasher::new(0).is_err());
+  }
+
+  #[test]
+  fn test_rolling_hasher_window_cap() {
+    for window in [1u32, 31, 32, 33, 64, MAX_ROLLING_WINDOW] {
+      let zeros = vec![0u8; window as usize];
+      assert_eq!(RollingHasher::new(window).unwrap().value(), rolling_hash(zeros.into()), "window {}", window);
+    }
+
+    let err = RollingHasher::new(MAX_ROLLING_WINDOW + 1).err().unwrap();
+    assert!(err.reason.starts_with("Window size too large"));
+    assert!(RollingHasher::new(u32::MAX).is_err());
+    assert!(find_chunk_boundaries(vec![0u8; 16].into(), MAX_ROLLING_WINDOW + 1, 0xff).is_err());
+  }
+
+  #[test]
+  fn test_find_chunk_boundaries_resynchronise() {
+    let data: Vec<u8> = (0..50_000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 11) as u8).collect();
+    let boundaries = find_chunk_boundaries(data.clone().into(), 48, 0xff).unwrap();
+    assert!(boundaries.len() > 50, "{} boundaries", boundaries.len());
+    assert!(boundaries.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // Inserting bytes at the front shifts, but does not move, later boundaries
+    let mut shifted = vec![0xabu8; 7];
+    shifted.extend_from_slice(&data);
+    let moved = find_chunk_boundaries(shifted.into(), 48, 0xff).unwrap();
+    let later: Vec<u32> = boundaries.iter().filter(|&&b| b > 100).map(|b| b + 7).collect();
+    assert!(later.iter().all(|b| moved.contains(b)));
+  }
+}
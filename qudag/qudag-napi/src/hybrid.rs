@@ -0,0 +1,1492 @@
+//! Hybrid constructions combining ML-KEM-768 with symmetric encryption
+//!
+//! Shared secrets from ML-KEM are never used as AEAD keys directly; they are
+//! first passed through a KDF with a construction-specific context.
+//!
+//! `hybrid_encrypt` output layout:
+//! `version (1) || ML-KEM ciphertext (1088) || nonce (12) || AES-256-GCM ciphertext || tag (16)`
+//!
+//! The version byte is currently 1; blobs with any other version are
+//! rejected so future layout changes are detected rather than misparsed.
+//!
+//! Signed envelopes wrap a hybrid blob with a length field and a checksum:
+//! `magic "QENV" (4) || version (1) || body length (4, LE) || body ||
+//! BLAKE3 checksum (32)`, where the body is a hybrid-layout blob whose
+//! plaintext is `ML-DSA-65 signature (3309) || message` and the checksum
+//! covers every byte before it.
+//!
+//! `hybrid_encrypt_deterministic` produces the same layout, with the KEM
+//! coins and nonce derived from the convergence key and plaintext instead of
+//! drawn at random, so `hybrid_decrypt` opens it.
+//!
+//! `hybrid_encrypt_padded` uses the same layout over a padded plaintext
+//! `length (4, LE) || plaintext || zero bytes`, sized to a multiple of the
+//! chosen block, and authenticates a padding-specific AAD so padded and
+//! unpadded blobs can't be opened as each other.
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use hkdf::Hkdf;
+use std::collections::{HashMap, HashSet};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+use crate::crypto::{
+  hash_length_prefixed, mldsa65_sign, mldsa65_verify, mlkem768_decapsulate, mlkem768_encapsulate,
+  mlkem768_encapsulate_deterministic, mlkem768_public_from_secret,
+};
+
+const HYBRID_VERSION: u8 = 1;
+const HYBRID_MIN_LEN: usize = 1 + 1088 + 12 + 16;
+const HYBRID_AEAD_CONTEXT: &str = "qudag-napi hybrid aead key v1";
+const SECRET_EXPORT_AAD: &[u8] = b"qudag-napi secret key export v1";
+const PADDED_AAD: &[u8] = b"qudag-napi padded hybrid v1";
+/// Largest `hybrid_encrypt_padded` block size, in bytes (64 KiB)
+const MAX_PAD_BLOCK: u32 = 64 * 1024;
+const CONVERGENT_COINS_CONTEXT: &str = "qudag-napi convergent kem coins v1";
+const CONVERGENT_NONCE_CONTEXT: &str = "qudag-napi convergent nonce v1";
+const GROUP_MEMBERSHIP_CONTEXT: &str = "qudag-napi group membership key v1";
+const WRAPPED_SECRET_AAD: &[u8] = b"qudag-napi wrapped shared secret v1";
+const ENVELOPE_AAD: &[u8] = b"qudag-napi signed envelope v1";
+const ENVELOPE_SIGNATURE_CONTEXT: &str = "qudag-napi signed envelope signature v1";
+const ENVELOPE_MAGIC: &[u8; 4] = b"QENV";
+const ENVELOPE_VERSION: u8 = 1;
+const ENVELOPE_HEADER_LEN: usize = 4 + 1 + 4;
+const TRANSCRIPT_MAC_CONTEXT: &str = "qudag-napi transcript mac key v1";
+const SIGNED_TRANSCRIPT_CONTEXT: &str = "qudag-napi signed transcript v1";
+const KEM_COMBINER_CONTEXT: &str = "qudag-napi hybrid kem combiner v1";
+const SESSION_ID_CONTEXT: &str = "session-id";
+const ENC_KEY_CONTEXT: &str = "qudag-napi encrypt-then-mac enc key v1";
+const MAC_KEY_CONTEXT: &str = "qudag-napi encrypt-then-mac mac key v1";
+const INITIATOR_CONFIRM_CONTEXT: &str = "qudag-napi key confirmation initiator v1";
+const RESPONDER_CONFIRM_CONTEXT: &str = "qudag-napi key confirmation responder v1";
+
+/// Outcome of `hybrid_decrypt_safe`
+#[napi(object)]
+pub struct HybridDecryptResult {
+  /// Whether decryption succeeded
+  pub ok: bool,
+  /// Decrypted plaintext, when `ok`
+  pub plaintext: Option<Buffer>,
+  /// Failure code when not `ok`: "truncated", "unsupported_version",
+  /// "invalid_key" or "authentication_failed"
+  pub error: Option<String>,
+}
+
+/// Independent keys for encrypt-then-MAC, from `derive_enc_mac_keys`
+#[napi(object)]
+pub struct EncMacKeys {
+  /// Encryption key (32 bytes)
+  pub enc_key: Buffer,
+  /// MAC key (32 bytes)
+  pub mac_key: Buffer,
+}
+
+/// Key confirmation tags, from `derive_confirmation_tags`
+#[napi(object)]
+pub struct ConfirmationTags {
+  /// Tag the initiator sends to prove it holds the secret (32 bytes)
+  pub initiator_tag: Buffer,
+  /// Tag the responder sends to prove it holds the secret (32 bytes)
+  pub responder_tag: Buffer,
+}
+
+/// Encapsulation whose shared secret is wrapped, from `mlkem768_encapsulate_wrapped`
+#[napi(object)]
+pub struct WrappedEncapsulation {
+  /// ML-KEM-768 ciphertext for the recipient (1088 bytes)
+  pub ciphertext: Buffer,
+  /// Shared secret wrapped under the caller's key: nonce || ciphertext || tag (60 bytes)
+  pub wrapped_secret: Buffer,
+}
+
+/// Group secret encapsulated to every member of a group
+#[napi(object)]
+pub struct GroupEncapsulation {
+  /// ML-KEM-768 ciphertext for each recipient, in input order
+  pub per_recipient_ciphertexts: Vec<Buffer>,
+  /// Group secret wrapped for each recipient, in input order
+  pub wrapped_group_secrets: Vec<Buffer>,
+  /// The group secret every recipient recovers (32 bytes)
+  pub group_secret: Buffer,
+}
+
+/// Derive the AES-256-GCM key that wraps a group secret for one recipient
+fn group_wrap_key(shared_secret: &[u8]) -> [u8; 32] {
+  let mut key = [0u8; 32];
+  Hkdf::<Sha256>::new(None, shared_secret)
+    .expand(b"qudag-napi group key wrap v1", &mut key)
+    .expect("32 bytes is a valid HKDF-SHA256 output length");
+  key
+}
+
+/// Derive the AES-256-GCM key used by `hybrid_encrypt` from a KEM shared secret
+fn hybrid_aead_key(shared_secret: &[u8]) -> Result<[u8; 32]> {
+  if shared_secret.len() != 32 {
+    return Err(Error::from_reason(format!(
+      "Invalid shared secret length: expected 32 bytes, got {}",
+      shared_secret.len()
+    )));
+  }
+  Ok(blake3::derive_key(HYBRID_AEAD_CONTEXT, shared_secret))
+}
+
+fn parse_nonce(nonce: &[u8]) -> Result<[u8; 12]> {
+  nonce
+    .try_into()
+    .map_err(|_| Error::from_reason(format!(
+      "Invalid nonce length: expected 12 bytes, got {}",
+      nonce.len()
+    )))
+}
+
+/// Encrypt `plaintext` under `key` with an explicit nonce, returning ciphertext || tag
+fn aead_seal_raw(key: &[u8; 32], nonce: &[u8; 12], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+  Aes256Gcm::new(key.into())
+    .encrypt(Nonce::from_slice(nonce), Payload { msg: plaintext, aad })
+    .map_err(|_| Error::from_reason("Encryption failed"))
+}
+
+/// Decrypt a ciphertext || tag blob produced by `aead_seal_raw`
+fn aead_open_raw(key: &[u8; 32], nonce: &[u8; 12], sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+  Aes256Gcm::new(key.into())
+    .decrypt(Nonce::from_slice(nonce), Payload { msg: sealed, aad })
+    .map_err(|_| Error::from_reason("Decryption failed: authentication tag mismatch"))
+}
+
+/// Encrypt `plaintext` under `key` with a random nonce, returning nonce || ciphertext || tag
+pub(crate) fn aead_seal(key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+  let mut nonce = [0u8; 12];
+  OsRng.fill_bytes(&mut nonce);
+
+  let sealed = aead_seal_raw(key, &nonce, plaintext, aad)?;
+
+  let mut out = nonce.to_vec();
+  out.extend_from_slice(&sealed);
+  Ok(out)
+}
+
+/// Decrypt a nonce || ciphertext || tag blob produced by `aead_seal`
+pub(crate) fn aead_open(key: &[u8; 32], blob: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+  if blob.len() < 12 + 16 {
+    return Err(Error::from_reason(format!(
+      "Invalid ciphertext length: expected at least 28 bytes, got {}",
+      blob.len()
+    )));
+  }
+
+  let (nonce, sealed) = blob.split_at(12);
+  aead_open_raw(key, &parse_nonce(nonce)?, sealed, aad)
+}
+
+/// Encapsulate to `public_key` and seal `plaintext` in the hybrid layout
+fn hybrid_seal(public_key: Buffer, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+  let encapsulated = mlkem768_encapsulate(public_key)?;
+  let key = hybrid_aead_key(encapsulated.shared_secret.as_ref())?;
+  let sealed = aead_seal(&key, plaintext, aad)?;
+
+  let mut out = Vec::with_capacity(1 + encapsulated.ciphertext.len() + sealed.len());
+  out.push(HYBRID_VERSION);
+  out.extend_from_slice(&encapsulated.ciphertext);
+  out.extend_from_slice(&sealed);
+  Ok(out)
+}
+
+/// Reject hybrid-layout blobs whose version byte this build doesn't know
+fn check_hybrid_version(ciphertext: &[u8]) -> Result<()> {
+  match ciphertext.first() {
+    Some(&HYBRID_VERSION) => Ok(()),
+    Some(&version) => Err(Error::from_reason(format!(
+      "Unsupported hybrid ciphertext version: {} (expected {})",
+      version, HYBRID_VERSION
+    ))),
+    None => Err(Error::from_reason("Invalid hybrid ciphertext: empty")),
+  }
+}
+
+/// Decapsulate with `secret_key` and open a hybrid-layout ciphertext
+fn hybrid_open(secret_key: Buffer, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+  if ciphertext.len() < HYBRID_MIN_LEN {
+    return Err(Error::from_reason(format!(
+      "Invalid hybrid ciphertext length: expected at least {} bytes, got {}",
+      HYBRID_MIN_LEN,
+      ciphertext.len()
+    )));
+  }
+  check_hybrid_version(ciphertext)?;
+
+  let (kem_ciphertext, sealed) = ciphertext[1..].split_at(1088);
+  let shared_secret = mlkem768_decapsulate(kem_ciphertext.to_vec().into(), secret_key)?;
+  let key = hybrid_aead_key(shared_secret.as_ref())?;
+  aead_open(&key, sealed, aad)
+}
+
+/// Encrypt data to an ML-KEM-768 public key
+///
+/// Encapsulates a fresh shared secret, derives an AES-256-GCM key from it
+/// with BLAKE3 and encrypts under a random 12-byte nonce. Random nonces are
+/// safe here because every call uses a new key; for many messages under one
+/// shared secret use `aead_seal_with_nonce` with a `NonceTracker`.
+///
+/// # Arguments
+/// * `public_key` - Recipient's ML-KEM-768 public key (1184 bytes)
+/// * `plaintext` - Data to encrypt
+///
+/// # Returns
+/// version || KEM ciphertext || nonce || AEAD ciphertext || tag
+#[napi]
+pub fn hybrid_encrypt(public_key: Buffer, plaintext: Buffer) -> Result<Buffer> {
+  Ok(hybrid_seal(public_key, plaintext.as_ref(), &[])?.into())
+}
+
+/// Decrypt data produced by `hybrid_encrypt`
+///
+/// # Arguments
+/// * `secret_key` - Recipient's ML-KEM-768 secret key (2400 bytes)
+/// * `ciphertext` - Output of `hybrid_encrypt`
+///
+/// # Returns
+/// Decrypted plaintext
+#[napi]
+pub fn hybrid_decrypt(secret_key: Buffer, ciphertext: Buffer) -> Result<Buffer> {
+  Ok(hybrid_open(secret_key, ciphertext.as_ref(), &[])?.into())
+}
+
+/// Encrypt data to an ML-KEM-768 public key deterministically
+///
+/// Convergent encryption for content-addressed storage: the same public
+/// key, plaintext and `key_commitment` always give the same ciphertext, so
+/// duplicates can be detected and stored once. The KEM coins and AES-GCM
+/// nonce are derived with BLAKE3 from all three inputs, and the output opens
+/// with `hybrid_decrypt`.
+///
+/// Privacy tradeoff: anyone who sees two ciphertexts learns whether they
+/// hold the same plaintext, and anyone who knows `key_commitment` can
+/// confirm a guessed plaintext by re-encrypting it. Keep `key_commitment`
+/// secret (e.g. one per tenant), and don't use this for low-entropy data
+/// such as passwords or short fields; use `hybrid_encrypt` when
+/// deduplication is not needed.
+///
+/// # Arguments
+/// * `public_key` - Recipient's ML-KEM-768 public key (1184 bytes)
+/// * `plaintext` - Data to encrypt
+/// * `key_commitment` - Secret convergence key (32 bytes)
+///
+/// # Returns
+/// version || KEM ciphertext || nonce || AEAD ciphertext || tag
+#[napi]
+pub fn hybrid_encrypt_deterministic(public_key: Buffer, plaintext: Buffer, key_commitment: Buffer) -> Result<Buffer> {
+  if key_commitment.len() != 32 {
+    return Err(Error::from_reason(format!(
+      "Invalid key commitment length: expected 32 bytes, got {}",
+      key_commitment.len()
+    )));
+  }
+
+  let coins = hash_length_prefixed(CONVERGENT_COINS_CONTEXT, [&key_commitment[..], &public_key[..], &plaintext[..]]);
+  let digest = hash_length_prefixed(CONVERGENT_NONCE_CONTEXT, [&key_commitment[..], &plaintext[..]]);
+  let nonce: [u8; 12] = digest[..12].try_into().expect("digest is 32 bytes");
+
+  let encapsulated = mlkem768_encapsulate_deterministic(public_key, coins.to_vec().into())?;
+  let key = hybrid_aead_key(encapsulated.shared_secret.as_ref())?;
+  let sealed = aead_seal_raw(&key, &nonce, plaintext.as_ref(), &[])?;
+
+  let mut out = Vec::with_capacity(1 + encapsulated.ciphertext.len() + nonce.len() + sealed.len());
+  out.push(HYBRID_VERSION);
+  out.extend_from_slice(&encapsulated.ciphertext);
+  out.extend_from_slice(&nonce);
+  out.extend_from_slice(&sealed);
+  Ok(out.into())
+}
+
+/// Encrypt data to an ML-KEM-768 public key, hiding its exact length
+///
+/// The plaintext is length-prefixed and zero-padded to a multiple of
+/// `pad_to` bytes before encryption, so every plaintext in the same block
+/// produces a ciphertext of the same size. The four-byte length prefix
+/// counts towards the padded size.
+///
+/// # Arguments
+/// * `public_key` - Recipient's ML-KEM-768 public key (1184 bytes)
+/// * `plaintext` - Data to encrypt
+/// * `pad_to` - Block size in bytes (1 to 65536)
+///
+/// # Returns
+/// Hybrid-layout blob for `hybrid_decrypt_padded`
+#[napi]
+pub fn hybrid_encrypt_padded(public_key: Buffer, plaintext: Buffer, pad_to: u32) -> Result<Buffer> {
+  if pad_to == 0 {
+    return Err(Error::from_reason("Padding block size must be at least 1 byte"));
+  }
+  if pad_to > MAX_PAD_BLOCK {
+    return Err(Error::from_reason(format!(
+      "Padding block size too large: at most {} bytes, got {}",
+      MAX_PAD_BLOCK, pad_to
+    )));
+  }
+  let length = u32::try_from(plaintext.len())
+    .map_err(|_| Error::from_reason("Plaintext too long to pad"))?;
+
+  // The padded size must itself fit in u32 so the length prefix scheme
+  // and `hybrid_decrypt_padded` can represent it
+  let padded_len = (4 + plaintext.len() as u64).div_ceil(pad_to as u64) * pad_to as u64;
+  let padded_len = u32::try_from(padded_len)
+    .map_err(|_| Error::from_reason("Plaintext too long to pad"))? as usize;
+  let mut padded = Vec::with_capacity(padded_len);
+  padded.extend_from_slice(&length.to_le_bytes());
+  padded.extend_from_slice(&plaintext);
+  padded.resize(padded_len, 0);
+
+  Ok(hybrid_seal(public_key, &padded, PADDED_AAD)?.into())
+}
+
+/// Decrypt data produced by `hybrid_encrypt_padded`
+///
+/// # Arguments
+/// * `secret_key` - Recipient's ML-KEM-768 secret key (2400 bytes)
+/// * `ciphertext` - Output of `hybrid_encrypt_padded`
+///
+/// # Returns
+/// The original plaintext with the padding removed
+#[napi]
+pub fn hybrid_decrypt_padded(secret_key: Buffer, ciphertext: Buffer) -> Result<Buffer> {
+  let padded = hybrid_open(secret_key, ciphertext.as_ref(), PADDED_AAD)?;
+
+  let invalid = || Error::from_reason("Invalid padded plaintext");
+  let (prefix, body) = padded.split_first_chunk::<4>().ok_or_else(invalid)?;
+  let length = u32::from_le_bytes(*prefix) as usize;
+  if length > body.len() || body[length..].iter().any(|&b| b != 0) {
+    return Err(invalid());
+  }
+
+  Ok(body[..length].to_vec().into())
+}
+
+/// Read the format version byte of a hybrid ciphertext
+///
+/// # Arguments
+/// * `blob` - Output of `hybrid_encrypt` (or another hybrid-layout blob)
+///
+/// # Returns
+/// The version byte; errors on an empty blob. The version is not checked
+/// against the versions this build supports.
+#[napi]
+pub fn hybrid_ciphertext_version(blob: Buffer) -> Result<u8> {
+  blob
+    .first()
+    .copied()
+    .ok_or_else(|| Error::from_reason("Invalid hybrid ciphertext: empty"))
+}
+
+/// Decrypt data produced by `hybrid_encrypt` without throwing
+///
+/// Intended for network code handling partial or corrupted frames. Instead
+/// of an exception, failures are reported in `error` as one of:
+/// - "truncated": shorter than the 1117-byte minimum, e.g. a partial frame
+/// - "unsupported_version": the version byte is not one this build reads
+/// - "invalid_key": the secret key is not 2400 bytes
+/// - "authentication_failed": well-formed, but the tag did not verify
+///   (tampered data, wrong recipient, or not a `hybrid_encrypt` payload)
+///
+/// # Arguments
+/// * `secret_key` - Recipient's ML-KEM-768 secret key (2400 bytes)
+/// * `ciphertext` - Output of `hybrid_encrypt`
+///
+/// # Returns
+/// HybridDecryptResult with `ok` and either `plaintext` or `error`
+#[napi]
+pub fn hybrid_decrypt_safe(secret_key: Buffer, ciphertext: Buffer) -> HybridDecryptResult {
+  let error = if ciphertext.len() < HYBRID_MIN_LEN {
+    "truncated"
+  } else if check_hybrid_version(ciphertext.as_ref()).is_err() {
+    "unsupported_version"
+  } else if secret_key.len() != 2400 {
+    "invalid_key"
+  } else {
+    match hybrid_open(secret_key, ciphertext.as_ref(), &[]) {
+      Ok(plaintext) => {
+        return HybridDecryptResult {
+          ok: true,
+          plaintext: Some(plaintext.into()),
+          error: None,
+        };
+      }
+      Err(_) => "authentication_failed",
+    }
+  };
+
+  HybridDecryptResult {
+    ok: false,
+    plaintext: None,
+    error: Some(error.to_string()),
+  }
+}
+
+/// Encrypt a secret key to a peer's ML-KEM-768 public key for delegation
+///
+/// A sealed box for keys: the blob has the `hybrid_encrypt` layout, but is
+/// authenticated with a key-export AAD so it can't be confused with (or
+/// opened as) an ordinary `hybrid_encrypt` message.
+///
+/// # Arguments
+/// * `secret_key` - Secret key to hand off (any algorithm)
+/// * `peer_public_kem` - Recipient's ML-KEM-768 public key (1184 bytes)
+///
+/// # Returns
+/// Encrypted key blob for `import_secret_from_peer`
+#[napi]
+pub fn export_secret_to_peer(secret_key: Buffer, peer_public_kem: Buffer) -> Result<Buffer> {
+  Ok(hybrid_seal(peer_public_kem, secret_key.as_ref(), SECRET_EXPORT_AAD)?.into())
+}
+
+/// Decrypt a secret key produced by `export_secret_to_peer`
+///
+/// # Arguments
+/// * `blob` - Output of `export_secret_to_peer`
+/// * `my_secret_kem` - Recipient's ML-KEM-768 secret key (2400 bytes)
+///
+/// # Returns
+/// The delegated secret key; errors if the blob was not encrypted to this key
+#[napi]
+pub fn import_secret_from_peer(blob: Buffer, my_secret_kem: Buffer) -> Result<Buffer> {
+  Ok(hybrid_open(my_secret_kem, blob.as_ref(), SECRET_EXPORT_AAD)?.into())
+}
+
+/// Sign a message and encrypt it to a recipient
+///
+/// Sign-then-encrypt: the ML-DSA-65 signature covers the recipient's public
+/// key and the message, so a recipient cannot re-encrypt the signed message
+/// to someone else as if the sender had addressed it to them. The signature
+/// travels inside the ciphertext, so relays learn nothing about the sender.
+///
+/// # Arguments
+/// * `message` - Message to send
+/// * `recipient_kem_public` - Recipient's ML-KEM-768 public key (1184 bytes)
+/// * `sender_dsa_secret` - Sender's ML-DSA-65 secret key (4032 bytes)
+///
+/// # Returns
+/// `QENV` envelope for `open_envelope`
+#[napi]
+pub fn seal_envelope(message: Buffer, recipient_kem_public: Buffer, sender_dsa_secret: Buffer) -> Result<Buffer> {
+  let signed = envelope_signed_digest(&recipient_kem_public, &message);
+  let signature = mldsa65_sign(signed.to_vec().into(), sender_dsa_secret)?;
+
+  let mut inner = Vec::with_capacity(signature.len() + message.len());
+  inner.extend_from_slice(&signature);
+  inner.extend_from_slice(&message);
+  let body = hybrid_seal(recipient_kem_public, &inner, ENVELOPE_AAD)?;
+  let body_len = u32::try_from(body.len()).map_err(|_| Error::from_reason("Message too long for an envelope"))?;
+
+  let mut out = Vec::with_capacity(ENVELOPE_HEADER_LEN + body.len() + 32);
+  out.extend_from_slice(ENVELOPE_MAGIC);
+  out.push(ENVELOPE_VERSION);
+  out.extend_from_slice(&body_len.to_le_bytes());
+  out.extend_from_slice(&body);
+  let checksum = blake3::hash(&out);
+  out.extend_from_slice(checksum.as_bytes());
+  Ok(out.into())
+}
+
+/// Decrypt an envelope from `seal_envelope` and verify its signature
+///
+/// # Arguments
+/// * `envelope` - `QENV` envelope
+/// * `recipient_kem_secret` - Recipient's ML-KEM-768 secret key (2400 bytes)
+/// * `sender_dsa_public` - Expected sender's ML-DSA-65 public key (1952 bytes)
+///
+/// # Returns
+/// The message; errors on a corrupt envelope, the wrong recipient, or a
+/// signature that does not verify under `sender_dsa_public`
+#[napi]
+pub fn open_envelope(envelope: Buffer, recipient_kem_secret: Buffer, sender_dsa_public: Buffer) -> Result<Buffer> {
+  if !envelope_integrity_ok(envelope.clone()) {
+    return Err(Error::from_reason("Invalid envelope: structure or checksum check failed"));
+  }
+
+  let recipient_kem_public = mlkem768_public_from_secret(&recipient_kem_secret)?;
+  let body = &envelope[ENVELOPE_HEADER_LEN..envelope.len() - 32];
+  let inner = hybrid_open(recipient_kem_secret, body, ENVELOPE_AAD)?;
+
+  let (signature, message) = inner.split_at(3309);
+  let signed = envelope_signed_digest(&recipient_kem_public, message);
+  if !mldsa65_verify(signed.to_vec().into(), signature.to_vec().into(), sender_dsa_public)? {
+    return Err(Error::from_reason("Envelope signature verification failed"));
+  }
+  Ok(message.to_vec().into())
+}
+
+/// Check an envelope's structure without any keys
+///
+/// For relays triaging stored or forwarded envelopes: checks the magic,
+/// version, length field, the embedded hybrid header and minimum size, and
+/// the BLAKE3 checksum. The checksum is unkeyed, so it catches truncation
+/// and accidental corruption but not deliberate tampering; only
+/// `open_envelope` authenticates the contents.
+///
+/// # Arguments
+/// * `envelope` - `QENV` envelope
+///
+/// # Returns
+/// true if the envelope is structurally sound; false otherwise
+#[napi]
+pub fn envelope_integrity_ok(envelope: Buffer) -> bool {
+  let bytes = envelope.as_ref();
+  if bytes.len() < ENVELOPE_HEADER_LEN + 32 || &bytes[..4] != ENVELOPE_MAGIC || bytes[4] != ENVELOPE_VERSION {
+    return false;
+  }
+
+  let body_len = u32::from_le_bytes(bytes[5..9].try_into().expect("slice is 4 bytes")) as usize;
+  if body_len != bytes.len() - ENVELOPE_HEADER_LEN - 32 || body_len < HYBRID_MIN_LEN + 3309 {
+    return false;
+  }
+
+  let (covered, checksum) = bytes.split_at(bytes.len() - 32);
+  check_hybrid_version(&covered[ENVELOPE_HEADER_LEN..]).is_ok() && blake3::hash(covered).as_bytes() == checksum
+}
+
+fn envelope_signed_digest(recipient_kem_public: &[u8], message: &[u8]) -> [u8; 32] {
+  hash_length_prefixed(ENVELOPE_SIGNATURE_CONTEXT, [recipient_kem_public, message])
+}
+
+/// Derive the AES-256-GCM key `hybrid_encrypt` uses from a KEM shared secret
+///
+/// This is a stable part of the hybrid format: the key is
+/// `BLAKE3-derive_key("qudag-napi hybrid aead key v1", shared_secret)`, so
+/// third-party implementations can reproduce it with any BLAKE3 library.
+///
+/// # Arguments
+/// * `shared_secret` - ML-KEM shared secret (32 bytes)
+///
+/// # Returns
+/// 32-byte AES-256-GCM key
+#[napi]
+pub fn derive_aead_key(shared_secret: Buffer) -> Result<Buffer> {
+  Ok(hybrid_aead_key(shared_secret.as_ref())?.to_vec().into())
+}
+
+/// Derive separate encryption and MAC keys from a KEM shared secret
+///
+/// For encrypt-then-MAC constructions that must not reuse one key for both
+/// roles. Each key is `BLAKE3-derive_key` of the shared secret under its own
+/// context ("qudag-napi encrypt-then-mac enc key v1" and
+/// "qudag-napi encrypt-then-mac mac key v1"), so knowing one key reveals
+/// nothing about the other.
+///
+/// # Arguments
+/// * `shared_secret` - ML-KEM shared secret (32 bytes)
+///
+/// # Returns
+/// EncMacKeys with two 32-byte keys
+#[napi]
+pub fn derive_enc_mac_keys(shared_secret: Buffer) -> Result<EncMacKeys> {
+  if shared_secret.len() != 32 {
+    return Err(Error::from_reason(format!(
+      "Invalid shared secret length: expected 32 bytes, got {}",
+      shared_secret.len()
+    )));
+  }
+
+  Ok(EncMacKeys {
+    enc_key: blake3::derive_key(ENC_KEY_CONTEXT, &shared_secret).to_vec().into(),
+    mac_key: blake3::derive_key(MAC_KEY_CONTEXT, &shared_secret).to_vec().into(),
+  })
+}
+
+/// Derive key confirmation tags for both directions of a handshake
+///
+/// Each side sends its own tag and checks the other's with
+/// `verify_initiator_tag` / `verify_responder_tag`. The tags come from
+/// separate `derive_key` contexts, so a tag reflected back at its sender
+/// does not verify as the peer's, and since every handshake has a fresh
+/// shared secret, tags from an earlier session do not verify either.
+///
+/// # Arguments
+/// * `shared_secret` - ML-KEM shared secret (32 bytes)
+///
+/// # Returns
+/// ConfirmationTags with the initiator and responder tags
+#[napi]
+pub fn derive_confirmation_tags(shared_secret: Buffer) -> Result<ConfirmationTags> {
+  Ok(ConfirmationTags {
+    initiator_tag: confirmation_tag(INITIATOR_CONFIRM_CONTEXT, &shared_secret)?.as_bytes().to_vec().into(),
+    responder_tag: confirmation_tag(RESPONDER_CONFIRM_CONTEXT, &shared_secret)?.as_bytes().to_vec().into(),
+  })
+}
+
+/// Check the initiator's confirmation tag, in constant time
+///
+/// # Arguments
+/// * `shared_secret` - ML-KEM shared secret (32 bytes)
+/// * `tag` - Tag received from the initiator
+///
+/// # Returns
+/// true if the tag matches; false on mismatch or a tag that is not 32 bytes
+#[napi]
+pub fn verify_initiator_tag(shared_secret: Buffer, tag: Buffer) -> Result<bool> {
+  verify_confirmation_tag(INITIATOR_CONFIRM_CONTEXT, &shared_secret, &tag)
+}
+
+/// Check the responder's confirmation tag, in constant time
+///
+/// # Arguments
+/// * `shared_secret` - ML-KEM shared secret (32 bytes)
+/// * `tag` - Tag received from the responder
+///
+/// # Returns
+/// true if the tag matches; false on mismatch or a tag that is not 32 bytes
+#[napi]
+pub fn verify_responder_tag(shared_secret: Buffer, tag: Buffer) -> Result<bool> {
+  verify_confirmation_tag(RESPONDER_CONFIRM_CONTEXT, &shared_secret, &tag)
+}
+
+fn confirmation_tag(context: &str, shared_secret: &[u8]) -> Result<blake3::Hash> {
+  if shared_secret.len() != 32 {
+    return Err(Error::from_reason(format!(
+      "Invalid shared secret length: expected 32 bytes, got {}",
+      shared_secret.len()
+    )));
+  }
+  Ok(blake3::derive_key(context, shared_secret).into())
+}
+
+fn verify_confirmation_tag(context: &str, shared_secret: &[u8], tag: &[u8]) -> Result<bool> {
+  let expected = confirmation_tag(context, shared_secret)?;
+  let Ok(tag) = <[u8; 32]>::try_from(tag) else {
+    return Ok(false);
+  };
+  Ok(expected == tag)
+}
+
+/// Generate a fresh random 32-byte symmetric key
+///
+/// Drawn from the operating system CSPRNG. The key can be passed wherever
+/// the AEAD helpers (`aead_seal_with_nonce`, `aead_open_with_nonce`,
+/// `NonceTracker`) take a `shared_secret`, for data encrypted without a KEM
+/// exchange.
+///
+/// # Returns
+/// 32-byte key
+#[napi]
+pub fn generate_symmetric_key() -> Buffer {
+  let mut key = [0u8; 32];
+  OsRng.fill_bytes(&mut key);
+  key.to_vec().into()
+}
+
+/// Encrypt data under a KEM shared secret with a caller-chosen nonce
+///
+/// Uses the same key derivation as `hybrid_encrypt`. The caller is
+/// responsible for never reusing a nonce with the same shared secret, which
+/// would break AES-GCM confidentiality and integrity; deterministic counters
+/// are the usual choice, and `NonceTracker` can enforce uniqueness.
+///
+/// # Arguments
+/// * `shared_secret` - ML-KEM shared secret (32 bytes)
+/// * `plaintext` - Data to encrypt
+/// * `nonce` - Unique nonce (12 bytes)
+/// * `aad` - Optional associated data to authenticate
+///
+/// # Returns
+/// AEAD ciphertext || tag (the nonce is not included)
+#[napi]
+pub fn aead_seal_with_nonce(shared_secret: Buffer, plaintext: Buffer, nonce: Buffer, aad: Option<Buffer>) -> Result<Buffer> {
+  let key = hybrid_aead_key(shared_secret.as_ref())?;
+  let nonce = parse_nonce(nonce.as_ref())?;
+  let aad = aad.as_ref().map_or(&[][..], |a| a.as_ref());
+  Ok(aead_seal_raw(&key, &nonce, plaintext.as_ref(), aad)?.into())
+}
+
+/// Decrypt data produced by `aead_seal_with_nonce`
+///
+/// # Arguments
+/// * `shared_secret` - ML-KEM shared secret (32 bytes)
+/// * `ciphertext` - AEAD ciphertext || tag
+/// * `nonce` - Nonce used for encryption (12 bytes)
+/// * `aad` - Associated data used for encryption, if any
+///
+/// # Returns
+/// Decrypted plaintext
+#[napi]
+pub fn aead_open_with_nonce(shared_secret: Buffer, ciphertext: Buffer, nonce: Buffer, aad: Option<Buffer>) -> Result<Buffer> {
+  let key = hybrid_aead_key(shared_secret.as_ref())?;
+  let nonce = parse_nonce(nonce.as_ref())?;
+  let aad = aad.as_ref().map_or(&[][..], |a| a.as_ref());
+  Ok(aead_open_raw(&key, &nonce, ciphertext.as_ref(), aad)?.into())
+}
+
+/// MAC a handshake transcript under a key derived from a KEM shared secret
+///
+/// The MAC key is `derive_key("qudag-napi transcript mac key v1",
+/// shared_secret)`; the MAC is keyed BLAKE3 over the transcript elements,
+/// each prefixed with its length as a little-endian u64, so element
+/// boundaries are unambiguous. Both sides compute it over the messages they
+/// saw and compare to confirm they agree on the transcript.
+///
+/// # Arguments
+/// * `shared_secret` - ML-KEM shared secret (32 bytes)
+/// * `transcript` - Handshake messages, in order
+///
+/// # Returns
+/// 32-byte transcript MAC
+#[napi]
+pub fn transcript_mac(shared_secret: Buffer, transcript: Vec<Buffer>) -> Result<Buffer> {
+  Ok(compute_transcript_mac(shared_secret.as_ref(), &transcript)?.as_bytes().to_vec().into())
+}
+
+/// Verify a MAC produced by `transcript_mac`, in constant time
+///
+/// # Arguments
+/// * `shared_secret` - ML-KEM shared secret (32 bytes)
+/// * `transcript` - Handshake messages, in order
+/// * `mac` - MAC to check
+///
+/// # Returns
+/// true if the MAC matches; false on mismatch or a MAC that is not 32 bytes
+#[napi]
+pub fn verify_transcript_mac(shared_secret: Buffer, transcript: Vec<Buffer>, mac: Buffer) -> Result<bool> {
+  let expected = compute_transcript_mac(shared_secret.as_ref(), &transcript)?;
+  let Ok(mac) = <[u8; 32]>::try_from(mac.as_ref()) else {
+    return Ok(false);
+  };
+  Ok(expected == mac)
+}
+
+/// Sign a handshake transcript with ML-DSA-65
+///
+/// Asymmetric counterpart of `transcript_mac` for when the signer's identity
+/// matters: the length-prefixed transcript is hashed with BLAKE3 under its
+/// own context and the 32-byte digest is signed, so anyone with the public
+/// key can check who vouched for the transcript.
+///
+/// # Arguments
+/// * `transcript` - Handshake messages, in order
+/// * `dsa_secret` - ML-DSA-65 secret key (4032 bytes)
+///
+/// # Returns
+/// Signature (3309 bytes)
+#[napi]
+pub fn sign_transcript(transcript: Vec<Buffer>, dsa_secret: Buffer) -> Result<Buffer> {
+  mldsa65_sign(signed_transcript_digest(&transcript).to_vec().into(), dsa_secret)
+}
+
+/// Verify a signature produced by `sign_transcript`
+///
+/// # Arguments
+/// * `transcript` - Handshake messages, in the order they were signed
+/// * `signature` - Signature to verify (3309 bytes)
+/// * `dsa_public` - ML-DSA-65 public key (1952 bytes)
+///
+/// # Returns
+/// true if the signature covers exactly these messages in this order
+#[napi]
+pub fn verify_transcript(transcript: Vec<Buffer>, signature: Buffer, dsa_public: Buffer) -> Result<bool> {
+  mldsa65_verify(signed_transcript_digest(&transcript).to_vec().into(), signature, dsa_public)
+}
+
+fn signed_transcript_digest(transcript: &[Buffer]) -> [u8; 32] {
+  hash_length_prefixed(SIGNED_TRANSCRIPT_CONTEXT, transcript.iter().map(|m| m.as_ref()))
+}
+
+fn compute_transcript_mac(shared_secret: &[u8], transcript: &[Buffer]) -> Result<blake3::Hash> {
+  if shared_secret.len() != 32 {
+    return Err(Error::from_reason(format!(
+      "Invalid shared secret length: expected 32 bytes, got {}",
+      shared_secret.len()
+    )));
+  }
+
+  let key = blake3::derive_key(TRANSCRIPT_MAC_CONTEXT, shared_secret);
+  let mut hasher = blake3::Hasher::new_keyed(&key);
+  for element in transcript {
+    hasher.update(&(element.len() as u64).to_le_bytes());
+    hasher.update(element);
+  }
+  Ok(hasher.finalize())
+}
+
+/// Derive a public session identifier from a KEM shared secret
+///
+/// `sid:` followed by the hex of the first 16 bytes of
+/// `derive_key("session-id", shared_secret)`. Both ends of a handshake get
+/// the same id, and it can be logged or sent in the clear: BLAKE3 is
+/// one-way and the context separates it from every other key derived from
+/// the secret.
+///
+/// # Arguments
+/// * `shared_secret` - KEM shared secret (32 bytes)
+///
+/// # Returns
+/// Session id string, `sid:` plus 32 hex characters
+#[napi]
+pub fn session_id(shared_secret: Buffer) -> Result<String> {
+  if shared_secret.len() != 32 {
+    return Err(Error::from_reason(format!(
+      "Invalid shared secret length: expected 32 bytes, got {}",
+      shared_secret.len()
+    )));
+  }
+
+  let derived = blake3::derive_key(SESSION_ID_CONTEXT, shared_secret.as_ref());
+  Ok(format!("sid:{}", hex::encode(&derived[..16])))
+}
+
+/// Combine an ML-KEM and an X25519 shared secret into one key
+///
+/// Both secrets are length-prefixed and hashed together with BLAKE3 in
+/// `derive_key` mode, so the output stays secret as long as either input
+/// does: a break of ML-KEM or of X25519 alone does not reveal the combined
+/// key. The same inputs always give the same key.
+///
+/// # Arguments
+/// * `mlkem_secret` - ML-KEM shared secret (32 bytes)
+/// * `x25519_secret` - X25519 shared secret (32 bytes)
+///
+/// # Returns
+/// 32-byte combined key
+#[napi]
+pub fn hybrid_kem_combine(mlkem_secret: Buffer, x25519_secret: Buffer) -> Result<Buffer> {
+  for (name, secret) in [("ML-KEM", &mlkem_secret), ("X25519", &x25519_secret)] {
+    if secret.len() != 32 {
+      return Err(Error::from_reason(format!(
+        "Invalid {} shared secret length: expected 32 bytes, got {}",
+        name,
+        secret.len()
+      )));
+    }
+  }
+
+  let combined = hash_length_prefixed(KEM_COMBINER_CONTEXT, [mlkem_secret.as_ref(), x25519_secret.as_ref()]);
+  Ok(combined.to_vec().into())
+}
+
+pub use nonce_tracker_class::NonceTracker;
+
+#[allow(missing_docs)]
+mod nonce_tracker_class {
+  use super::*;
+
+  /// In-process guard against AES-GCM nonce reuse
+  ///
+  /// Remembers every nonce sealed through it, per derived key, and refuses to
+  /// seal twice with the same key and nonce. Keys are tracked by a BLAKE3
+  /// digest of the AEAD key, never the shared secret itself. Memory grows with
+  /// the number of sealed messages, and state is lost on restart, so persistent
+  /// counters are still needed across processes.
+  #[napi]
+  pub struct NonceTracker {
+    pub(super) used: HashMap<[u8; 32], HashSet<[u8; 12]>>,
+  }
+}
+
+#[napi]
+impl NonceTracker {
+  /// Create an empty tracker
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Self { used: HashMap::new() }
+  }
+
+  /// Seal like `aead_seal_with_nonce`, erroring if the nonce was already used with this key
+  #[napi]
+  pub fn seal(&mut self, shared_secret: Buffer, plaintext: Buffer, nonce: Buffer, aad: Option<Buffer>) -> Result<Buffer> {
+    let key = hybrid_aead_key(shared_secret.as_ref())?;
+    let nonce_array = parse_nonce(nonce.as_ref())?;
+
+    let key_id = *blake3::hash(&key).as_bytes();
+    if !self.used.entry(key_id).or_default().insert(nonce_array) {
+      return Err(Error::from_reason("Nonce reuse detected: this nonce was already used with this key"));
+    }
+
+    aead_seal_with_nonce(shared_secret, plaintext, nonce, aad)
+  }
+}
+
+impl Default for NonceTracker {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Encapsulate one random group secret to several ML-KEM-768 public keys
+///
+/// A fresh 32-byte group secret is generated, then for each recipient an
+/// ML-KEM encapsulation is performed and its shared secret (via HKDF-SHA256)
+/// AES-256-GCM-wraps the group secret. The KEM ciphertext is bound as
+/// associated data, so wrapped secrets cannot be swapped between recipients.
+///
+/// # Arguments
+/// * `public_keys` - ML-KEM-768 public keys (1184 bytes each)
+///
+/// # Returns
+/// GroupEncapsulation with one ciphertext and wrapped secret per recipient
+#[napi]
+pub fn group_encapsulate(public_keys: Vec<Buffer>) -> Result<GroupEncapsulation> {
+  if public_keys.is_empty() {
+    return Err(Error::from_reason("At least one recipient public key is required"));
+  }
+
+  let mut group_secret = [0u8; 32];
+  OsRng.fill_bytes(&mut group_secret);
+
+  let mut per_recipient_ciphertexts = Vec::with_capacity(public_keys.len());
+  let mut wrapped_group_secrets = Vec::with_capacity(public_keys.len());
+  for (index, public_key) in public_keys.into_iter().enumerate() {
+    let encapsulated = mlkem768_encapsulate(public_key)
+      .map_err(|e| Error::from_reason(format!("Recipient {}: {}", index, e.reason)))?;
+
+    let key = group_wrap_key(encapsulated.shared_secret.as_ref());
+    let wrapped = aead_seal(&key, &group_secret, encapsulated.ciphertext.as_ref())?;
+
+    per_recipient_ciphertexts.push(encapsulated.ciphertext);
+    wrapped_group_secrets.push(wrapped.into());
+  }
+
+  Ok(GroupEncapsulation {
+    per_recipient_ciphertexts,
+    wrapped_group_secrets,
+    group_secret: group_secret.to_vec().into(),
+  })
+}
+
+/// Recover the group secret produced by `group_encapsulate`
+///
+/// # Arguments
+/// * `my_secret` - Recipient's ML-KEM-768 secret key (2400 bytes)
+/// * `my_ciphertext` - Recipient's entry from `per_recipient_ciphertexts`
+/// * `wrapped_group_secret` - Recipient's entry from `wrapped_group_secrets`
+///
+/// # Returns
+/// Group secret (32 bytes)
+#[napi]
+pub fn group_decapsulate(my_secret: Buffer, my_ciphertext: Buffer, wrapped_group_secret: Buffer) -> Result<Buffer> {
+  let shared_secret = mlkem768_decapsulate(my_ciphertext.clone(), my_secret)?;
+  let key = group_wrap_key(shared_secret.as_ref());
+  let group_secret = aead_open(&key, wrapped_group_secret.as_ref(), my_ciphertext.as_ref())?;
+  Ok(group_secret.into())
+}
+
+/// Derive a key bound to a shared secret and a group's membership
+///
+/// The fingerprints are lowercased, sorted and length-prefixed before
+/// hashing with the secret, so the key depends on who is in the group but
+/// not on the order members are listed. Adding, removing or replacing any
+/// member changes the key, so members who disagree about the membership
+/// fail to decrypt instead of silently talking to a different group.
+///
+/// # Arguments
+/// * `shared_secret` - Group secret, e.g. from `group_encapsulate` (32 bytes)
+/// * `member_fingerprints` - One fingerprint per member, e.g. from `quantum_fingerprint`
+///
+/// # Returns
+/// 32-byte key; errors on an empty or duplicated member list
+#[napi]
+pub fn group_key(shared_secret: Buffer, member_fingerprints: Vec<String>) -> Result<Buffer> {
+  if shared_secret.len() != 32 {
+    return Err(Error::from_reason(format!(
+      "Invalid shared secret length: expected 32 bytes, got {}",
+      shared_secret.len()
+    )));
+  }
+  if member_fingerprints.is_empty() {
+    return Err(Error::from_reason("Group must have at least one member"));
+  }
+
+  let mut members: Vec<String> = member_fingerprints.iter().map(|fp| fp.to_ascii_lowercase()).collect();
+  members.sort_unstable();
+  if let Some(pair) = members.windows(2).find(|pair| pair[0] == pair[1]) {
+    return Err(Error::from_reason(format!("Duplicate group member: {}", pair[0])));
+  }
+
+  let parts = std::iter::once(shared_secret.as_ref()).chain(members.iter().map(|fp| fp.as_bytes()));
+  Ok(hash_length_prefixed(GROUP_MEMBERSHIP_CONTEXT, parts).to_vec().into())
+}
+
+/// Encapsulate to an ML-KEM-768 public key, returning the secret wrapped
+///
+/// The shared secret is AES-256-GCM-wrapped under `wrapping_key` and the
+/// native copy is zeroized, so this call returns only the wrapped form, never
+/// the raw secret. `unwrap_secret` does return the plaintext secret to its
+/// caller; keeping it off the JS heap means calling that only from the
+/// native code or enclave that holds `wrapping_key`.
+///
+/// # Arguments
+/// * `public_key` - Recipient's ML-KEM-768 public key (1184 bytes)
+/// * `wrapping_key` - AES-256-GCM key, e.g. from `generate_symmetric_key` (32 bytes)
+///
+/// # Returns
+/// WrappedEncapsulation with the KEM ciphertext and the wrapped secret
+#[napi]
+pub fn mlkem768_encapsulate_wrapped(public_key: Buffer, wrapping_key: Buffer) -> Result<WrappedEncapsulation> {
+  let key = parse_wrapping_key(&wrapping_key)?;
+  let mut encapsulated = mlkem768_encapsulate(public_key)?;
+  let wrapped = aead_seal(&key, encapsulated.shared_secret.as_ref(), WRAPPED_SECRET_AAD);
+  encapsulated.shared_secret.as_mut().zeroize();
+
+  Ok(WrappedEncapsulation {
+    ciphertext: encapsulated.ciphertext,
+    wrapped_secret: wrapped?.into(),
+  })
+}
+
+/// Unwrap a shared secret from `mlkem768_encapsulate_wrapped`
+///
+/// Returns the raw shared secret to the caller; called from JS, the secret
+/// is then on the JS heap like any other Buffer.
+///
+/// # Arguments
+/// * `wrapped_secret` - `wrapped_secret` field of a WrappedEncapsulation
+/// * `wrapping_key` - Key the secret was wrapped under (32 bytes)
+///
+/// # Returns
+/// Shared secret (32 bytes); errors if the key is wrong or the blob was modified
+#[napi]
+pub fn unwrap_secret(wrapped_secret: Buffer, wrapping_key: Buffer) -> Result<Buffer> {
+  let key = parse_wrapping_key(&wrapping_key)?;
+  Ok(aead_open(&key, wrapped_secret.as_ref(), WRAPPED_SECRET_AAD)?.into())
+}
+
+fn parse_wrapping_key(wrapping_key: &[u8]) -> Result<[u8; 32]> {
+  wrapping_key.try_into().map_err(|_| {
+    Error::from_reason(format!(
+      "Invalid wrapping key length: expected 32 bytes, got {}",
+      wrapping_key.len()
+    ))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::crypto::mlkem768_generate_keypair;
+
+  #[test]
+  fn test_hybrid_encrypt_decrypt() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let plaintext = b"hybrid payload".to_vec();
+
+    let ciphertext = hybrid_encrypt(keypair.public_key, plaintext.clone().into()).unwrap();
+    assert_eq!(ciphertext.len(), 1 + 1088 + 12 + plaintext.len() + 16);
+    assert_eq!(hybrid_ciphertext_version(ciphertext.clone()).unwrap(), 1);
+
+    let decrypted = hybrid_decrypt(keypair.secret_key, ciphertext).unwrap();
+    assert_eq!(decrypted.as_ref(), plaintext.as_slice());
+  }
+
+  #[test]
+  fn test_hybrid_decrypt_rejects_tampering() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let mut ciphertext = hybrid_encrypt(keypair.public_key, b"payload".to_vec().into()).unwrap().to_vec();
+    let last = ciphertext.len() - 1;
+    ciphertext[last] ^= 1;
+
+    assert!(hybrid_decrypt(keypair.secret_key.clone(), ciphertext.into()).is_err());
+    assert!(hybrid_decrypt(keypair.secret_key, vec![0u8; 100].into()).is_err());
+  }
+
+  #[test]
+  fn test_hybrid_rejects_unknown_version() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let mut ciphertext = hybrid_encrypt(keypair.public_key, b"payload".to_vec().into()).unwrap().to_vec();
+    ciphertext[0] = 2;
+
+    assert_eq!(hybrid_ciphertext_version(ciphertext.clone().into()).unwrap(), 2);
+    let err = hybrid_decrypt(keypair.secret_key.clone(), ciphertext.clone().into()).err().unwrap();
+    assert!(err.reason.contains("Unsupported hybrid ciphertext version: 2"));
+
+    let result = hybrid_decrypt_safe(keypair.secret_key, ciphertext.into());
+    assert_eq!(result.error.as_deref(), Some("unsupported_version"));
+    assert!(hybrid_ciphertext_version(Vec::new().into()).is_err());
+  }
+
+  #[test]
+  fn test_hybrid_decrypt_safe() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let ciphertext = hybrid_encrypt(keypair.public_key, b"frame".to_vec().into()).unwrap();
+
+    let result = hybrid_decrypt_safe(keypair.secret_key.clone(), ciphertext.clone());
+    assert!(result.ok);
+    assert_eq!(result.plaintext.unwrap().as_ref(), b"frame");
+    assert!(result.error.is_none());
+
+    let partial = hybrid_decrypt_safe(keypair.secret_key.clone(), ciphertext[..500].to_vec().into());
+    assert!(!partial.ok);
+    assert!(partial.plaintext.is_none());
+    assert_eq!(partial.error.as_deref(), Some("truncated"));
+
+    let mut tampered = ciphertext.to_vec();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    let forged = hybrid_decrypt_safe(keypair.secret_key, tampered.into());
+    assert!(!forged.ok);
+    assert_eq!(forged.error.as_deref(), Some("authentication_failed"));
+
+    let bad_key = hybrid_decrypt_safe(vec![0u8; 10].into(), ciphertext);
+    assert_eq!(bad_key.error.as_deref(), Some("invalid_key"));
+  }
+
+  #[test]
+  fn test_export_import_secret_roundtrip() {
+    let peer = mlkem768_generate_keypair().unwrap();
+    let delegated = crate::crypto::mldsa65_generate_keypair().unwrap().secret_key;
+
+    let blob = export_secret_to_peer(delegated.clone(), peer.public_key).unwrap();
+    let imported = import_secret_from_peer(blob.clone(), peer.secret_key.clone()).unwrap();
+    assert_eq!(imported.as_ref(), delegated.as_ref());
+
+    // Export blobs are domain-separated from plain hybrid messages
+    assert!(hybrid_decrypt(peer.secret_key, blob).is_err());
+  }
+
+  #[test]
+  fn test_import_secret_rejects_wrong_recipient() {
+    let peer = mlkem768_generate_keypair().unwrap();
+    let other = mlkem768_generate_keypair().unwrap();
+
+    let blob = export_secret_to_peer(vec![9u8; 64].into(), peer.public_key).unwrap();
+    assert!(import_secret_from_peer(blob, other.secret_key).is_err());
+  }
+
+  #[test]
+  fn test_hybrid_encrypt_deterministic() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let commitment: Buffer = vec![7u8; 32].into();
+
+    let first = hybrid_encrypt_deterministic(keypair.public_key.clone(), b"block".to_vec().into(), commitment.clone()).unwrap();
+    let second = hybrid_encrypt_deterministic(keypair.public_key.clone(), b"block".to_vec().into(), commitment.clone()).unwrap();
+    assert_eq!(first.as_ref(), second.as_ref());
+    assert_eq!(first[0], HYBRID_VERSION);
+    assert_eq!(hybrid_decrypt(keypair.secret_key.clone(), first.clone()).unwrap().as_ref(), b"block");
+
+    let other = hybrid_encrypt_deterministic(keypair.public_key.clone(), b"blocK".to_vec().into(), commitment).unwrap();
+    assert_ne!(first.as_ref(), other.as_ref());
+
+    let other_key = hybrid_encrypt_deterministic(keypair.public_key.clone(), b"block".to_vec().into(), vec![8u8; 32].into()).unwrap();
+    assert_ne!(first.as_ref(), other_key.as_ref());
+
+    assert!(hybrid_encrypt_deterministic(keypair.public_key, b"block".to_vec().into(), vec![7u8; 16].into()).is_err());
+  }
+
+  #[test]
+  fn test_envelope_roundtrip() {
+    let recipient = mlkem768_generate_keypair().unwrap();
+    let sender = crate::crypto::mldsa65_generate_keypair().unwrap();
+
+    let envelope = seal_envelope(b"wire transfer".to_vec().into(), recipient.public_key.clone(), sender.secret_key.clone()).unwrap();
+    assert!(envelope_integrity_ok(envelope.clone()));
+
+    let opened = open_envelope(envelope.clone(), recipient.secret_key.clone(), sender.public_key.clone()).unwrap();
+    assert_eq!(opened.as_ref(), b"wire transfer");
+
+    let impostor = crate::crypto::mldsa65_generate_keypair().unwrap();
+    assert!(open_envelope(envelope, recipient.secret_key, impostor.public_key).is_err());
+  }
+
+  #[test]
+  fn test_envelope_integrity_rejects_corruption() {
+    let recipient = mlkem768_generate_keypair().unwrap();
+    let sender = crate::crypto::mldsa65_generate_keypair().unwrap();
+    let envelope = seal_envelope(Vec::new().into(), recipient.public_key, sender.secret_key).unwrap();
+    assert!(envelope_integrity_ok(envelope.clone()));
+
+    assert!(!envelope_integrity_ok(envelope[..envelope.len() - 1].to_vec().into()));
+    assert!(!envelope_integrity_ok(envelope[..100].to_vec().into()));
+    assert!(!envelope_integrity_ok(Vec::new().into()));
+
+    for index in [0, 4, 6, 9, 2000, envelope.len() - 1] {
+      let mut corrupt = envelope.to_vec();
+      corrupt[index] ^= 1;
+      assert!(!envelope_integrity_ok(corrupt.into()), "flip at {} not detected", index);
+    }
+  }
+
+  #[test]
+  fn test_generate_symmetric_key() {
+    let first = generate_symmetric_key();
+    let second = generate_symmetric_key();
+    assert_eq!(first.len(), 32);
+    assert_ne!(first.as_ref(), second.as_ref());
+
+    // Usable directly with the AEAD helpers
+    let nonce: Buffer = vec![0u8; 12].into();
+    let sealed = aead_seal_with_nonce(first.clone(), b"data".to_vec().into(), nonce.clone(), None).unwrap();
+    assert_eq!(aead_open_with_nonce(first, sealed, nonce, None).unwrap().as_ref(), b"data");
+  }
+
+  #[test]
+  fn test_derive_aead_key_interop() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let encapsulated = mlkem768_encapsulate(keypair.public_key).unwrap();
+
+    // Build a hybrid payload by hand, as an external implementation would
+    let key = derive_aead_key(encapsulated.shared_secret).unwrap();
+    let cipher = Aes256Gcm::new_from_slice(key.as_ref()).unwrap();
+    let nonce = [5u8; 12];
+    let sealed = cipher.encrypt(Nonce::from_slice(&nonce), b"interop".as_ref()).unwrap();
+
+    let mut payload = vec![1u8];
+    payload.extend_from_slice(&encapsulated.ciphertext);
+    payload.extend_from_slice(&nonce);
+    payload.extend_from_slice(&sealed);
+
+    let decrypted = hybrid_decrypt(keypair.secret_key, payload.into()).unwrap();
+    assert_eq!(decrypted.as_ref(), b"interop");
+
+    assert!(derive_aead_key(vec![0u8; 16].into()).is_err());
+  }
+
+  #[test]
+  fn test_aead_with_nonce_roundtrip() {
+    let shared_secret: Buffer = vec![3u8; 32].into();
+    let nonce: Buffer = vec![0u8; 12].into();
+    let aad: Buffer = b"header".to_vec().into();
+
+    let sealed = aead_seal_with_nonce(shared_secret.clone(), b"msg".to_vec().into(), nonce.clone(), Some(aad.clone())).unwrap();
+    let opened = aead_open_with_nonce(shared_secret.clone(), sealed.clone(), nonce.clone(), Some(aad)).unwrap();
+    assert_eq!(opened.as_ref(), b"msg");
+
+    assert!(aead_open_with_nonce(shared_secret.clone(), sealed, nonce, None).is_err());
+    assert!(aead_seal_with_nonce(shared_secret, b"msg".to_vec().into(), vec![0u8; 8].into(), None).is_err());
+  }
+
+  #[test]
+  fn test_transcript_mac() {
+    let shared_secret: Buffer = vec![4u8; 32].into();
+    let transcript: Vec<Buffer> = vec![b"client hello".to_vec().into(), b"server hello".to_vec().into()];
+    let mac = transcript_mac(shared_secret.clone(), transcript.clone()).unwrap();
+    assert!(verify_transcript_mac(shared_secret.clone(), transcript, mac.clone()).unwrap());
+
+    // Moving a byte across an element boundary changes the MAC
+    let shifted: Vec<Buffer> = vec![b"client hell".to_vec().into(), b"oserver hello".to_vec().into()];
+    assert_ne!(transcript_mac(shared_secret.clone(), shifted.clone()).unwrap().as_ref(), mac.as_ref());
+    assert!(!verify_transcript_mac(shared_secret.clone(), shifted, mac.clone()).unwrap());
+
+    let other_key = transcript_mac(vec![5u8; 32].into(), vec![b"client hello".to_vec().into()]).unwrap();
+    assert_ne!(other_key.as_ref(), mac.as_ref());
+    assert!(!verify_transcript_mac(shared_secret, vec![], mac[..16].to_vec().into()).unwrap());
+  }
+
+  #[test]
+  fn test_sign_transcript() {
+    let keypair = crate::crypto::mldsa65_generate_keypair().unwrap();
+    let hello = |m: &[u8]| -> Buffer { m.to_vec().into() };
+    let transcript = vec![hello(b"client hello"), hello(b"server hello"), hello(b"finished")];
+
+    let signature = sign_transcript(transcript.clone(), keypair.secret_key.clone()).unwrap();
+    assert!(verify_transcript(transcript.clone(), signature.clone(), keypair.public_key.clone()).unwrap());
+
+    let reordered = vec![transcript[1].clone(), transcript[0].clone(), transcript[2].clone()];
+    assert!(!verify_transcript(reordered, signature.clone(), keypair.public_key.clone()).unwrap());
+
+    let shifted = vec![hello(b"client hell"), hello(b"oserver hello"), hello(b"finished")];
+    assert!(!verify_transcript(shifted, signature.clone(), keypair.public_key.clone()).unwrap());
+    assert!(!verify_transcript(transcript[..2].to_vec(), signature, keypair.public_key).unwrap());
+  }
+
+  #[test]
+  fn test_nonce_tracker_rejects_reuse() {
+    let mut tracker = NonceTracker::new();
+    let secret_a: Buffer = vec![1u8; 32].into();
+    let secret_b: Buffer = vec![2u8; 32].into();
+    let nonce: Buffer = vec![9u8; 12].into();
+
+    tracker.seal(secret_a.clone(), b"one".to_vec().into(), nonce.clone(), None).unwrap();
+    assert!(tracker.seal(secret_a.clone(), b"two".to_vec().into(), nonce.clone(), None).is_err());
+
+    // The same nonce under a different key is fine
+    tracker.seal(secret_b, b"three".to_vec().into(), nonce, None).unwrap();
+    tracker.seal(secret_a, b"four".to_vec().into(), vec![10u8; 12].into(), None).unwrap();
+  }
+
+  #[test]
+  fn test_group_encapsulate_three_recipients() {
+    let recipients: Vec<_> = (0..3).map(|_| mlkem768_generate_keypair().unwrap()).collect();
+    let group = group_encapsulate(
+      recipients.iter().map(|kp| kp.public_key.clone()).collect()
+    ).unwrap();
+
+    assert_eq!(group.per_recipient_ciphertexts.len(), 3);
+    assert_eq!(group.wrapped_group_secrets.len(), 3);
+    assert_eq!(group.group_secret.len(), 32);
+
+    for (i, recipient) in recipients.iter().enumerate() {
+      let recovered = group_decapsulate(
+        recipient.secret_key.clone(),
+        group.per_recipient_ciphertexts[i].clone(),
+        group.wrapped_group_secrets[i].clone()
+      ).unwrap();
+      assert_eq!(recovered.as_ref(), group.group_secret.as_ref());
+    }
+  }
+
+  #[test]
+  fn test_mlkem768_encapsulate_wrapped_roundtrip() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let wrapping_key = generate_symmetric_key();
+
+    let wrapped = mlkem768_encapsulate_wrapped(keypair.public_key, wrapping_key.clone()).unwrap();
+    assert_eq!(wrapped.ciphertext.len(), 1088);
+    assert_eq!(wrapped.wrapped_secret.len(), 12 + 32 + 16);
+
+    let secret = unwrap_secret(wrapped.wrapped_secret, wrapping_key).unwrap();
+    let decapsulated = mlkem768_decapsulate(wrapped.ciphertext, keypair.secret_key).unwrap();
+    assert_eq!(secret.as_ref(), decapsulated.as_ref());
+  }
+
+  #[test]
+  fn test_unwrap_secret_rejects_wrong_key() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let wrapped = mlkem768_encapsulate_wrapped(keypair.public_key.clone(), generate_symmetric_key()).unwrap();
+
+    assert!(unwrap_secret(wrapped.wrapped_secret.clone(), generate_symmetric_key()).is_err());
+    assert!(unwrap_secret(wrapped.wrapped_secret, vec![0u8; 16].into()).is_err());
+    assert!(mlkem768_encapsulate_wrapped(keypair.public_key, vec![0u8; 31].into()).is_err());
+  }
+
+  #[test]
+  fn test_group_key_binds_membership() {
+    let secret: Buffer = vec![3u8; 32].into();
+    let members = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+    let key = group_key(secret.clone(), members(&["qf:aa", "qf:bb", "qf:cc"])).unwrap();
+    assert_eq!(key.len(), 32);
+    assert_eq!(key.as_ref(), group_key(secret.clone(), members(&["qf:cc", "qf:aa", "qf:BB"])).unwrap().as_ref());
+
+    assert_ne!(key.as_ref(), group_key(secret.clone(), members(&["qf:aa", "qf:bb"])).unwrap().as_ref());
+    assert_ne!(key.as_ref(), group_key(secret.clone(), members(&["qf:aa", "qf:bb", "qf:cc", "qf:dd"])).unwrap().as_ref());
+    assert_ne!(key.as_ref(), group_key(vec![4u8; 32].into(), members(&["qf:aa", "qf:bb", "qf:cc"])).unwrap().as_ref());
+
+    assert!(group_key(secret.clone(), Vec::new()).is_err());
+    assert!(group_key(secret, members(&["qf:aa", "qf:AA"])).is_err());
+    assert!(group_key(vec![3u8; 16].into(), members(&["qf:aa"])).is_err());
+  }
+
+  #[test]
+  fn test_group_decapsulate_rejects_swapped_wrap() {
+    let recipients: Vec<_> = (0..2).map(|_| mlkem768_generate_keypair().unwrap()).collect();
+    let group = group_encapsulate(
+      recipients.iter().map(|kp| kp.public_key.clone()).collect()
+    ).unwrap();
+
+    assert!(group_decapsulate(
+      recipients[0].secret_key.clone(),
+      group.per_recipient_ciphertexts[0].clone(),
+      group.wrapped_group_secrets[1].clone()
+    ).is_err());
+  }
+
+  #[test]
+  fn test_group_encapsulate_reports_bad_key_index() {
+    let good = mlkem768_generate_keypair().unwrap();
+    let err = group_encapsulate(vec![good.public_key, vec![0u8; 10].into()]).err().unwrap();
+    assert!(err.reason.contains("Recipient 1"));
+  }
+
+  #[test]
+  fn test_hybrid_encrypt_padded() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let short = hybrid_encrypt_padded(keypair.public_key.clone(), b"hi".to_vec().into(), 256).unwrap();
+    let long = hybrid_encrypt_padded(keypair.public_key.clone(), vec![7u8; 200].into(), 256).unwrap();
+    assert_eq!(short.len(), long.len());
+    assert_eq!(hybrid_ciphertext_version(short.clone()).unwrap(), 1);
+
+    assert_eq!(hybrid_decrypt_padded(keypair.secret_key.clone(), short.clone()).unwrap().as_ref(), b"hi");
+    assert_eq!(hybrid_decrypt_padded(keypair.secret_key.clone(), long).unwrap().as_ref(), &[7u8; 200]);
+
+    // Crossing the block boundary (length prefix included) grows the ciphertext
+    let spilled = hybrid_encrypt_padded(keypair.public_key.clone(), vec![7u8; 253].into(), 256).unwrap();
+    assert_eq!(spilled.len(), short.len() + 256);
+
+    let empty = hybrid_encrypt_padded(keypair.public_key.clone(), Vec::new().into(), 1).unwrap();
+    assert!(hybrid_decrypt_padded(keypair.secret_key.clone(), empty).unwrap().is_empty());
+
+    // Padded and unpadded blobs don't open as each other
+    assert!(hybrid_decrypt(keypair.secret_key.clone(), short).is_err());
+    let plain = hybrid_encrypt(keypair.public_key.clone(), vec![0u8; 8].into()).unwrap();
+    assert!(hybrid_decrypt_padded(keypair.secret_key, plain).is_err());
+    assert!(hybrid_encrypt_padded(keypair.public_key, Vec::new().into(), 0).is_err());
+  }
+
+  #[test]
+  fn test_hybrid_encrypt_padded_rejects_large_block() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let max = hybrid_encrypt_padded(keypair.public_key.clone(), b"hi".to_vec().into(), MAX_PAD_BLOCK).unwrap();
+    assert_eq!(hybrid_decrypt_padded(keypair.secret_key, max).unwrap().as_ref(), b"hi");
+
+    let err = hybrid_encrypt_padded(keypair.public_key.clone(), b"hi".to_vec().into(), MAX_PAD_BLOCK + 1).err().unwrap();
+    assert!(err.reason.starts_with("Padding block size too large"));
+    assert!(hybrid_encrypt_padded(keypair.public_key, b"hi".to_vec().into(), u32::MAX).is_err());
+  }
+
+  #[test]
+  fn test_derive_enc_mac_keys() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let sender = mlkem768_encapsulate(keypair.public_key).unwrap();
+    let receiver = mlkem768_decapsulate(sender.ciphertext, keypair.secret_key).unwrap();
+
+    let ours = derive_enc_mac_keys(sender.shared_secret.clone()).unwrap();
+    let theirs = derive_enc_mac_keys(receiver).unwrap();
+    assert_eq!(ours.enc_key.as_ref(), theirs.enc_key.as_ref());
+    assert_eq!(ours.mac_key.as_ref(), theirs.mac_key.as_ref());
+
+    assert_eq!(ours.enc_key.len(), 32);
+    assert_ne!(ours.enc_key.as_ref(), ours.mac_key.as_ref());
+    assert_ne!(ours.enc_key.as_ref(), sender.shared_secret.as_ref());
+    assert_ne!(ours.enc_key.as_ref(), derive_aead_key(sender.shared_secret).unwrap().as_ref());
+    assert!(derive_enc_mac_keys(vec![0u8; 31].into()).is_err());
+  }
+
+  #[test]
+  fn test_confirmation_tags_are_directional() {
+    let shared_secret: Buffer = vec![6u8; 32].into();
+    let tags = derive_confirmation_tags(shared_secret.clone()).unwrap();
+    assert_ne!(tags.initiator_tag.as_ref(), tags.responder_tag.as_ref());
+
+    assert!(verify_initiator_tag(shared_secret.clone(), tags.initiator_tag.clone()).unwrap());
+    assert!(verify_responder_tag(shared_secret.clone(), tags.responder_tag.clone()).unwrap());
+
+    // A reflected tag does not pass as the peer's
+    assert!(!verify_responder_tag(shared_secret.clone(), tags.initiator_tag.clone()).unwrap());
+    assert!(!verify_initiator_tag(shared_secret.clone(), tags.responder_tag).unwrap());
+
+    // Nor does a tag from another session
+    assert!(!verify_initiator_tag(vec![7u8; 32].into(), tags.initiator_tag.clone()).unwrap());
+    assert!(!verify_initiator_tag(shared_secret, tags.initiator_tag[..31].to_vec().into()).unwrap());
+    assert!(derive_confirmation_tags(vec![0u8; 16].into()).is_err());
+  }
+
+  #[test]
+  fn test_session_id_matches_across_parties() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let sender = mlkem768_encapsulate(keypair.public_key).unwrap();
+    let receiver = mlkem768_decapsulate(sender.ciphertext, keypair.secret_key).unwrap();
+
+    let sid = session_id(sender.shared_secret.clone()).unwrap();
+    assert_eq!(sid, session_id(receiver).unwrap());
+    assert!(sid.starts_with("sid:"));
+    assert_eq!(sid.len(), 4 + 32);
+    assert!(!sid.contains(&hex::encode(&sender.shared_secret[..16])));
+
+    assert_ne!(sid, session_id(vec![0u8; 32].into()).unwrap());
+    assert!(session_id(vec![0u8; 16].into()).is_err());
+  }
+
+  #[test]
+  fn test_hybrid_kem_combine() {
+    let mlkem: Buffer = vec![1u8; 32].into();
+    let x25519: Buffer = vec![2u8; 32].into();
+    let combined = hybrid_kem_combine(mlkem.clone(), x25519.clone()).unwrap();
+    assert_eq!(combined.len(), 32);
+    assert_eq!(combined.as_ref(), hybrid_kem_combine(mlkem.clone(), x25519.clone()).unwrap().as_ref());
+
+    assert_ne!(combined.as_ref(), hybrid_kem_combine(vec![3u8; 32].into(), x25519.clone()).unwrap().as_ref());
+    assert_ne!(combined.as_ref(), hybrid_kem_combine(mlkem.clone(), vec![3u8; 32].into()).unwrap().as_ref());
+    // Swapping the inputs is a different combination
+    assert_ne!(combined.as_ref(), hybrid_kem_combine(x25519, mlkem.clone()).unwrap().as_ref());
+
+    assert!(hybrid_kem_combine(mlkem, vec![0u8; 31].into()).is_err());
+  }
+}
@@ -0,0 +1,370 @@
+//! Passphrase-protected secret keys
+//!
+//! Secret keys are wrapped with AES-256-GCM under a key stretched from the
+//! passphrase with Argon2id. The header, including the salt and Argon2
+//! parameters, is authenticated as associated data.
+//!
+//! Wrapped key layout:
+//! `magic "QPWK" (4) || version (1) || salt (16) || memory KiB (4, LE) ||
+//! iterations (4, LE) || parallelism (4, LE) || nonce (12) || ciphertext || tag (16)`
+//!
+//! Identity bundles keep the public keys readable and wrap both secret keys
+//! together, along with a digest binding them to the public keys:
+//! `magic "QIDB" (4) || version (1) || ML-KEM-768 public key (1184) ||
+//! ML-DSA-65 public key (1952) || wrapped secrets`, where the wrapped
+//! secrets are a wrapped key blob of
+//! `ML-KEM-768 secret key (2400) || ML-DSA-65 secret key (4032) || public key digest (32)`.
+
+use argon2::{Algorithm as Argon2Algorithm, Argon2, Params, Version};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use zeroize::Zeroize;
+
+use crate::crypto::{hash_length_prefixed, keypair_lengths, Algorithm, KeyPair};
+use crate::encoding::IdentityKeys;
+use crate::hybrid::{aead_open, aead_seal};
+
+/// Keypairs recovered from an identity bundle
+#[napi(object)]
+pub struct IdentityBundle {
+  /// ML-KEM-768 keypair
+  pub kem: KeyPair,
+  /// ML-DSA-65 keypair
+  pub dsa: KeyPair,
+}
+
+const WRAP_MAGIC: &[u8; 4] = b"QPWK";
+const WRAP_VERSION: u8 = 1;
+const WRAP_HEADER_LEN: usize = 4 + 1 + 16 + 12;
+const SALT_LEN: usize = 16;
+
+const BUNDLE_MAGIC: &[u8; 4] = b"QIDB";
+const BUNDLE_VERSION: u8 = 1;
+const BUNDLE_PUBLIC_END: usize = 5 + 1184 + 1952;
+const BUNDLE_SECRETS_LEN: usize = 2400 + 4032 + 32;
+
+// OWASP-recommended Argon2id minimum: 19 MiB, 2 iterations, 1 lane
+const ARGON2_MEMORY_KIB: u32 = 19 * 1024;
+const ARGON2_ITERATIONS: u32 = 2;
+const ARGON2_PARALLELISM: u32 = 1;
+
+// Upper bounds accepted from a blob, so a crafted header can't demand
+// unbounded memory or time
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ITERATIONS: u32 = 16;
+const MAX_PARALLELISM: u32 = 16;
+
+/// Rough passphrase entropy estimate in bits
+///
+/// Length times log2 of the character pool implied by the classes present
+/// (lowercase, uppercase, digits, ASCII symbols and spaces, anything else).
+/// This is a heuristic that overestimates dictionary words and predictable
+/// patterns; it only catches passphrases that are weak by any measure.
+fn estimate_entropy_bits(passphrase: &str) -> f64 {
+  let (mut lower, mut upper, mut digit, mut symbol, mut other) = (false, false, false, false, false);
+  for c in passphrase.chars() {
+    match c {
+      'a'..='z' => lower = true,
+      'A'..='Z' => upper = true,
+      '0'..='9' => digit = true,
+      ' '..='~' => symbol = true,
+      _ => other = true,
+    }
+  }
+
+  let pool = [(lower, 26), (upper, 26), (digit, 10), (symbol, 33), (other, 100)]
+    .iter()
+    .filter(|(present, _)| *present)
+    .map(|(_, size)| size)
+    .sum::<u32>();
+  if pool == 0 {
+    return 0.0;
+  }
+
+  passphrase.chars().count() as f64 * (pool as f64).log2()
+}
+
+/// Stretch a passphrase into a 32-byte wrapping key with Argon2id
+fn stretch_passphrase(passphrase: &str, salt: &[u8], memory_kib: u32, iterations: u32, parallelism: u32) -> Result<[u8; 32]> {
+  let params = Params::new(memory_kib, iterations, parallelism, Some(32))
+    .map_err(|e| Error::from_reason(format!("Invalid Argon2 parameters: {}", e)))?;
+
+  let mut key = [0u8; 32];
+  Argon2::new(Argon2Algorithm::Argon2id, Version::V0x13, params)
+    .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+    .map_err(|e| Error::from_reason(format!("Argon2 key derivation failed: {}", e)))?;
+  Ok(key)
+}
+
+/// Encrypt a secret key under a passphrase
+///
+/// # Arguments
+/// * `secret_key` - Secret key bytes of any algorithm
+/// * `passphrase` - Passphrase to protect the key with
+/// * `min_entropy_bits` - Optional minimum estimated passphrase entropy;
+///   weaker passphrases are rejected before any work is done
+///
+/// # Returns
+/// Wrapped key blob for `decrypt_secret_key`
+#[napi]
+pub fn encrypt_secret_key(secret_key: Buffer, passphrase: String, min_entropy_bits: Option<u32>) -> Result<Buffer> {
+  if let Some(min_bits) = min_entropy_bits {
+    let estimate = estimate_entropy_bits(&passphrase);
+    if estimate < min_bits as f64 {
+      return Err(Error::from_reason(format!(
+        "Passphrase too weak: estimated {:.0} bits of entropy, need at least {}",
+        estimate, min_bits
+      )));
+    }
+  }
+
+  let mut salt = [0u8; SALT_LEN];
+  OsRng.fill_bytes(&mut salt);
+
+  let mut header = Vec::with_capacity(WRAP_HEADER_LEN);
+  header.extend_from_slice(WRAP_MAGIC);
+  header.push(WRAP_VERSION);
+  header.extend_from_slice(&salt);
+  header.extend_from_slice(&ARGON2_MEMORY_KIB.to_le_bytes());
+  header.extend_from_slice(&ARGON2_ITERATIONS.to_le_bytes());
+  header.extend_from_slice(&ARGON2_PARALLELISM.to_le_bytes());
+
+  let mut key = stretch_passphrase(&passphrase, &salt, ARGON2_MEMORY_KIB, ARGON2_ITERATIONS, ARGON2_PARALLELISM)?;
+  let sealed = aead_seal(&key, secret_key.as_ref(), &header);
+  key.zeroize();
+
+  header.extend_from_slice(&sealed?);
+  Ok(header.into())
+}
+
+/// Decrypt a secret key wrapped by `encrypt_secret_key`
+///
+/// # Arguments
+/// * `blob` - Wrapped key blob
+/// * `passphrase` - Passphrase used for wrapping
+///
+/// # Returns
+/// Secret key bytes; errors on a wrong passphrase or a tampered blob
+#[napi]
+pub fn decrypt_secret_key(blob: Buffer, passphrase: String) -> Result<Buffer> {
+  let bytes = blob.as_ref();
+  if bytes.len() < WRAP_HEADER_LEN || &bytes[..4] != WRAP_MAGIC {
+    return Err(Error::from_reason("Invalid wrapped key: missing QPWK magic"));
+  }
+  if bytes[4] != WRAP_VERSION {
+    return Err(Error::from_reason(format!("Unsupported wrapped key version: {}", bytes[4])));
+  }
+
+  let (header, sealed) = bytes.split_at(WRAP_HEADER_LEN);
+  let salt = &header[5..5 + SALT_LEN];
+  let param = |i: usize| u32::from_le_bytes(header[21 + 4 * i..25 + 4 * i].try_into().expect("slice is 4 bytes"));
+
+  let (memory_kib, iterations, parallelism) = (param(0), param(1), param(2));
+  if memory_kib > MAX_MEMORY_KIB || iterations > MAX_ITERATIONS || parallelism > MAX_PARALLELISM {
+    return Err(Error::from_reason("Invalid wrapped key: Argon2 parameters out of range"));
+  }
+
+  let mut key = stretch_passphrase(&passphrase, salt, memory_kib, iterations, parallelism)?;
+  let opened = aead_open(&key, sealed, header);
+  key.zeroize();
+
+  opened
+    .map(Buffer::from)
+    .map_err(|_| Error::from_reason("Cannot decrypt wrapped key: wrong passphrase or corrupted data"))
+}
+
+/// Export ML-KEM and ML-DSA keypairs as one passphrase-protected bundle
+///
+/// # Arguments
+/// * `kem` - ML-KEM-768 keypair
+/// * `dsa` - ML-DSA-65 keypair
+/// * `passphrase` - Passphrase protecting both secret keys
+///
+/// # Returns
+/// Identity bundle for `import_identity_bundle`; the public keys stay
+/// readable with `identity_bundle_public_keys`
+#[napi]
+pub fn export_identity_bundle(kem: KeyPair, dsa: KeyPair, passphrase: String) -> Result<Buffer> {
+  check_keypair(&kem, Algorithm::MlKem768)?;
+  check_keypair(&dsa, Algorithm::MlDsa65)?;
+
+  let mut secrets = Vec::with_capacity(BUNDLE_SECRETS_LEN);
+  secrets.extend_from_slice(&kem.secret_key);
+  secrets.extend_from_slice(&dsa.secret_key);
+  secrets.extend_from_slice(&bundle_public_digest(&kem.public_key, &dsa.public_key));
+  let wrapped = encrypt_secret_key(secrets.into(), passphrase, None)?;
+
+  let mut bundle = Vec::with_capacity(BUNDLE_PUBLIC_END + wrapped.len());
+  bundle.extend_from_slice(BUNDLE_MAGIC);
+  bundle.push(BUNDLE_VERSION);
+  bundle.extend_from_slice(&kem.public_key);
+  bundle.extend_from_slice(&dsa.public_key);
+  bundle.extend_from_slice(&wrapped);
+  Ok(bundle.into())
+}
+
+/// Read the public keys of an identity bundle without the passphrase
+///
+/// The keys are not authenticated until the bundle is imported; treat them
+/// as untrusted until then.
+///
+/// # Arguments
+/// * `bundle` - Bundle from `export_identity_bundle`
+///
+/// # Returns
+/// IdentityKeys with the ML-KEM and ML-DSA public keys
+#[napi]
+pub fn identity_bundle_public_keys(bundle: Buffer) -> Result<IdentityKeys> {
+  let (kem_public, dsa_public, _) = split_bundle(bundle.as_ref())?;
+  Ok(IdentityKeys {
+    kem_public: kem_public.to_vec().into(),
+    dsa_public: dsa_public.to_vec().into(),
+  })
+}
+
+/// Import both keypairs from an identity bundle
+///
+/// # Arguments
+/// * `bundle` - Bundle from `export_identity_bundle`
+/// * `passphrase` - Passphrase used for export
+///
+/// # Returns
+/// IdentityBundle with both keypairs; errors on a wrong passphrase, or if
+/// the public keys were altered after export
+#[napi]
+pub fn import_identity_bundle(bundle: Buffer, passphrase: String) -> Result<IdentityBundle> {
+  let (kem_public, dsa_public, wrapped) = split_bundle(bundle.as_ref())?;
+
+  let mut secrets = decrypt_secret_key(wrapped.to_vec().into(), passphrase)?.to_vec();
+  let valid = secrets.len() == BUNDLE_SECRETS_LEN
+    && secrets[6432..] == bundle_public_digest(kem_public, dsa_public);
+  let imported = valid.then(|| IdentityBundle {
+    kem: KeyPair {
+      public_key: kem_public.to_vec().into(),
+      secret_key: secrets[..2400].to_vec().into(),
+    },
+    dsa: KeyPair {
+      public_key: dsa_public.to_vec().into(),
+      secret_key: secrets[2400..6432].to_vec().into(),
+    },
+  });
+  secrets.zeroize();
+
+  imported.ok_or_else(|| Error::from_reason("Invalid identity bundle: public keys do not match the wrapped secrets"))
+}
+
+fn check_keypair(keypair: &KeyPair, algorithm: Algorithm) -> Result<()> {
+  let (public_len, secret_len) = keypair_lengths(algorithm)?;
+  if keypair.public_key.len() != public_len as usize || keypair.secret_key.len() != secret_len as usize {
+    return Err(Error::from_reason(format!(
+      "Invalid {} keypair: expected {}/{} byte keys, got {}/{}",
+      algorithm.as_str(),
+      public_len,
+      secret_len,
+      keypair.public_key.len(),
+      keypair.secret_key.len()
+    )));
+  }
+  Ok(())
+}
+
+fn bundle_public_digest(kem_public: &[u8], dsa_public: &[u8]) -> [u8; 32] {
+  hash_length_prefixed("qudag-napi identity bundle v1", [kem_public, dsa_public])
+}
+
+/// Split a bundle into the ML-KEM public key, ML-DSA public key and wrapped secrets
+fn split_bundle(bundle: &[u8]) -> Result<(&[u8], &[u8], &[u8])> {
+  if bundle.len() < BUNDLE_PUBLIC_END || &bundle[..4] != BUNDLE_MAGIC {
+    return Err(Error::from_reason("Invalid identity bundle: missing QIDB magic"));
+  }
+  if bundle[4] != BUNDLE_VERSION {
+    return Err(Error::from_reason(format!("Unsupported identity bundle version: {}", bundle[4])));
+  }
+
+  Ok((&bundle[5..1189], &bundle[1189..BUNDLE_PUBLIC_END], &bundle[BUNDLE_PUBLIC_END..]))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::crypto::{mldsa65_generate_keypair, mlkem768_generate_keypair};
+
+  #[test]
+  fn test_encrypt_secret_key_roundtrip() {
+    let secret: Buffer = vec![7u8; 2400].into();
+    let passphrase = "correct horse battery staple 42".to_string();
+    let wrapped = encrypt_secret_key(secret.clone(), passphrase.clone(), None).unwrap();
+
+    let unwrapped = decrypt_secret_key(wrapped.clone(), passphrase.clone()).unwrap();
+    assert_eq!(unwrapped.as_ref(), secret.as_ref());
+    assert!(decrypt_secret_key(wrapped.clone(), "wrong".to_string()).is_err());
+
+    // The Argon2 parameters are authenticated
+    let mut tampered = wrapped.to_vec();
+    tampered[21] ^= 1;
+    assert!(decrypt_secret_key(tampered.into(), passphrase.clone()).is_err());
+
+    let mut expensive = wrapped.to_vec();
+    expensive[24] = 0xff;
+    let err = decrypt_secret_key(expensive.into(), passphrase).err().unwrap();
+    assert!(err.reason.contains("out of range"));
+  }
+
+  #[test]
+  fn test_encrypt_secret_key_min_entropy() {
+    let secret: Buffer = vec![7u8; 32].into();
+
+    let err = encrypt_secret_key(secret.clone(), "password".to_string(), Some(60)).err().unwrap();
+    assert!(err.reason.contains("Passphrase too weak"));
+
+    assert!(encrypt_secret_key(secret.clone(), "Tr0ub4dor & correct horse battery".to_string(), Some(60)).is_ok());
+    assert!(encrypt_secret_key(secret, String::new(), Some(1)).is_err());
+  }
+
+  #[test]
+  fn test_identity_bundle_roundtrip() {
+    let kem = mlkem768_generate_keypair().unwrap();
+    let dsa = mldsa65_generate_keypair().unwrap();
+    let copy = |kp: &KeyPair| KeyPair {
+      public_key: kp.public_key.clone(),
+      secret_key: kp.secret_key.clone(),
+    };
+
+    let bundle = export_identity_bundle(copy(&kem), copy(&dsa), "bundle passphrase".to_string()).unwrap();
+    let imported = import_identity_bundle(bundle.clone(), "bundle passphrase".to_string()).unwrap();
+    assert_eq!(imported.kem.public_key.as_ref(), kem.public_key.as_ref());
+    assert_eq!(imported.kem.secret_key.as_ref(), kem.secret_key.as_ref());
+    assert_eq!(imported.dsa.public_key.as_ref(), dsa.public_key.as_ref());
+    assert_eq!(imported.dsa.secret_key.as_ref(), dsa.secret_key.as_ref());
+
+    // Swapped keypairs are rejected before anything is wrapped
+    assert!(export_identity_bundle(copy(&dsa), copy(&kem), "bundle passphrase".to_string()).is_err());
+  }
+
+  #[test]
+  fn test_identity_bundle_wrong_passphrase() {
+    let kem = mlkem768_generate_keypair().unwrap();
+    let dsa = mldsa65_generate_keypair().unwrap();
+    let bundle = export_identity_bundle(
+      KeyPair { public_key: kem.public_key.clone(), secret_key: kem.secret_key },
+      KeyPair { public_key: dsa.public_key.clone(), secret_key: dsa.secret_key },
+      "right".to_string(),
+    ).unwrap();
+
+    let err = import_identity_bundle(bundle.clone(), "wrong".to_string()).err().unwrap();
+    assert!(err.reason.contains("wrong passphrase"));
+
+    // The public halves stay readable without the passphrase
+    let public = identity_bundle_public_keys(bundle.clone()).unwrap();
+    assert_eq!(public.kem_public.as_ref(), kem.public_key.as_ref());
+    assert_eq!(public.dsa_public.as_ref(), dsa.public_key.as_ref());
+
+    // ...but a swapped public key is caught on import
+    let mut tampered = bundle.to_vec();
+    tampered[100] ^= 1;
+    let err = import_identity_bundle(tampered.into(), "right".to_string()).err().unwrap();
+    assert!(err.reason.contains("do not match"));
+  }
+}
@@ -25,17 +25,44 @@
 //! ```
 
 #![deny(clippy::all)]
+// `#[napi]` on a class struct also generates undocumented public helpers
+// (`instance_of`, `into_instance`, `into_reference`). Each class struct is
+// therefore declared in its own `#[allow(missing_docs)]` module with
+// `pub(super)` fields; its `#[napi] impl` stays outside and is still checked.
 #![warn(missing_docs)]
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
+mod bench;
 mod crypto;
+mod encoding;
+mod files;
+mod hashing;
+mod hybrid;
+mod keywrap;
+mod merkle;
+mod ratchet;
+mod rng;
+mod selftest;
+mod tasks;
 // mod vault;  // TODO: Fix async runtime issues
 // mod exchange;  // TODO: Fix async runtime issues
 mod utils;
 
+pub use bench::*;
 pub use crypto::*;
+pub use encoding::*;
+pub use files::*;
+pub use hashing::*;
+pub use hybrid::*;
+pub use keywrap::*;
+pub use merkle::*;
+pub use ratchet::*;
+#[cfg(feature = "test-rng")]
+pub use rng::set_test_rng;
+pub use selftest::*;
+pub use tasks::*;
 // pub use vault::*;
 // pub use exchange::*;
 pub use utils::*;
@@ -52,6 +79,20 @@ pub fn version() -> String {
   env!("CARGO_PKG_VERSION").to_string()
 }
 
+/// Get the backend this module was compiled for
+///
+/// Returns "wasm" when built for `wasm32` targets and "native" for the
+/// Node.js addon. The native addon is typically ~3x faster, so this is
+/// useful when logging or explaining performance differences.
+#[napi]
+pub fn backend_kind() -> String {
+  if cfg!(target_arch = "wasm32") {
+    "wasm".to_string()
+  } else {
+    "native".to_string()
+  }
+}
+
 /// Get module information
 #[napi(object)]
 pub struct ModuleInfo {
@@ -88,6 +129,12 @@ mod tests {
     assert!(!v.is_empty());
   }
 
+  #[test]
+  fn test_backend_kind() {
+    let kind = backend_kind();
+    assert!(kind == "native" || kind == "wasm");
+  }
+
   #[test]
   fn test_init() {
     let msg = init().unwrap();
@@ -0,0 +1,264 @@
+//! Merkle trees
+//!
+//! Binary Merkle trees over a list of leaves with a selectable node hash,
+//! so roots can be shared with systems that expect SHA3 as well as BLAKE3.
+//!
+//! Leaves and interior nodes are domain-separated as in RFC 6962:
+//! `leaf = H(0x00 || data)` and `node = H(0x01 || left || right)`. When a
+//! level has an odd number of nodes the last one is carried up unchanged
+//! rather than duplicated, so appending a copy of the last leaf always
+//! changes the root.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use sha3::{Digest, Sha3_256};
+
+use crate::crypto::Algorithm;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Inclusion proof for one leaf of a Merkle tree
+#[napi(object)]
+pub struct MerkleProof {
+  /// Position of the proven leaf in the leaf list
+  pub leaf_index: u32,
+  /// Number of leaves in the tree
+  pub leaf_count: u32,
+  /// Sibling hashes from the leaf level up to just below the root
+  pub siblings: Vec<Buffer>,
+}
+
+/// Node hash function used to build a tree
+#[derive(Clone, Copy)]
+enum NodeHash {
+  Blake3,
+  Sha3_256,
+}
+
+impl NodeHash {
+  fn from_algorithm(algorithm: Algorithm) -> Result<Self> {
+    match algorithm {
+      Algorithm::Blake3 => Ok(NodeHash::Blake3),
+      Algorithm::Sha3_256 => Ok(NodeHash::Sha3_256),
+      _ => Err(Error::from_reason(format!(
+        "{} is not a hash algorithm; use \"blake3\" or \"sha3-256\"",
+        algorithm.as_str()
+      ))),
+    }
+  }
+
+  fn hash(self, prefix: u8, parts: &[&[u8]]) -> [u8; 32] {
+    match self {
+      NodeHash::Blake3 => {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&[prefix]);
+        for part in parts {
+          hasher.update(part);
+        }
+        *hasher.finalize().as_bytes()
+      }
+      NodeHash::Sha3_256 => {
+        let mut hasher = Sha3_256::new();
+        hasher.update([prefix]);
+        for part in parts {
+          hasher.update(part);
+        }
+        hasher.finalize().into()
+      }
+    }
+  }
+
+  fn leaf(self, data: &[u8]) -> [u8; 32] {
+    self.hash(LEAF_PREFIX, &[data])
+  }
+
+  fn node(self, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    self.hash(NODE_PREFIX, &[left, right])
+  }
+}
+
+/// Hash one level of the tree into the next, carrying an odd node up
+fn next_level(hash: NodeHash, level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+  level
+    .chunks(2)
+    .map(|pair| match pair {
+      [left, right] => hash.node(left, right),
+      [single] => *single,
+      _ => unreachable!("chunks(2) yields one or two nodes"),
+    })
+    .collect()
+}
+
+fn leaf_level(hash: NodeHash, leaves: &[Buffer]) -> Result<Vec<[u8; 32]>> {
+  if leaves.is_empty() {
+    return Err(Error::from_reason("Cannot build a Merkle tree with no leaves"));
+  }
+  if u32::try_from(leaves.len()).is_err() {
+    return Err(Error::from_reason("Too many leaves for a Merkle tree"));
+  }
+  Ok(leaves.iter().map(|leaf| hash.leaf(leaf.as_ref())).collect())
+}
+
+/// Build the root of a Merkle tree with the given node hash
+///
+/// # Arguments
+/// * `leaves` - Leaf data, in order (at least one)
+/// * `algorithm` - "blake3" or "sha3-256"
+///
+/// # Returns
+/// 32-byte Merkle root
+#[napi]
+pub fn build_merkle_root_with(leaves: Vec<Buffer>, algorithm: Algorithm) -> Result<Buffer> {
+  let hash = NodeHash::from_algorithm(algorithm)?;
+  let mut level = leaf_level(hash, &leaves)?;
+
+  while level.len() > 1 {
+    level = next_level(hash, &level);
+  }
+
+  Ok(level[0].to_vec().into())
+}
+
+/// Build an inclusion proof for one leaf
+///
+/// # Arguments
+/// * `leaves` - Leaf data, in order (at least one)
+/// * `index` - Position of the leaf to prove
+/// * `algorithm` - "blake3" or "sha3-256"; must match the one used for the root
+///
+/// # Returns
+/// MerkleProof for `verify_merkle_proof_with`
+#[napi]
+pub fn merkle_proof_with(leaves: Vec<Buffer>, index: u32, algorithm: Algorithm) -> Result<MerkleProof> {
+  let hash = NodeHash::from_algorithm(algorithm)?;
+  let mut level = leaf_level(hash, &leaves)?;
+
+  if index as usize >= leaves.len() {
+    return Err(Error::from_reason(format!(
+      "Leaf index {} out of range for {} leaves",
+      index,
+      leaves.len()
+    )));
+  }
+
+  let mut siblings = Vec::new();
+  let mut position = index as usize;
+  while level.len() > 1 {
+    let sibling = position ^ 1;
+    if sibling < level.len() {
+      siblings.push(level[sibling].to_vec().into());
+    }
+    level = next_level(hash, &level);
+    position /= 2;
+  }
+
+  Ok(MerkleProof {
+    leaf_index: index,
+    leaf_count: leaves.len() as u32,
+    siblings,
+  })
+}
+
+/// Verify that a leaf belongs to a Merkle tree
+///
+/// # Arguments
+/// * `leaf` - Leaf data being proven
+/// * `proof` - Proof from `merkle_proof_with`
+/// * `root` - Expected 32-byte root
+/// * `algorithm` - "blake3" or "sha3-256"; must match the one used to build the tree
+///
+/// # Returns
+/// True if the proof leads from `leaf` to `root`; false for any mismatch or malformed proof
+#[napi]
+pub fn verify_merkle_proof_with(leaf: Buffer, proof: MerkleProof, root: Buffer, algorithm: Algorithm) -> Result<bool> {
+  let hash = NodeHash::from_algorithm(algorithm)?;
+
+  if proof.leaf_index >= proof.leaf_count {
+    return Ok(false);
+  }
+
+  let mut current = hash.leaf(leaf.as_ref());
+  let mut position = proof.leaf_index as usize;
+  let mut width = proof.leaf_count as usize;
+  let mut siblings = proof.siblings.iter();
+
+  while width > 1 {
+    // The last node of an odd level has no sibling and is carried up
+    if position ^ 1 < width {
+      let Some(Ok(sibling)) = siblings.next().map(|s| <[u8; 32]>::try_from(s.as_ref())) else {
+        return Ok(false);
+      };
+      current = if position.is_multiple_of(2) {
+        hash.node(&current, &sibling)
+      } else {
+        hash.node(&sibling, &current)
+      };
+    }
+    position /= 2;
+    width = width.div_ceil(2);
+  }
+
+  Ok(siblings.next().is_none() && current.as_slice() == root.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn leaves(count: usize) -> Vec<Buffer> {
+    (0..count).map(|i| format!("leaf {}", i).into_bytes().into()).collect()
+  }
+
+  #[test]
+  fn test_merkle_proofs_verify_under_both_algorithms() {
+    for algorithm in [Algorithm::Blake3, Algorithm::Sha3_256] {
+      for count in [1, 2, 3, 5, 8] {
+        let root = build_merkle_root_with(leaves(count), algorithm).unwrap();
+        assert_eq!(root.len(), 32);
+
+        for index in 0..count as u32 {
+          let proof = merkle_proof_with(leaves(count), index, algorithm).unwrap();
+          let leaf = leaves(count).swap_remove(index as usize);
+          assert!(verify_merkle_proof_with(leaf, proof, root.clone(), algorithm).unwrap());
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_merkle_algorithms_do_not_cross_verify() {
+    let blake3_root = build_merkle_root_with(leaves(4), Algorithm::Blake3).unwrap();
+    let sha3_root = build_merkle_root_with(leaves(4), Algorithm::Sha3_256).unwrap();
+    assert_ne!(blake3_root.as_ref(), sha3_root.as_ref());
+
+    let sha3_proof = merkle_proof_with(leaves(4), 1, Algorithm::Sha3_256).unwrap();
+    assert!(!verify_merkle_proof_with(leaves(4).swap_remove(1), sha3_proof, blake3_root.clone(), Algorithm::Sha3_256).unwrap());
+
+    let blake3_proof = merkle_proof_with(leaves(4), 1, Algorithm::Blake3).unwrap();
+    assert!(!verify_merkle_proof_with(leaves(4).swap_remove(1), blake3_proof, blake3_root, Algorithm::Sha3_256).unwrap());
+  }
+
+  #[test]
+  fn test_merkle_rejects_tampering() {
+    let root = build_merkle_root_with(leaves(5), Algorithm::Blake3).unwrap();
+    let proof = || merkle_proof_with(leaves(5), 2, Algorithm::Blake3).unwrap();
+
+    assert!(!verify_merkle_proof_with(b"other".to_vec().into(), proof(), root.clone(), Algorithm::Blake3).unwrap());
+
+    let mut moved = proof();
+    moved.leaf_index = 3;
+    assert!(!verify_merkle_proof_with(leaves(5).swap_remove(2), moved, root.clone(), Algorithm::Blake3).unwrap());
+
+    let mut extended = proof();
+    extended.siblings.push(vec![0u8; 32].into());
+    assert!(!verify_merkle_proof_with(leaves(5).swap_remove(2), extended, root, Algorithm::Blake3).unwrap());
+  }
+
+  #[test]
+  fn test_merkle_rejects_bad_input() {
+    assert!(build_merkle_root_with(Vec::new(), Algorithm::Blake3).is_err());
+    assert!(build_merkle_root_with(leaves(2), Algorithm::MlKem768).is_err());
+    assert!(merkle_proof_with(leaves(2), 2, Algorithm::Blake3).is_err());
+  }
+}
@@ -0,0 +1,154 @@
+//! Randomness source for key generation and encapsulation
+//!
+//! Everything that generates ML-KEM keys or encapsulation randomness draws
+//! from `module_rng()`. In normal builds that is always `OsRng`.
+//!
+//! With the `test-rng` Cargo feature, `set_test_rng(seed)` installs a seeded
+//! ChaCha20 generator for the calling thread so test suites can be made
+//! fully deterministic. The feature is off by default and the function is
+//! not compiled at all without it, so a release build cannot be switched to
+//! predictable randomness at runtime. Never ship a build with `test-rng`
+//! enabled.
+//!
+//! ML-DSA keygen and signing draw randomness inside `pqcrypto-dilithium` and
+//! are not affected by the test RNG.
+
+#[cfg(feature = "test-rng")]
+use napi::bindgen_prelude::*;
+#[cfg(feature = "test-rng")]
+use napi_derive::napi;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "test-rng")]
+use rand_chacha::rand_core::SeedableRng;
+#[cfg(feature = "test-rng")]
+use rand_chacha::ChaCha20Rng;
+#[cfg(feature = "test-rng")]
+use std::cell::RefCell;
+
+#[cfg(feature = "test-rng")]
+thread_local! {
+  static TEST_RNG: RefCell<Option<ChaCha20Rng>> = const { RefCell::new(None) };
+}
+
+/// Random number generator handed out by `module_rng()`
+pub(crate) enum ModuleRng {
+  /// Operating system CSPRNG
+  Os(OsRng),
+  /// Generator forked from the installed test seed
+  #[cfg(feature = "test-rng")]
+  Seeded(Box<ChaCha20Rng>),
+}
+
+impl RngCore for ModuleRng {
+  fn next_u32(&mut self) -> u32 {
+    match self {
+      ModuleRng::Os(rng) => rng.next_u32(),
+      #[cfg(feature = "test-rng")]
+      ModuleRng::Seeded(rng) => rng.next_u32(),
+    }
+  }
+
+  fn next_u64(&mut self) -> u64 {
+    match self {
+      ModuleRng::Os(rng) => rng.next_u64(),
+      #[cfg(feature = "test-rng")]
+      ModuleRng::Seeded(rng) => rng.next_u64(),
+    }
+  }
+
+  fn fill_bytes(&mut self, dest: &mut [u8]) {
+    match self {
+      ModuleRng::Os(rng) => rng.fill_bytes(dest),
+      #[cfg(feature = "test-rng")]
+      ModuleRng::Seeded(rng) => rng.fill_bytes(dest),
+    }
+  }
+
+  fn try_fill_bytes(&mut self, dest: &mut [u8]) -> std::result::Result<(), rand::Error> {
+    match self {
+      ModuleRng::Os(rng) => rng.try_fill_bytes(dest),
+      #[cfg(feature = "test-rng")]
+      ModuleRng::Seeded(rng) => rng.try_fill_bytes(dest),
+    }
+  }
+}
+
+impl CryptoRng for ModuleRng {}
+
+/// Get the RNG to use for key generation and encapsulation
+///
+/// Returns `OsRng` unless a test seed is installed on this thread, in which
+/// case a generator is forked from the seeded stream, so successive calls
+/// are deterministic but distinct.
+pub(crate) fn module_rng() -> ModuleRng {
+  #[cfg(feature = "test-rng")]
+  {
+    let forked = TEST_RNG.with(|cell| {
+      cell.borrow_mut().as_mut().map(|rng| {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        Box::new(ChaCha20Rng::from_seed(seed))
+      })
+    });
+    if let Some(rng) = forked {
+      return ModuleRng::Seeded(rng);
+    }
+  }
+
+  ModuleRng::Os(OsRng)
+}
+
+/// Install a deterministic RNG seed for this thread (test builds only)
+///
+/// Only available with the `test-rng` Cargo feature. After this call, key
+/// generation and encapsulation on the calling thread (the JS main thread
+/// for synchronous calls) use a ChaCha20 stream seeded from `seed`, so the
+/// same seed reproduces the same keys and ciphertexts. Do not enable the
+/// feature in production builds.
+///
+/// # Arguments
+/// * `seed` - 32-byte seed
+#[cfg(feature = "test-rng")]
+#[napi]
+pub fn set_test_rng(seed: Buffer) -> Result<()> {
+  let seed: [u8; 32] = seed.as_ref()
+    .try_into()
+    .map_err(|_| Error::from_reason(format!(
+      "Invalid seed length: expected 32 bytes, got {}",
+      seed.len()
+    )))?;
+
+  TEST_RNG.with(|cell| *cell.borrow_mut() = Some(ChaCha20Rng::from_seed(seed)));
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_module_rng_defaults_to_os() {
+    assert!(matches!(module_rng(), ModuleRng::Os(_)));
+  }
+
+  #[cfg(feature = "test-rng")]
+  #[test]
+  fn test_set_test_rng_reproducible_keygen() {
+    use crate::crypto::{mlkem768_encapsulate, mlkem768_generate_keypair};
+
+    set_test_rng(vec![42u8; 32].into()).unwrap();
+    let first = mlkem768_generate_keypair().unwrap();
+    let first_enc = mlkem768_encapsulate(first.public_key.clone()).unwrap();
+
+    set_test_rng(vec![42u8; 32].into()).unwrap();
+    let second = mlkem768_generate_keypair().unwrap();
+    let second_enc = mlkem768_encapsulate(second.public_key.clone()).unwrap();
+
+    assert_eq!(first.public_key.as_ref(), second.public_key.as_ref());
+    assert_eq!(first.secret_key.as_ref(), second.secret_key.as_ref());
+    assert_eq!(first_enc.ciphertext.as_ref(), second_enc.ciphertext.as_ref());
+
+    assert!(set_test_rng(vec![0u8; 16].into()).is_err());
+  }
+}
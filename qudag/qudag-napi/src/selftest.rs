@@ -0,0 +1,355 @@
+//! Embedded known-answer vectors
+//!
+//! A small KAT set run against the linked crypto crates. Published vectors
+//! pin the primitives to their specifications; regression snapshots pin
+//! behaviour that has no published vector in this crate (e.g. ML-KEM
+//! deterministic generation) so dependency upgrades that change outputs are
+//! caught. `export_test_vector` produces new deterministic vectors for other
+//! implementations to check against. `run_self_tests_detailed` runs the
+//! set at startup and reports per-vector timing.
+
+use ml_kem::{EncapsulateDeterministic, EncodedSizeUser, KemCore, MlKem768, B32};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::time::Instant;
+
+use crate::crypto::Algorithm;
+
+/// One embedded known-answer vector
+struct KnownAnswer {
+  id: &'static str,
+  algorithm: &'static str,
+  source: &'static str,
+  input: &'static [u8],
+  expected_hex: &'static str,
+}
+
+/// Public description of one embedded known-answer vector
+#[napi(object)]
+pub struct VectorInfo {
+  /// Vector identifier
+  pub id: String,
+  /// Algorithm the vector exercises
+  pub algorithm: String,
+  /// Where the expected value comes from, e.g. "NIST FIPS 202 examples"
+  pub source: String,
+}
+
+/// Outcome of running one known-answer vector
+#[napi(object)]
+pub struct SelfTestResult {
+  /// Vector identifier, as listed by `list_test_vectors`
+  pub name: String,
+  /// Whether the output matched the expected value
+  pub passed: bool,
+  /// Wall-clock time to compute the output, in nanoseconds
+  pub duration_ns: i64,
+}
+
+/// Seed bytes `0x00..=0x5f`: ML-KEM `d || z || m`
+const MLKEM_SEED: [u8; 96] = {
+  let mut seed = [0u8; 96];
+  let mut i = 0;
+  while i < 96 {
+    seed[i] = i as u8;
+    i += 1;
+  }
+  seed
+};
+
+const KNOWN_ANSWERS: &[KnownAnswer] = &[
+  KnownAnswer {
+    id: "blake3-empty",
+    algorithm: "blake3",
+    source: "BLAKE3 reference test vectors",
+    input: b"",
+    expected_hex: "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+  },
+  KnownAnswer {
+    id: "blake3-abc",
+    algorithm: "blake3",
+    source: "BLAKE3 reference implementation",
+    input: b"abc",
+    expected_hex: "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+  },
+  KnownAnswer {
+    id: "sha3-256-empty",
+    algorithm: "sha3-256",
+    source: "NIST FIPS 202 examples",
+    input: b"",
+    expected_hex: "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+  },
+  KnownAnswer {
+    id: "sha3-256-abc",
+    algorithm: "sha3-256",
+    source: "NIST FIPS 202 examples",
+    input: b"abc",
+    expected_hex: "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+  },
+  KnownAnswer {
+    id: "ml-kem-768-deterministic",
+    algorithm: "ml-kem-768",
+    source: "qudag-napi regression snapshot",
+    input: &MLKEM_SEED,
+    expected_hex: "b77e4ffed2055f3f82ede950a161dcd79f8bad185779e90e28e5995eaf47f0fd",
+  },
+];
+
+/// Outputs of one deterministic ML-KEM-768 keygen + encapsulation
+struct MlKemRun {
+  public_key: Vec<u8>,
+  secret_key: Vec<u8>,
+  ciphertext: Vec<u8>,
+  shared_secret: Vec<u8>,
+}
+
+/// Generate a keypair from `d || z` and encapsulate to it with `m`
+fn mlkem768_deterministic_run(seed: &[u8; 96]) -> MlKemRun {
+  let d = B32::try_from(&seed[0..32]).expect("32-byte d");
+  let z = B32::try_from(&seed[32..64]).expect("32-byte z");
+  let m = B32::try_from(&seed[64..96]).expect("32-byte m");
+
+  let (dk, ek) = MlKem768::generate_deterministic(&d, &z);
+  let (ct, ss) = ek.encapsulate_deterministic(&m).expect("deterministic encapsulation");
+
+  MlKemRun {
+    public_key: ek.as_bytes().to_vec(),
+    secret_key: dk.as_bytes().to_vec(),
+    ciphertext: ct.to_vec(),
+    shared_secret: ss.to_vec(),
+  }
+}
+
+/// Run one vector and return its actual output
+///
+/// Hash vectors return the digest. The ML-KEM vector generates a keypair
+/// from `d || z`, encapsulates with `m`, and returns
+/// BLAKE3(public key || ciphertext || shared secret).
+fn compute(kat: &KnownAnswer) -> Vec<u8> {
+  match kat.algorithm {
+    "blake3" => blake3::hash(kat.input).as_bytes().to_vec(),
+    "sha3-256" => Sha3_256::digest(kat.input).to_vec(),
+    "ml-kem-768" => {
+      let seed: &[u8; 96] = kat.input.try_into().expect("96-byte ML-KEM seed");
+      let run = mlkem768_deterministic_run(seed);
+
+      let mut hasher = blake3::Hasher::new();
+      hasher.update(&run.public_key);
+      hasher.update(&run.ciphertext);
+      hasher.update(&run.shared_secret);
+      hasher.finalize().as_bytes().to_vec()
+    }
+    other => unreachable!("no KAT runner for {}", other),
+  }
+}
+
+/// Fingerprint of the module version and its known-answer outputs
+///
+/// Hashes the crate version together with every embedded known-answer
+/// vector: its id, its expected value, and the actual output. The value is
+/// stable for a given build, and is expected to change whenever the crate
+/// version is bumped or an upgraded crypto dependency changes any output, so
+/// CI can pin it to detect silent behaviour changes.
+///
+/// # Returns
+/// Fingerprint string in format "qf:{hash}"
+#[napi]
+pub fn module_fingerprint() -> String {
+  let mut hasher = blake3::Hasher::new_derive_key("qudag-napi module fingerprint v1");
+  hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+  for kat in KNOWN_ANSWERS {
+    let output = compute(kat);
+    hasher.update(&(kat.id.len() as u64).to_le_bytes());
+    hasher.update(kat.id.as_bytes());
+    hasher.update(&(kat.expected_hex.len() as u64).to_le_bytes());
+    hasher.update(kat.expected_hex.as_bytes());
+    hasher.update(&(output.len() as u64).to_le_bytes());
+    hasher.update(&output);
+  }
+  format!("qf:{}", hasher.finalize().to_hex())
+}
+
+/// List the embedded known-answer vectors and where they come from
+///
+/// Lets auditors see exactly what `module_fingerprint` and the self-test
+/// cover. Vectors sourced as a "regression snapshot" have no published
+/// counterpart and only pin this crate's behaviour across upgrades.
+///
+/// # Returns
+/// One VectorInfo per vector, in the order they are run
+#[napi]
+pub fn list_test_vectors() -> Vec<VectorInfo> {
+  KNOWN_ANSWERS
+    .iter()
+    .map(|kat| VectorInfo {
+      id: kat.id.to_string(),
+      algorithm: kat.algorithm.to_string(),
+      source: kat.source.to_string(),
+    })
+    .collect()
+}
+
+/// Run every embedded known-answer vector with timing
+///
+/// Checks each vector's output against its expected value and times the
+/// computation, so a startup check can catch both wrong results and
+/// performance regressions (e.g. a build that lost its SIMD backend).
+/// Timings are single runs and noisy; compare them against generous
+/// thresholds, or use `measure_speedup` for throughput figures.
+///
+/// # Returns
+/// One SelfTestResult per vector, in the order they are run
+#[napi]
+pub fn run_self_tests_detailed() -> Vec<SelfTestResult> {
+  KNOWN_ANSWERS
+    .iter()
+    .map(|kat| {
+      let start = Instant::now();
+      let output = compute(kat);
+      let elapsed = start.elapsed();
+
+      SelfTestResult {
+        name: kat.id.to_string(),
+        passed: hex::encode(output) == kat.expected_hex,
+        // Coarse clocks can report zero for the small hash vectors
+        duration_ns: i64::try_from(elapsed.as_nanos()).unwrap_or(i64::MAX).max(1),
+      }
+    })
+    .collect()
+}
+
+/// JSON layout of an exported test vector; every byte field is hex
+#[derive(Serialize, Deserialize)]
+struct TestVector {
+  algorithm: String,
+  seed: String,
+  d: String,
+  z: String,
+  m: String,
+  public_key: String,
+  secret_key: String,
+  ciphertext: String,
+  shared_secret: String,
+}
+
+/// Export a deterministic interop test vector as JSON
+///
+/// Expands `seed` with BLAKE3 into the FIPS 203 randomness `d || z || m`,
+/// generates a keypair from `d || z` and encapsulates to it with `m`. The
+/// JSON carries the seed, `d`, `z`, `m`, both keys, the ciphertext and the
+/// shared secret, all hex-encoded, so another implementation can start from
+/// `d`, `z` and `m` directly and compare every output.
+///
+/// Only ML-KEM-768 is supported: ML-DSA-65 signing in this crate goes
+/// through `pqcrypto-dilithium`, which has no seeded key generation.
+///
+/// # Arguments
+/// * `algorithm` - Algorithm to export a vector for ("ml-kem-768")
+/// * `seed` - 32-byte seed
+///
+/// # Returns
+/// JSON test vector
+#[napi]
+pub fn export_test_vector(algorithm: Algorithm, seed: Buffer) -> Result<String> {
+  if !matches!(algorithm, Algorithm::MlKem768) {
+    return Err(Error::from_reason(format!(
+      "Test vector export is not supported for {}",
+      algorithm.as_str()
+    )));
+  }
+  if seed.len() != 32 {
+    return Err(Error::from_reason(format!(
+      "Invalid seed length: expected 32 bytes, got {}",
+      seed.len()
+    )));
+  }
+
+  let mut randomness = [0u8; 96];
+  blake3::Hasher::new_derive_key("qudag-napi test vector seed v1")
+    .update(seed.as_ref())
+    .finalize_xof()
+    .fill(&mut randomness);
+  let run = mlkem768_deterministic_run(&randomness);
+
+  let vector = TestVector {
+    algorithm: algorithm.as_str().to_string(),
+    seed: hex::encode(seed.as_ref()),
+    d: hex::encode(&randomness[0..32]),
+    z: hex::encode(&randomness[32..64]),
+    m: hex::encode(&randomness[64..96]),
+    public_key: hex::encode(run.public_key),
+    secret_key: hex::encode(run.secret_key),
+    ciphertext: hex::encode(run.ciphertext),
+    shared_secret: hex::encode(run.shared_secret),
+  };
+  serde_json::to_string_pretty(&vector)
+    .map_err(|e| Error::from_reason(format!("JSON encoding failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_known_answers_match() {
+    for kat in KNOWN_ANSWERS {
+      assert_eq!(hex::encode(compute(kat)), kat.expected_hex, "KAT {} failed", kat.id);
+    }
+  }
+
+  #[test]
+  fn test_list_test_vectors() {
+    let vectors = list_test_vectors();
+    assert_eq!(vectors.len(), KNOWN_ANSWERS.len());
+    assert!(!vectors.is_empty());
+    for vector in &vectors {
+      assert!(!vector.source.is_empty(), "vector {} has no source", vector.id);
+      assert!(vector.algorithm.parse::<Algorithm>().is_ok());
+    }
+    assert!(vectors.iter().any(|v| v.id == "sha3-256-abc" && v.source.contains("FIPS 202")));
+  }
+
+  #[test]
+  fn test_run_self_tests_detailed() {
+    let results = run_self_tests_detailed();
+    assert_eq!(results.len(), KNOWN_ANSWERS.len());
+    for (result, kat) in results.iter().zip(KNOWN_ANSWERS) {
+      assert_eq!(result.name, kat.id);
+      assert!(result.passed, "self-test {} failed", result.name);
+      assert!(result.duration_ns > 0);
+    }
+  }
+
+  #[test]
+  fn test_module_fingerprint_stable() {
+    let fingerprint = module_fingerprint();
+    assert!(fingerprint.starts_with("qf:"));
+    assert_eq!(fingerprint.len(), 67);
+    assert_eq!(fingerprint, module_fingerprint());
+  }
+
+  #[test]
+  fn test_export_test_vector_reproducible() {
+    let json = export_test_vector(Algorithm::MlKem768, vec![9u8; 32].into()).unwrap();
+    let vector: TestVector = serde_json::from_str(&json).unwrap();
+
+    // Re-exporting from the recorded seed gives the same document
+    let seed = hex::decode(&vector.seed).unwrap();
+    assert_eq!(export_test_vector(Algorithm::MlKem768, seed.into()).unwrap(), json);
+
+    // Re-running from d, z and m alone reproduces every output
+    let mut randomness = hex::decode(&vector.d).unwrap();
+    randomness.extend(hex::decode(&vector.z).unwrap());
+    randomness.extend(hex::decode(&vector.m).unwrap());
+    let run = mlkem768_deterministic_run(&randomness.try_into().unwrap());
+    assert_eq!(hex::encode(run.public_key), vector.public_key);
+    assert_eq!(hex::encode(run.secret_key), vector.secret_key);
+    assert_eq!(hex::encode(run.ciphertext), vector.ciphertext);
+    assert_eq!(hex::encode(run.shared_secret), vector.shared_secret);
+
+    assert!(export_test_vector(Algorithm::MlDsa65, vec![9u8; 32].into()).is_err());
+    assert!(export_test_vector(Algorithm::MlKem768, vec![9u8; 16].into()).is_err());
+  }
+}
@@ -0,0 +1,133 @@
+//! Promise-returning variants of the ML-KEM API
+//!
+//! Each function only copies its arguments on the calling thread and returns
+//! an `AsyncTask`; all validation and cryptography run in `compute` on the
+//! libuv thread pool. Invalid input therefore always surfaces as a rejected
+//! Promise carrying the same error message as the sync function, never as a
+//! synchronous throw at call time.
+
+use napi::bindgen_prelude::*;
+use napi::{Env, Task};
+use napi_derive::napi;
+
+use crate::crypto::{mlkem768_decapsulate, mlkem768_encapsulate, EncapsulatedSecret};
+
+/// Background ML-KEM-768 encapsulation
+pub struct EncapsulateTask {
+  public_key: Vec<u8>,
+}
+
+impl Task for EncapsulateTask {
+  type Output = (Vec<u8>, Vec<u8>);
+  type JsValue = EncapsulatedSecret;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let encapsulated = mlkem768_encapsulate(self.public_key.clone().into())?;
+    Ok((encapsulated.ciphertext.to_vec(), encapsulated.shared_secret.to_vec()))
+  }
+
+  fn resolve(&mut self, _env: Env, (ciphertext, shared_secret): Self::Output) -> Result<Self::JsValue> {
+    Ok(EncapsulatedSecret {
+      ciphertext: ciphertext.into(),
+      shared_secret: shared_secret.into(),
+    })
+  }
+}
+
+/// Background ML-KEM-768 decapsulation
+pub struct DecapsulateTask {
+  ciphertext: Vec<u8>,
+  secret_key: Vec<u8>,
+}
+
+impl Task for DecapsulateTask {
+  type Output = Vec<u8>;
+  type JsValue = Buffer;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let shared_secret = mlkem768_decapsulate(
+      self.ciphertext.clone().into(),
+      self.secret_key.clone().into(),
+    )?;
+    Ok(shared_secret.to_vec())
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output.into())
+  }
+}
+
+/// Encapsulate a shared secret off the main thread
+///
+/// Async counterpart of `mlkem768_encapsulate`. A malformed public key
+/// rejects the returned Promise with the same error as the sync function.
+///
+/// # Arguments
+/// * `public_key` - ML-KEM-768 public key (1184 bytes)
+///
+/// # Returns
+/// Promise of an EncapsulatedSecret
+#[napi]
+pub fn mlkem768_encapsulate_async(public_key: Buffer) -> AsyncTask<EncapsulateTask> {
+  AsyncTask::new(EncapsulateTask {
+    public_key: public_key.to_vec(),
+  })
+}
+
+/// Decapsulate a shared secret off the main thread
+///
+/// Async counterpart of `mlkem768_decapsulate`. Malformed inputs reject the
+/// returned Promise with the same error as the sync function.
+///
+/// # Arguments
+/// * `ciphertext` - ML-KEM-768 ciphertext (1088 bytes)
+/// * `secret_key` - ML-KEM-768 secret key (2400 bytes)
+///
+/// # Returns
+/// Promise of the 32-byte shared secret
+#[napi]
+pub fn mlkem768_decapsulate_async(ciphertext: Buffer, secret_key: Buffer) -> AsyncTask<DecapsulateTask> {
+  AsyncTask::new(DecapsulateTask {
+    ciphertext: ciphertext.to_vec(),
+    secret_key: secret_key.to_vec(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::crypto::mlkem768_generate_keypair;
+
+  #[test]
+  fn test_encapsulate_task_rejects_in_compute() {
+    // Building the task never fails; the error is produced by the worker
+    let mut task = EncapsulateTask {
+      public_key: vec![0u8; 100],
+    };
+    let async_err = task.compute().err().unwrap();
+    let sync_err = mlkem768_encapsulate(vec![0u8; 100].into()).err().unwrap();
+
+    assert_eq!(async_err.reason, sync_err.reason);
+    assert_eq!(async_err.status, sync_err.status);
+  }
+
+  #[test]
+  fn test_tasks_roundtrip() {
+    let keypair = mlkem768_generate_keypair().unwrap();
+    let (ciphertext, shared_secret) = EncapsulateTask {
+      public_key: keypair.public_key.to_vec(),
+    }.compute().unwrap();
+
+    let recovered = DecapsulateTask {
+      ciphertext,
+      secret_key: keypair.secret_key.to_vec(),
+    }.compute().unwrap();
+    assert_eq!(recovered, shared_secret);
+
+    let mut bad = DecapsulateTask {
+      ciphertext: vec![0u8; 1088],
+      secret_key: vec![0u8; 10],
+    };
+    assert!(bad.compute().is_err());
+  }
+}
@@ -2,6 +2,7 @@
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use subtle::{Choice, ConditionallySelectable};
 
 /// Convert hex string to bytes
 #[napi]
@@ -41,3 +42,259 @@ pub fn constant_time_compare(a: Buffer, b: Buffer) -> Result<bool> {
 
   Ok(result == 0)
 }
+
+/// Constant-time select between two buffers
+///
+/// Returns a copy of `a` if `condition` is true and of `b` otherwise, using
+/// `subtle`'s branchless byte selection so the choice doesn't show up in
+/// timing. `condition` itself crosses the JS boundary as a plain boolean, so
+/// keep the code computing it branch-free as well.
+///
+/// # Arguments
+/// * `condition` - Which input to return
+/// * `a` - Returned when `condition` is true
+/// * `b` - Returned when `condition` is false; must be the same length as `a`
+///
+/// # Returns
+/// Copy of the selected buffer
+#[napi]
+pub fn ct_select(condition: bool, a: Buffer, b: Buffer) -> Result<Buffer> {
+  if a.len() != b.len() {
+    return Err(Error::from_reason(format!(
+      "Buffer length mismatch: {} vs {} bytes",
+      a.len(),
+      b.len()
+    )));
+  }
+
+  let choice = Choice::from(condition as u8);
+  let selected: Vec<u8> = a
+    .iter()
+    .zip(b.iter())
+    .map(|(x, y)| u8::conditional_select(y, x, choice))
+    .collect();
+  Ok(selected.into())
+}
+
+/// Check whether a secret is trivially weak, in constant time
+///
+/// A defensive guard against RNG or KEM failures: returns true for an empty
+/// secret or one whose bytes are all identical (including all zeros). The
+/// whole buffer is always scanned. Callers should abort on a true result.
+///
+/// # Arguments
+/// * `secret` - Shared secret or key to check
+///
+/// # Returns
+/// true if the secret is empty or made of a single repeated byte
+#[napi]
+pub fn is_weak_secret(secret: Buffer) -> Result<bool> {
+  let Some(&first) = secret.first() else {
+    return Ok(true);
+  };
+
+  let mut diff = 0u8;
+  for byte in secret.iter() {
+    diff |= byte ^ first;
+  }
+
+  Ok(diff == 0)
+}
+
+/// Check whether a key is an all-zero placeholder
+///
+/// Earlier builds returned zero-filled ML-DSA keys from a stub keygen. The
+/// current keygen is real, but zero-filled keys can still turn up from old
+/// fixtures or uninitialised buffers. Empty buffers count as placeholders.
+///
+/// # Arguments
+/// * `key` - Public or secret key bytes
+///
+/// # Returns
+/// true if every byte is zero
+#[napi]
+pub fn is_stub_key(key: Buffer) -> Result<bool> {
+  let mut acc = 0u8;
+  for byte in key.iter() {
+    acc |= byte;
+  }
+
+  Ok(acc == 0)
+}
+
+/// Constant-time equality check for secret keys
+///
+/// Intended for tests and key-management code: comparing secret keys with
+/// `Buffer.equals` in JS exits at the first differing byte and leaks timing.
+/// Keys of different lengths compare unequal.
+///
+/// # Arguments
+/// * `a` - First secret key
+/// * `b` - Second secret key
+///
+/// # Returns
+/// true if both keys have the same length and bytes
+#[napi]
+pub fn secret_keys_equal(a: Buffer, b: Buffer) -> Result<bool> {
+  constant_time_compare(a, b)
+}
+
+/// Strip trailing whitespace from a key buffer and check its length
+///
+/// Keys read from files often carry a trailing newline. Trailing ASCII
+/// whitespace (space, tab, CR, LF, VT, FF) is only removed when the buffer
+/// is longer than `expected_len`, so a binary key of the right length whose
+/// last byte happens to be 0x0a or 0x20 is returned untouched.
+///
+/// # Arguments
+/// * `buf` - Key bytes as loaded
+/// * `expected_len` - Required key length in bytes
+///
+/// # Returns
+/// The key without the trailing whitespace; errors if the result is not
+/// `expected_len` bytes
+#[napi]
+pub fn normalize_key_buffer(buf: Buffer, expected_len: u32) -> Result<Buffer> {
+  let expected = expected_len as usize;
+  let mut key = buf.as_ref();
+  while key.len() > expected && matches!(key.last(), Some(b' ' | b'\t' | b'\r' | b'\n' | 0x0b | 0x0c)) {
+    key = &key[..key.len() - 1];
+  }
+
+  if key.len() != expected {
+    return Err(Error::from_reason(format!(
+      "Invalid key length: expected {} bytes, got {} (after trimming trailing whitespace)",
+      expected,
+      key.len()
+    )));
+  }
+
+  Ok(key.to_vec().into())
+}
+
+/// Decode one hex character without data-dependent branches
+///
+/// Returns the nibble value, or 0xffff if `c` is not a hex digit.
+fn ct_hex_nibble(c: u8) -> u16 {
+  let c = c as i16;
+  let mut ret: i16 = -1;
+  // '0'..='9'
+  ret += (((0x2f - c) & (c - 0x3a)) >> 8) & (c - 47);
+  // 'A'..='F'
+  ret += (((0x40 - c) & (c - 0x47)) >> 8) & (c - 54);
+  // 'a'..='f'
+  ret += (((0x60 - c) & (c - 0x67)) >> 8) & (c - 86);
+  ret as u16
+}
+
+/// Convert hex string to bytes in constant time
+///
+/// Unlike `hex_to_bytes`, every character is decoded without branching on
+/// its value and invalid characters are only reported after the whole
+/// string has been processed. Use this for hex-encoded secret keys. Only
+/// the string length (and an optional `0x` prefix) may influence timing.
+#[napi]
+pub fn decode_hex_ct(s: String) -> Result<Buffer> {
+  let digits = s.strip_prefix("0x").unwrap_or(&s).as_bytes();
+  if !digits.len().is_multiple_of(2) {
+    return Err(Error::from_reason("Invalid hex string: odd length"));
+  }
+
+  let mut bytes = vec![0u8; digits.len() / 2];
+  let mut invalid = 0u16;
+  for (byte, pair) in bytes.iter_mut().zip(digits.chunks_exact(2)) {
+    let hi = ct_hex_nibble(pair[0]);
+    let lo = ct_hex_nibble(pair[1]);
+    invalid |= (hi | lo) >> 8;
+    *byte = ((hi << 4) | lo) as u8;
+  }
+
+  if invalid != 0 {
+    return Err(Error::from_reason("Invalid hex string: invalid character"));
+  }
+
+  Ok(bytes.into())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_ct_select() {
+    let a: Buffer = vec![1u8; 16].into();
+    let b: Buffer = vec![2u8; 16].into();
+    assert_eq!(ct_select(true, a.clone(), b.clone()).unwrap().as_ref(), a.as_ref());
+    assert_eq!(ct_select(false, a.clone(), b).unwrap().as_ref(), &[2u8; 16]);
+    assert!(ct_select(true, a, vec![2u8; 15].into()).is_err());
+  }
+
+  #[test]
+  fn test_is_weak_secret() {
+    assert!(is_weak_secret(vec![0u8; 32].into()).unwrap());
+    assert!(is_weak_secret(vec![0xffu8; 32].into()).unwrap());
+    assert!(is_weak_secret(Vec::new().into()).unwrap());
+    assert!(!is_weak_secret(random_bytes(32).unwrap()).unwrap());
+  }
+
+  #[test]
+  fn test_is_stub_key() {
+    // Shape of the zero-filled ML-DSA-65 public key the old stub returned
+    assert!(is_stub_key(vec![0u8; 1952].into()).unwrap());
+
+    let real = crate::crypto::mlkem768_generate_keypair().unwrap();
+    assert!(!is_stub_key(real.public_key).unwrap());
+    assert!(!is_stub_key(crate::crypto::mldsa65_generate_keypair().unwrap().public_key).unwrap());
+  }
+
+  #[test]
+  fn test_secret_keys_equal() {
+    let key = crate::crypto::mlkem768_generate_keypair().unwrap().secret_key.to_vec();
+    assert!(secret_keys_equal(key.clone().into(), key.clone().into()).unwrap());
+
+    let mut last_differs = key.clone();
+    *last_differs.last_mut().unwrap() ^= 1;
+    assert!(!secret_keys_equal(key.clone().into(), last_differs.into()).unwrap());
+
+    assert!(!secret_keys_equal(key.clone().into(), key[..2399].to_vec().into()).unwrap());
+  }
+
+  #[test]
+  fn test_decode_hex_ct_valid() {
+    let bytes = decode_hex_ct("00ff10Ab".to_string()).unwrap();
+    assert_eq!(bytes.as_ref(), &[0x00, 0xff, 0x10, 0xab]);
+
+    let all: Vec<u8> = (0..=255).collect();
+    let decoded = decode_hex_ct(format!("0x{}", hex::encode(&all))).unwrap();
+    assert_eq!(decoded.as_ref(), all.as_slice());
+  }
+
+  #[test]
+  fn test_decode_hex_ct_rejects_invalid() {
+    assert!(decode_hex_ct("0g".to_string()).is_err());
+    assert!(decode_hex_ct("zz00".to_string()).is_err());
+    assert!(decode_hex_ct("00:0".to_string()).is_err());
+    assert!(decode_hex_ct("abc".to_string()).is_err());
+  }
+
+  #[test]
+  fn test_normalize_key_buffer() {
+    let key: Vec<u8> = (0..32).collect();
+
+    let mut pasted = key.clone();
+    pasted.extend_from_slice(b"\r\n");
+    assert_eq!(normalize_key_buffer(pasted.into(), 32).unwrap().as_ref(), key.as_slice());
+
+    // A right-length key ending in a newline byte is not trimmed
+    let mut binary = key.clone();
+    binary[31] = b'\n';
+    assert_eq!(normalize_key_buffer(binary.clone().into(), 32).unwrap().as_ref(), binary.as_slice());
+
+    let err = normalize_key_buffer(key[..31].to_vec().into(), 32).err().unwrap();
+    assert!(err.reason.contains("expected 32 bytes, got 31"));
+
+    let mut junk = key;
+    junk.extend_from_slice(b"x\n");
+    assert!(normalize_key_buffer(junk.into(), 32).is_err());
+  }
+}